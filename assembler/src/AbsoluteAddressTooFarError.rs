@@ -0,0 +1,28 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// A 32-bit relative `JMP` or `CALL` was attempted to an absolute address whose displacement exceeded the (inclusive range) i32::MIN to i32::MAX bytes.
+///
+/// Use `jmp_via_register()` or `call_via_register()` instead.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AbsoluteAddressTooFarError
+{
+	/// The absolute address that was jumped to or called.
+	pub target: InstructionPointer,
+	
+	/// The displacement that was computed, relative to the end of the jump or call instruction.
+	pub displacement: isize,
+}
+
+impl Display for AbsoluteAddressTooFarError
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "32-bit JMP or CALL to 0x{:X} was too far with a displacement of {} bytes", self.target, self.displacement)
+	}
+}
+
+impl Error for AbsoluteAddressTooFarError
+{
+}
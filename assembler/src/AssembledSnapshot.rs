@@ -0,0 +1,197 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// A position-independent copy of the bytes emitted by an instruction stream, its labels and the locations still to be patched with them.
+///
+/// Created using `InstructionStream.snapshot()` and restored, at a new address, using `InstructionStream::from_snapshot()`.
+///
+/// Can be persisted using `to_bytes()` and `from_bytes()`, eg to cache generated code between runs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssembledSnapshot
+{
+	bytes: Vec<u8>,
+	label_offsets: Vec<Option<usize>>,
+	offsets_to_replace_labels_with_8_bit_displacements: Vec<(Label, usize)>,
+	offsets_to_replace_labels_with_32_bit_displacements: Vec<(Label, usize)>,
+	emitted_labels: Vec<(Label, usize)>,
+	relaxable_jumps: Vec<(Label, usize, u8)>,
+}
+
+impl AssembledSnapshot
+{
+	const Magic: &'static [u8; 8] = b"ASMSNAP1";
+	
+	const UnlabelledSentinel: u64 = u64::MAX;
+	
+	/// The emitted bytes.
+	///
+	/// Locations written by `emit_label()` are zeroed.
+	#[inline(always)]
+	pub fn bytes(&self) -> &[u8]
+	{
+		&self.bytes
+	}
+	
+	/// Serializes to a little-endian byte representation.
+	pub fn to_bytes(&self) -> Vec<u8>
+	{
+		let mut serialized = Vec::with_capacity(Self::Magic.len() + self.bytes.len());
+		serialized.extend_from_slice(Self::Magic);
+		
+		let mut write = |value: usize| serialized.extend_from_slice(&(value as u64).to_le_bytes());
+		
+		write(self.bytes.len());
+		write(self.label_offsets.len());
+		write(self.offsets_to_replace_labels_with_8_bit_displacements.len());
+		write(self.offsets_to_replace_labels_with_32_bit_displacements.len());
+		write(self.emitted_labels.len());
+		write(self.relaxable_jumps.len());
+		
+		for label_offset in self.label_offsets.iter()
+		{
+			serialized.extend_from_slice(&label_offset.map(|offset| offset as u64).unwrap_or(Self::UnlabelledSentinel).to_le_bytes());
+		}
+		for &(label, offset) in self.offsets_to_replace_labels_with_8_bit_displacements.iter().chain(self.offsets_to_replace_labels_with_32_bit_displacements.iter()).chain(self.emitted_labels.iter())
+		{
+			serialized.extend_from_slice(&(label.0 as u64).to_le_bytes());
+			serialized.extend_from_slice(&(offset as u64).to_le_bytes());
+		}
+		for &(label, offset, short_opcode) in self.relaxable_jumps.iter()
+		{
+			serialized.extend_from_slice(&(label.0 as u64).to_le_bytes());
+			serialized.extend_from_slice(&(offset as u64).to_le_bytes());
+			serialized.push(short_opcode);
+		}
+		serialized.extend_from_slice(&self.bytes);
+		serialized
+	}
+	
+	/// Deserializes from the byte representation created by `to_bytes()`.
+	///
+	/// Returns `None` if `serialized` is not such a representation, or refers to labels or offsets which do not exist.
+	pub fn from_bytes(serialized: &[u8]) -> Option<Self>
+	{
+		if !serialized.starts_with(Self::Magic)
+		{
+			return None
+		}
+		let mut remaining = &serialized[Self::Magic.len() .. ];
+		
+		let read_u8 = |remaining: &mut &[u8]| -> Option<u8>
+		{
+			let (&value, rest) = remaining.split_first()?;
+			*remaining = rest;
+			Some(value)
+		};
+		let read_u64 = |remaining: &mut &[u8]| -> Option<u64>
+		{
+			if remaining.len() < 8
+			{
+				return None
+			}
+			let (value, rest) = remaining.split_at(8);
+			*remaining = rest;
+			let mut le_bytes = [0; 8];
+			le_bytes.copy_from_slice(value);
+			Some(u64::from_le_bytes(le_bytes))
+		};
+		let read_usize = |remaining: &mut &[u8]| read_u64(remaining).map(|value| value as usize);
+		
+		let number_of_bytes = read_usize(&mut remaining)?;
+		let number_of_labels = read_usize(&mut remaining)?;
+		let number_of_8_bit_displacements = read_usize(&mut remaining)?;
+		let number_of_32_bit_displacements = read_usize(&mut remaining)?;
+		let number_of_emitted_labels = read_usize(&mut remaining)?;
+		let number_of_relaxable_jumps = read_usize(&mut remaining)?;
+		
+		let label = |index: usize| if index < number_of_labels
+		{
+			Some(Label(index))
+		}
+		else
+		{
+			None
+		};
+		let offset = |offset: usize, size: usize| if offset.checked_add(size)? <= number_of_bytes
+		{
+			Some(offset)
+		}
+		else
+		{
+			None
+		};
+		
+		let mut label_offsets = Vec::with_capacity(number_of_labels.min(remaining.len()));
+		for _ in 0 .. number_of_labels
+		{
+			let label_offset = read_u64(&mut remaining)?;
+			label_offsets.push(if label_offset == Self::UnlabelledSentinel
+			{
+				None
+			}
+			else
+			{
+				Some(offset(label_offset as usize, 0)?)
+			});
+		}
+		
+		let read_patches = |remaining: &mut &[u8], count: usize, size: usize| -> Option<Vec<(Label, usize)>>
+		{
+			let mut patches = Vec::with_capacity(count.min(remaining.len()));
+			for _ in 0 .. count
+			{
+				let patch_label = label(read_usize(remaining)?)?;
+				let patch_offset = offset(read_usize(remaining)?, size)?;
+				patches.push((patch_label, patch_offset));
+			}
+			Some(patches)
+		};
+		let offsets_to_replace_labels_with_8_bit_displacements = read_patches(&mut remaining, number_of_8_bit_displacements, 1)?;
+		let offsets_to_replace_labels_with_32_bit_displacements = read_patches(&mut remaining, number_of_32_bit_displacements, 4)?;
+		let emitted_labels = read_patches(&mut remaining, number_of_emitted_labels, 8)?;
+		
+		let mut relaxable_jumps = Vec::with_capacity(number_of_relaxable_jumps.min(remaining.len()));
+		for _ in 0 .. number_of_relaxable_jumps
+		{
+			let jump_label = label(read_usize(&mut remaining)?)?;
+			let jump_offset = offset(read_usize(&mut remaining)?, 5)?;
+			let short_opcode = read_u8(&mut remaining)?;
+			relaxable_jumps.push((jump_label, jump_offset, short_opcode));
+		}
+		
+		if remaining.len() != number_of_bytes
+		{
+			return None
+		}
+		
+		Some
+		(
+			Self
+			{
+				bytes: remaining.to_vec(),
+				label_offsets,
+				offsets_to_replace_labels_with_8_bit_displacements,
+				offsets_to_replace_labels_with_32_bit_displacements,
+				emitted_labels,
+				relaxable_jumps,
+			}
+		)
+	}
+	
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	pub(crate) fn hints(&self) -> InstructionStreamHints
+	{
+		let mut hints = InstructionStreamHints
+		{
+			number_of_labels: self.label_offsets.len(),
+			number_of_8_bit_jumps: self.offsets_to_replace_labels_with_8_bit_displacements.len(),
+			number_of_32_bit_jumps: self.offsets_to_replace_labels_with_32_bit_displacements.len(),
+			number_of_emitted_labels: self.emitted_labels.len(),
+			number_of_bytes: self.bytes.len(),
+		};
+		hints.adjust();
+		hints
+	}
+}
@@ -9,22 +9,34 @@ pub(crate) struct ByteEmitter
 	instruction_pointer: InstructionPointer,
 	end_instruction_pointer: InstructionPointer,
 	bookmark: InstructionPointer,
+	writable_offset: usize,
 }
 
 impl ByteEmitter
 {
+	#[cfg(feature = "std")]
 	#[inline(always)]
 	pub(crate) fn new(executable_anonymous_memory_map: & mut ExecutableAnonymousMemoryMap) -> Self
 	{
-		let instruction_pointer = executable_anonymous_memory_map.address as usize;
-		let length = executable_anonymous_memory_map.length;
-		
+		Self::with_memory(executable_anonymous_memory_map.address as usize, executable_anonymous_memory_map.length, executable_anonymous_memory_map.writable_offset())
+	}
+	
+	#[inline(always)]
+	pub(crate) fn for_buffer(buffer: &mut [u8]) -> Self
+	{
+		Self::with_memory(buffer.as_mut_ptr() as usize, buffer.len(), 0)
+	}
+	
+	#[inline(always)]
+	fn with_memory(instruction_pointer: InstructionPointer, length: usize, writable_offset: usize) -> Self
+	{
 		Self
 		{
 			start_instruction_pointer: instruction_pointer,
 			instruction_pointer,
 			end_instruction_pointer: instruction_pointer + length,
 			bookmark: instruction_pointer,
+			writable_offset,
 		}
 	}
 	
@@ -34,6 +46,19 @@ impl ByteEmitter
 		self.end_instruction_pointer - self.instruction_pointer
 	}
 	
+	#[inline(always)]
+	pub(crate) fn capacity(&self) -> usize
+	{
+		self.end_instruction_pointer - self.start_instruction_pointer
+	}
+	
+	#[inline(always)]
+	pub(crate) fn reset(&mut self)
+	{
+		self.instruction_pointer = self.start_instruction_pointer;
+		self.bookmark = self.start_instruction_pointer
+	}
+	
 	#[inline(always)]
 	pub(crate) fn store_bookmark(&mut self)
 	{
@@ -76,7 +101,7 @@ impl ByteEmitter
 		debug_assert!(self.instruction_pointer + length <= self.end_instruction_pointer, "Not enough space to emit '{}' byte(s)", length);
 		unsafe
 		{
-			copy_nonoverlapping(bytes.as_ptr(), self.instruction_pointer as *mut u8, length);
+			copy_nonoverlapping(bytes.as_ptr(), self.writable(self.instruction_pointer) as *mut u8, length);
 			self.instruction_pointer += length;
 		}
 	}
@@ -123,18 +148,18 @@ impl ByteEmitter
 	}
 	
 	#[inline(always)]
-	pub(crate) fn insert_8_bit_effective_address_displacement(&mut self, insert_at_instruction_pointer: InstructionPointer, target_instruction_pointer: InstructionPointer) -> ShortJmpResult
+	pub(crate) fn insert_8_bit_effective_address_displacement(&mut self, label: Label, insert_at_instruction_pointer: InstructionPointer, target_instruction_pointer: InstructionPointer) -> ShortJmpResult
 	{
 		let end_of_jmp_instruction = (insert_at_instruction_pointer + 1) as isize;
 		
 		let displacement = (target_instruction_pointer as isize) - end_of_jmp_instruction;
 		
-		const Minimum: isize = ::std::i8::MIN as isize;
-		const Maximum: isize = ::std::i8::MAX as isize;
+		const Minimum: isize = i8::MIN as isize;
+		const Maximum: isize = i8::MAX as isize;
 		
 		if unlikely!(displacement < Minimum || displacement > Maximum)
 		{
-			return Err(())
+			return Err(ShortJumpTooFarError { label, displacement })
 		}
 		
 		self.emit_u8_at(displacement as u8, insert_at_instruction_pointer);
@@ -149,8 +174,8 @@ impl ByteEmitter
 		
 		let displacement = (target_instruction_pointer as isize) - end_of_jmp_instruction;
 		
-		const Minimum: isize = ::std::i32::MIN as isize;
-		const Maximum: isize = ::std::i32::MAX as isize;
+		const Minimum: isize = i32::MIN as isize;
+		const Maximum: isize = i32::MAX as isize;
 		
 		if unlikely!(displacement < Minimum || displacement > Maximum)
 		{
@@ -165,19 +190,19 @@ impl ByteEmitter
 	#[inline(always)]
 	pub(crate) fn emit_u8_at(&mut self, emit: u8, at: InstructionPointer)
 	{
-		unsafe { *(at as *mut u8) = emit };
+		unsafe { *(self.writable(at) as *mut u8) = emit };
 	}
 	
 	#[inline(always)]
 	pub(crate) fn emit_u32_at(&mut self, emit: u32, at: InstructionPointer)
 	{
-		unsafe { *(at as *mut u32) = emit };
+		unsafe { (self.writable(at) as *mut [u8; 4]).write_unaligned(emit.to_le_bytes()) };
 	}
 	
 	#[inline(always)]
 	pub(crate) fn emit_u64_at(&mut self, emit: u64, at: InstructionPointer)
 	{
-		unsafe { *(at as *mut u64) = emit };
+		unsafe { (self.writable(at) as *mut [u8; 8]).write_unaligned(emit.to_le_bytes()) };
 	}
 	
 	#[inline(always)]
@@ -185,7 +210,7 @@ impl ByteEmitter
 	{
 		const Size: usize = 1;
 		debug_assert!(self.instruction_pointer + Size <= self.end_instruction_pointer, "Not enough space to emit an u8");
-		unsafe { *(self.instruction_pointer as *mut u8) = emit };
+		unsafe { *(self.writable(self.instruction_pointer) as *mut u8) = emit };
 		self.instruction_pointer += Size;
 	}
 	
@@ -194,7 +219,7 @@ impl ByteEmitter
 	{
 		const Size: usize = 2;
 		debug_assert!(self.instruction_pointer + Size <= self.end_instruction_pointer, "Not enough space to emit an u16");
-		unsafe { *(self.instruction_pointer as *mut u16) = emit.to_le() };
+		unsafe { (self.writable(self.instruction_pointer) as *mut [u8; 2]).write_unaligned(emit.to_le_bytes()) };
 		self.instruction_pointer += Size;
 	}
 	
@@ -203,7 +228,7 @@ impl ByteEmitter
 	{
 		const Size: usize = 4;
 		debug_assert!(self.instruction_pointer + Size <= self.end_instruction_pointer, "Not enough space to emit an u32");
-		unsafe { *(self.instruction_pointer as *mut u32) = emit.to_le() };
+		unsafe { (self.writable(self.instruction_pointer) as *mut [u8; 4]).write_unaligned(emit.to_le_bytes()) };
 		self.instruction_pointer += Size;
 	}
 	
@@ -212,7 +237,7 @@ impl ByteEmitter
 	{
 		const Size: usize = 8;
 		debug_assert!(self.instruction_pointer + Size <= self.end_instruction_pointer, "Not enough space to emit an u64");
-		unsafe { *(self.instruction_pointer as *mut u64) = emit.to_le() };
+		unsafe { (self.writable(self.instruction_pointer) as *mut [u8; 8]).write_unaligned(emit.to_le_bytes()) };
 		self.instruction_pointer += Size;
 	}
 	
@@ -221,7 +246,7 @@ impl ByteEmitter
 	{
 		const Size: usize = 16;
 		debug_assert!(self.instruction_pointer + Size <= self.end_instruction_pointer, "Not enough space to emit an u128");
-		unsafe { *(self.instruction_pointer as *mut u128) = emit.to_le() };
+		unsafe { (self.writable(self.instruction_pointer) as *mut [u8; 16]).write_unaligned(emit.to_le_bytes()) };
 		self.instruction_pointer += Size;
 	}
 	
@@ -300,6 +325,13 @@ impl ByteEmitter
 		}
 	}
 	
+	/// Where to write the byte at `instruction_pointer`; differs from `instruction_pointer` for dual mappings.
+	#[inline(always)]
+	pub(crate) fn writable(&self, instruction_pointer: InstructionPointer) -> InstructionPointer
+	{
+		instruction_pointer.wrapping_add(self.writable_offset)
+	}
+	
 	#[inline(always)]
 	fn emit_nop(&mut self)
 	{
@@ -0,0 +1,28 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// The error of `try_emit_bytes()`.
+///
+/// There was not enough space left in the buffer or executable anonymous memory map backing an instruction stream, and it could not be grown.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CapacityExceededError
+{
+	/// The capacity, in bytes, of the buffer or executable anonymous memory map.
+	pub capacity: usize,
+	
+	/// The number of bytes that did not fit.
+	pub length: usize,
+}
+
+impl Display for CapacityExceededError
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "instruction stream exceeded mapped capacity of {} bytes (when emitting {} bytes)", self.capacity, self.length)
+	}
+}
+
+impl Error for CapacityExceededError
+{
+}
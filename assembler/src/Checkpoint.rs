@@ -0,0 +1,24 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// The state of an instruction stream at a point in time, created using `InstructionStream.checkpoint()` and restored using `InstructionStream.rollback_to()`.
+///
+/// Checkpoints can be nested; rolling back to a checkpoint invalidates any checkpoints created after it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Checkpoint
+{
+	section: SectionId,
+	instruction_pointer: InstructionPointer,
+	start_of_last_instruction: InstructionPointer,
+	number_of_labels: usize,
+	number_of_attached_labels: usize,
+	number_of_8_bit_jumps: usize,
+	number_of_32_bit_jumps: usize,
+	number_of_emitted_labels: usize,
+	number_of_relaxable_jumps: usize,
+	number_of_position_dependent_locations: usize,
+	number_of_annotations: usize,
+	stack_delta: isize,
+	frame_pointer_stack_delta: isize,
+}
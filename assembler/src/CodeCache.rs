@@ -0,0 +1,68 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// A cache of finished, executable code, shared between threads.
+///
+/// Worker threads emit code into their own `ExecutableAnonymousMemoryMap`, `finish()` it and then `publish()` the map, which is kept alive until both the cache and every `CodeCacheFunction` pointing into it have been dropped.
+///
+/// Once published, a map is only ever readable and executable; it can not be written again (for a dual mapping, the writable alias is unmapped).
+///
+/// ## Memory Ordering
+///
+/// `publish()` issues a `Release` fence after making the map executable.
+/// A thread calling a `CodeCacheFunction` must have obtained it in a way that synchronizes with (happens after) that fence, eg by receiving it over a channel, reading it from under a `Mutex` or loading a pointer to it with `Acquire` ordering; all of these give the required `Acquire` half.
+/// Handles received with `Relaxed` loads must be preceded by an explicit `fence(Ordering::Acquire)`.
+///
+/// The code is freshly written and has never been executed by the calling thread, so no serializing instruction is needed before the first call; see `ExecutableAnonymousMemoryMap.serialize_instruction_cache()` for code which is patched later.
+#[derive(Debug, Default)]
+pub struct CodeCache
+{
+	executable_anonymous_memory_maps: Mutex<Vec<Arc<PublishedExecutableAnonymousMemoryMap>>>,
+}
+
+impl CodeCache
+{
+	/// Creates a new, empty instance.
+	#[inline(always)]
+	pub fn new() -> Self
+	{
+		Self::default()
+	}
+	
+	/// Takes ownership of `executable_anonymous_memory_map`, which should contain code finished with `InstructionStream.finish()` (or its variants), makes it read-only and executable, and returns a function pointer to the code at `entry_offset` bytes from the start of the map.
+	///
+	/// Use `CodeCacheFunction.function_at()` to get function pointers to other entry points in the same map.
+	///
+	/// # Safety
+	///
+	/// `F` must be an `unsafe extern "C" fn` type, eg `unsafe extern "C" fn(u64) -> u64`, whose signature matches the code at `entry_offset`; it is not possible to check this.
+	/// A safe `fn` type would let safe code, on any thread, call the emitted code.
+	/// A type which is not the size of a pointer fails to compile.
+	#[inline(always)]
+	pub unsafe fn publish<F: Copy + Send + Sync>(&self, executable_anonymous_memory_map: ExecutableAnonymousMemoryMap, entry_offset: usize) -> CodeCacheFunction<F>
+	{
+		debug_assert!(entry_offset < executable_anonymous_memory_map.length, "entry_offset '{}' is outside of the map", entry_offset);
+		
+		let executable_anonymous_memory_map = Arc::new(PublishedExecutableAnonymousMemoryMap::new(executable_anonymous_memory_map));
+		fence(Ordering::Release);
+		
+		self.executable_anonymous_memory_maps.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(executable_anonymous_memory_map.clone());
+		
+		unsafe { CodeCacheFunction::new(executable_anonymous_memory_map, entry_offset) }
+	}
+	
+	/// The number of memory maps published.
+	#[inline(always)]
+	pub fn len(&self) -> usize
+	{
+		self.executable_anonymous_memory_maps.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).len()
+	}
+	
+	/// Whether no memory maps have been published.
+	#[inline(always)]
+	pub fn is_empty(&self) -> bool
+	{
+		self.len() == 0
+	}
+}
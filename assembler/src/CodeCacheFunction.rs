@@ -0,0 +1,67 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// A function pointer to code published in a `CodeCache`, which can be sent to and called from any thread.
+///
+/// Created using `CodeCache.publish()`.
+///
+/// Keeps the memory map the code is in alive, so the function pointer is valid for as long as this is.
+///
+/// Dereferences to the function pointer `F`, eg `unsafe { (*code_cache_function)() }`.
+#[derive(Debug, Clone)]
+pub struct CodeCacheFunction<F: Copy + Send + Sync>
+{
+	function_pointer: F,
+	executable_anonymous_memory_map: Arc<PublishedExecutableAnonymousMemoryMap>,
+}
+
+impl<F: Copy + Send + Sync> Deref for CodeCacheFunction<F>
+{
+	type Target = F;
+	
+	#[inline(always)]
+	fn deref(&self) -> &Self::Target
+	{
+		&self.function_pointer
+	}
+}
+
+impl<F: Copy + Send + Sync> CodeCacheFunction<F>
+{
+	#[inline(always)]
+	pub(crate) unsafe fn new(executable_anonymous_memory_map: Arc<PublishedExecutableAnonymousMemoryMap>, entry_offset: usize) -> Self
+	{
+		const { assert!(size_of::<F>() == size_of::<InstructionPointer>(), "F is not a function pointer") };
+		
+		let entry_instruction_pointer = executable_anonymous_memory_map.address as InstructionPointer + entry_offset;
+		
+		Self
+		{
+			function_pointer: unsafe { transmute_copy(&entry_instruction_pointer) },
+			executable_anonymous_memory_map,
+		}
+	}
+	
+	/// The function pointer.
+	///
+	/// Only valid for as long as this `CodeCacheFunction` (or a clone of it) is; do not let the copy escape.
+	#[inline(always)]
+	pub fn function_pointer(&self) -> F
+	{
+		self.function_pointer
+	}
+	
+	/// A function pointer to the code at `entry_offset` bytes from the start of the same memory map, eg for another function compiled into the same map.
+	///
+	/// # Safety
+	///
+	/// As for `CodeCache.publish()`, `G` must be an `unsafe extern "C" fn` type whose signature matches the code at `entry_offset`.
+	#[inline(always)]
+	pub unsafe fn function_at<G: Copy + Send + Sync>(&self, entry_offset: usize) -> CodeCacheFunction<G>
+	{
+		debug_assert!(entry_offset < self.executable_anonymous_memory_map.length, "entry_offset '{}' is outside of the map", entry_offset);
+		
+		unsafe { CodeCacheFunction::new(self.executable_anonymous_memory_map.clone(), entry_offset) }
+	}
+}
@@ -2,6 +2,14 @@
 // Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
 
 
+/// A displacement or immediate, emitted little-endian.
+///
+/// 8-bit displacements are used by short `JMP`, `Jcc`, `JrCXZ` and `LOOPcc` (`rel8`) and by `disp8` memory operands.
+/// 16-bit displacements are used by `RET imm16` and `ENTER`; `rel16` jumps are not supported in long mode.
+/// 32-bit displacements are used by near `JMP`, `Jcc` and `CALL` (`rel32`), by `disp32` memory operands and by `RIP`-relative addressing.
+/// 64-bit displacements are used by `moffs` memory offsets and `MOV r64, imm64`.
+///
+/// Signed displacements are emitted in two's complement, so are sign extended by the processor.
 pub(crate) trait Displacement
 {
 	#[inline(always)]
@@ -43,3 +51,39 @@ impl Displacement for u64
 		byte_emitter.emit_u64(self)
 	}
 }
+
+impl Displacement for i8
+{
+	#[inline(always)]
+	fn emit(self, byte_emitter: &mut ByteEmitter)
+	{
+		byte_emitter.emit_u8(self as u8)
+	}
+}
+
+impl Displacement for i16
+{
+	#[inline(always)]
+	fn emit(self, byte_emitter: &mut ByteEmitter)
+	{
+		byte_emitter.emit_u16(self as u16)
+	}
+}
+
+impl Displacement for i32
+{
+	#[inline(always)]
+	fn emit(self, byte_emitter: &mut ByteEmitter)
+	{
+		byte_emitter.emit_u32(self as u32)
+	}
+}
+
+impl Displacement for i64
+{
+	#[inline(always)]
+	fn emit(self, byte_emitter: &mut ByteEmitter)
+	{
+		byte_emitter.emit_u64(self as u64)
+	}
+}
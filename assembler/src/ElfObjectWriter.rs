@@ -0,0 +1,189 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// Writes a relocatable x86-64 ELF object file containing a single `.text` section.
+///
+/// See the System V Application Binary Interface, AMD64 Architecture Processor Supplement, Section 4.
+#[derive(Debug)]
+pub(crate) struct ElfObjectWriter<'a>
+{
+	text: &'a [u8],
+	symbols: &'a [(String, usize)],
+	relocations: &'a [Relocation],
+}
+
+impl<'a> ElfObjectWriter<'a>
+{
+	const ElfHeaderSize: usize = 64;
+	
+	const SectionHeaderSize: usize = 64;
+	
+	const SymbolSize: usize = 24;
+	
+	const RelocationWithAddendSize: usize = 24;
+	
+	const TextAlignment: usize = 16;
+	
+	const SHT_PROGBITS: u32 = 1;
+	
+	const SHT_SYMTAB: u32 = 2;
+	
+	const SHT_STRTAB: u32 = 3;
+	
+	const SHT_RELA: u32 = 4;
+	
+	const SHF_ALLOC: u64 = 0x02;
+	
+	const SHF_EXECINSTR: u64 = 0x04;
+	
+	const SHF_INFO_LINK: u64 = 0x40;
+	
+	const TextSectionIndex: u16 = 1;
+	
+	const SymbolTableSectionIndex: u32 = 3;
+	
+	const StringTableSectionIndex: u32 = 4;
+	
+	const SectionHeaderStringTableSectionIndex: u16 = 6;
+	
+	const NumberOfSections: u16 = 7;
+	
+	/// The null symbol and the `.text` section symbol.
+	const NumberOfLocalSymbols: u32 = 2;
+	
+	const TextSectionSymbolIndex: u64 = 1;
+	
+	const STB_GLOBAL: u8 = 1;
+	
+	const STT_FUNC: u8 = 2;
+	
+	const STT_SECTION: u8 = 3;
+	
+	const R_X86_64_64: u64 = 1;
+	
+	#[inline(always)]
+	pub(crate) fn new(text: &'a [u8], symbols: &'a [(String, usize)], relocations: &'a [Relocation]) -> Self
+	{
+		Self
+		{
+			text,
+			symbols,
+			relocations,
+		}
+	}
+	
+	/// Relocations are made against the `.text` section symbol, so they need no symbol table entries of their own.
+	pub(crate) fn write(&self) -> Vec<u8>
+	{
+		let mut string_table = vec![0];
+		let mut symbol_table = vec![0; Self::SymbolSize];
+		Self::write_symbol(&mut symbol_table, 0, Self::STT_SECTION, Self::TextSectionIndex, 0);
+		for &(ref name, offset) in self.symbols.iter()
+		{
+			let name_index = string_table.len() as u32;
+			string_table.extend_from_slice(name.as_bytes());
+			string_table.push(0);
+			Self::write_symbol(&mut symbol_table, name_index, (Self::STB_GLOBAL << 4) | Self::STT_FUNC, Self::TextSectionIndex, offset as u64);
+		}
+		
+		let mut relocation_table = Vec::with_capacity(self.relocations.len() * Self::RelocationWithAddendSize);
+		for relocation in self.relocations.iter()
+		{
+			let relocation_type = match relocation.kind
+			{
+				RelocationKind::Absolute64Bit => Self::R_X86_64_64,
+			};
+			relocation_table.extend_from_slice(&(relocation.offset as u64).to_le_bytes());
+			relocation_table.extend_from_slice(&((Self::TextSectionSymbolIndex << 32) | relocation_type).to_le_bytes());
+			relocation_table.extend_from_slice(&(relocation.target_offset as i64).to_le_bytes());
+		}
+		
+		let mut section_header_string_table = vec![0];
+		let mut section_name = |name: &str|
+		{
+			let index = section_header_string_table.len() as u32;
+			section_header_string_table.extend_from_slice(name.as_bytes());
+			section_header_string_table.push(0);
+			index
+		};
+		let text_name = section_name(".text");
+		let relocation_table_name = section_name(".rela.text");
+		let symbol_table_name = section_name(".symtab");
+		let string_table_name = section_name(".strtab");
+		let note_gnu_stack_name = section_name(".note.GNU-stack");
+		let section_header_string_table_name = section_name(".shstrtab");
+		
+		let text_offset = Self::ElfHeaderSize.next_multiple_of(Self::TextAlignment);
+		let relocation_table_offset = (text_offset + self.text.len()).next_multiple_of(8);
+		let symbol_table_offset = relocation_table_offset + relocation_table.len();
+		let string_table_offset = symbol_table_offset + symbol_table.len();
+		let section_header_string_table_offset = string_table_offset + string_table.len();
+		let section_headers_offset = (section_header_string_table_offset + section_header_string_table.len()).next_multiple_of(8);
+		
+		let mut object = Vec::with_capacity(section_headers_offset + (Self::NumberOfSections as usize) * Self::SectionHeaderSize);
+		
+		object.extend_from_slice(b"\x7FELF");
+		object.extend_from_slice(&[2, 1, 1, 0]);
+		object.resize(16, 0);
+		object.extend_from_slice(&1u16.to_le_bytes());
+		object.extend_from_slice(&62u16.to_le_bytes());
+		object.extend_from_slice(&1u32.to_le_bytes());
+		object.extend_from_slice(&0u64.to_le_bytes());
+		object.extend_from_slice(&0u64.to_le_bytes());
+		object.extend_from_slice(&(section_headers_offset as u64).to_le_bytes());
+		object.extend_from_slice(&0u32.to_le_bytes());
+		object.extend_from_slice(&(Self::ElfHeaderSize as u16).to_le_bytes());
+		object.extend_from_slice(&0u16.to_le_bytes());
+		object.extend_from_slice(&0u16.to_le_bytes());
+		object.extend_from_slice(&(Self::SectionHeaderSize as u16).to_le_bytes());
+		object.extend_from_slice(&Self::NumberOfSections.to_le_bytes());
+		object.extend_from_slice(&Self::SectionHeaderStringTableSectionIndex.to_le_bytes());
+		
+		object.resize(text_offset, 0);
+		object.extend_from_slice(self.text);
+		object.resize(relocation_table_offset, 0);
+		object.extend_from_slice(&relocation_table);
+		object.extend_from_slice(&symbol_table);
+		object.extend_from_slice(&string_table);
+		object.extend_from_slice(&section_header_string_table);
+		object.resize(section_headers_offset, 0);
+		
+		object.resize(section_headers_offset + Self::SectionHeaderSize, 0);
+		Self::write_section_header(&mut object, text_name, Self::SHT_PROGBITS, Self::SHF_ALLOC | Self::SHF_EXECINSTR, text_offset, self.text.len(), 0, 0, Self::TextAlignment, 0);
+		Self::write_section_header(&mut object, relocation_table_name, Self::SHT_RELA, Self::SHF_INFO_LINK, relocation_table_offset, relocation_table.len(), Self::SymbolTableSectionIndex, Self::TextSectionIndex as u32, 8, Self::RelocationWithAddendSize);
+		Self::write_section_header(&mut object, symbol_table_name, Self::SHT_SYMTAB, 0, symbol_table_offset, symbol_table.len(), Self::StringTableSectionIndex, Self::NumberOfLocalSymbols, 8, Self::SymbolSize);
+		Self::write_section_header(&mut object, string_table_name, Self::SHT_STRTAB, 0, string_table_offset, string_table.len(), 0, 0, 1, 0);
+		Self::write_section_header(&mut object, note_gnu_stack_name, Self::SHT_PROGBITS, 0, section_header_string_table_offset, 0, 0, 0, 1, 0);
+		Self::write_section_header(&mut object, section_header_string_table_name, Self::SHT_STRTAB, 0, section_header_string_table_offset, section_header_string_table.len(), 0, 0, 1, 0);
+		
+		object
+	}
+	
+	#[inline(always)]
+	fn write_symbol(symbol_table: &mut Vec<u8>, name_index: u32, information: u8, section_index: u16, value: u64)
+	{
+		symbol_table.extend_from_slice(&name_index.to_le_bytes());
+		symbol_table.push(information);
+		symbol_table.push(0);
+		symbol_table.extend_from_slice(&section_index.to_le_bytes());
+		symbol_table.extend_from_slice(&value.to_le_bytes());
+		symbol_table.extend_from_slice(&0u64.to_le_bytes());
+	}
+	
+	#[allow(clippy::too_many_arguments)]
+	#[inline(always)]
+	fn write_section_header(object: &mut Vec<u8>, name_index: u32, section_type: u32, flags: u64, offset: usize, size: usize, link: u32, information: u32, alignment: usize, entry_size: usize)
+	{
+		object.extend_from_slice(&name_index.to_le_bytes());
+		object.extend_from_slice(&section_type.to_le_bytes());
+		object.extend_from_slice(&flags.to_le_bytes());
+		object.extend_from_slice(&0u64.to_le_bytes());
+		object.extend_from_slice(&(offset as u64).to_le_bytes());
+		object.extend_from_slice(&(size as u64).to_le_bytes());
+		object.extend_from_slice(&link.to_le_bytes());
+		object.extend_from_slice(&information.to_le_bytes());
+		object.extend_from_slice(&(alignment as u64).to_le_bytes());
+		object.extend_from_slice(&(entry_size as u64).to_le_bytes());
+	}
+}
@@ -7,8 +7,10 @@
 pub struct ExecutableAnonymousMemoryMap
 {
 	address: *mut u8,
+	writable_address: *mut u8,
 	length: usize,
 	ignore_mlock_failure: bool,
+	growth_policy: GrowthPolicy,
 }
 
 impl Drop for ExecutableAnonymousMemoryMap
@@ -17,6 +19,11 @@ impl Drop for ExecutableAnonymousMemoryMap
 	fn drop(&mut self)
 	{
 		unsafe { munmap(self.address as *mut _, self.length) };
+		
+		if self.is_dual_mapping()
+		{
+			unsafe { munmap(self.writable_address as *mut _, self.length) };
+		}
 	}
 }
 
@@ -26,12 +33,12 @@ impl ExecutableAnonymousMemoryMap
 	///
 	/// `length` is rounded up to the nearest power of two, and is floored at the smallest page size (4Kb).
 	///
-	/// On Linux, `allocate_in_first_2Gb` should ideally be `true`.
+	/// On Linux, `allocate_in_first_2Gb` should ideally be `true`; it is ignored on other platforms and when the host is not x86-64.
 	///
 	/// On Linux, there are historic bugs in `mlock` which may require `ignore_mlock_failure` to be `true`.
 	///
 	/// Memory is created using an anonymous, shared mmap with no access rights (not even read) which is then locked (`mlock`'d).
-	#[cfg_attr(not(any(target_os = "android", target_os = "linux")), allow(unused_variables))]
+	#[cfg_attr(not(all(any(target_os = "android", target_os = "linux"), target_arch = "x86_64")), allow(unused_variables))]
 	#[inline(always)]
 	pub fn new(length: usize, allocate_in_first_2Gb: bool, ignore_mlock_failure: bool) -> Result<Self, ExecutableAnonymousMemoryMapCreationError>
 	{
@@ -53,7 +60,7 @@ impl ExecutableAnonymousMemoryMap
 		
 		let flags =
 		{
-			#[cfg(any(target_os = "android", target_os = "linux"))]
+			#[cfg(all(any(target_os = "android", target_os = "linux"), target_arch = "x86_64"))]
 			{
 				let mut flags = MAP_ANON | MAP_SHARED;
 				if allocate_in_first_2Gb
@@ -63,7 +70,7 @@ impl ExecutableAnonymousMemoryMap
 				flags
 			}
 			
-			#[cfg(not(any(target_os = "android", target_os = "linux")))]
+			#[cfg(not(all(any(target_os = "android", target_os = "linux"), target_arch = "x86_64")))]
 			{
 				MAP_ANON | MAP_SHARED
 			}
@@ -77,7 +84,7 @@ impl ExecutableAnonymousMemoryMap
 		else
 		{
 			let address = result;
-			let result = unsafe { mlock(address, length) };
+			let result = unsafe { mlock(address, aligned_length) };
 			if unlikely!(!ignore_mlock_failure && result != 0)
 			{
 				if likely!(result == -1)
@@ -95,13 +102,176 @@ impl ExecutableAnonymousMemoryMap
 				Self
 				{
 					address: address as *mut _,
-					length,
+					writable_address: address as *mut _,
+					length: aligned_length,
 					ignore_mlock_failure,
+					growth_policy: GrowthPolicy::default(),
 				}
 			)
 		}
 	}
 	
+	/// Create a new instance which maps the same memory twice, once readable and writable and once readable and executable, so that no memory is ever both writable and executable.
+	///
+	/// This is for hardened systems which forbid memory from being made executable after being writable (eg those enforcing W^X).
+	/// Code is emitted through the writable alias, but labels, function pointers and `instruction_pointer()` use the executable alias.
+	/// After `finish()`, code can still be patched through `writable_alias()`.
+	///
+	/// `length` is rounded up as for `new()`; unlike `new()`, the memory can not grow, so `length` must be large enough for all the code to be emitted.
+	///
+	/// On Linux, there are historic bugs in `mlock` which may require `ignore_mlock_failure` to be `true`.
+	///
+	/// Memory is created using `memfd_create`, mapped twice with shared mmaps and then locked (`mlock`'d).
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn new_dual_mapping(length: usize, ignore_mlock_failure: bool) -> Result<Self, ExecutableAnonymousMemoryMapCreationError>
+	{
+		use self::ExecutableAnonymousMemoryMapCreationError::*;
+		
+		const PageSize: usize = 4096;
+		const NoOffset: i64 = 0;
+		
+		let aligned_length = if length < PageSize
+		{
+			PageSize
+		}
+		else
+		{
+			length.next_power_of_two()
+		};
+		
+		let file_descriptor = unsafe { memfd_create(b"assembler\0".as_ptr() as *const c_char, MFD_CLOEXEC) };
+		if unlikely!(file_descriptor == -1)
+		{
+			return Err(SharedMemoryFailed(io::Error::last_os_error(), aligned_length))
+		}
+		
+		let map = |protection_flags: i32| unsafe { mmap(null_mut(), aligned_length, protection_flags, MAP_SHARED, file_descriptor, NoOffset) };
+		
+		let result = if unlikely!(unsafe { ftruncate(file_descriptor, aligned_length as off_t) } != 0)
+		{
+			Err(SharedMemoryFailed(io::Error::last_os_error(), aligned_length))
+		}
+		else
+		{
+			let executable_address = map(PROT_READ | PROT_EXEC);
+			if unlikely!(executable_address == MAP_FAILED)
+			{
+				Err(MMapFailed(io::Error::last_os_error(), aligned_length))
+			}
+			else
+			{
+				let writable_address = map(PROT_READ | PROT_WRITE);
+				if unlikely!(writable_address == MAP_FAILED)
+				{
+					let error = io::Error::last_os_error();
+					unsafe { munmap(executable_address, aligned_length) };
+					Err(MMapFailed(error, aligned_length))
+				}
+				else
+				{
+					Ok
+					(
+						Self
+						{
+							address: executable_address as *mut _,
+							writable_address: writable_address as *mut _,
+							length: aligned_length,
+							ignore_mlock_failure,
+							growth_policy: GrowthPolicy::default(),
+						}
+					)
+				}
+			}
+		};
+		
+		unsafe { close(file_descriptor) };
+		
+		let this = result?;
+		
+		let result = unsafe { mlock(this.writable_address as *mut _, aligned_length) };
+		if unlikely!(!ignore_mlock_failure && result != 0)
+		{
+			if likely!(result == -1)
+			{
+				return Err(MLockFailed(io::Error::last_os_error(), aligned_length))
+			}
+			else
+			{
+				panic!("Unexpected result code from mlock (new_dual_mapping) '{}'", result)
+			}
+		}
+		
+		Ok(this)
+	}
+	
+	/// The length of the map, in bytes.
+	///
+	/// This is the length passed to `new()` or `new_dual_mapping()` rounded up, plus any growth.
+	#[inline(always)]
+	pub fn length(&self) -> usize
+	{
+		self.length
+	}
+	
+	/// Sets how this map grows when an instruction stream runs out of space; the default is `GrowthPolicy::Double`.
+	///
+	/// Has no effect for a dual mapping, which can not grow.
+	#[inline(always)]
+	pub fn set_growth_policy(&mut self, growth_policy: GrowthPolicy)
+	{
+		self.growth_policy = growth_policy
+	}
+	
+	/// Is this a dual mapping created with `new_dual_mapping()`?
+	#[inline(always)]
+	pub fn is_dual_mapping(&self) -> bool
+	{
+		self.writable_address != self.address
+	}
+	
+	/// The address through which the code at `instruction_pointer` can be written.
+	///
+	/// For a dual mapping, this is in the writable alias, and can be used to patch code after `finish()`.
+	/// Otherwise, this is just `instruction_pointer`, which is not writable after `finish()`.
+	#[inline(always)]
+	pub fn writable_alias(&self, instruction_pointer: InstructionPointer) -> *mut u8
+	{
+		debug_assert!(instruction_pointer >= self.address as usize && instruction_pointer < self.address as usize + self.length, "instruction_pointer is not in this map");
+		
+		instruction_pointer.wrapping_add(self.writable_offset()) as *mut u8
+	}
+	
+	/// Makes code written to this map visible to instruction fetch on the current thread by executing a serializing instruction (`CPUID`).
+	///
+	/// x86-64 keeps instruction caches coherent with data writes, but a thread may still execute stale, already fetched or decoded instructions; this is only a problem for code which is modified after it has been executed (including under some hypervisors).
+	///
+	/// Called by `InstructionStream.finish()` (and its variants); call it again after patching code, on each thread that will execute the patched code.
+	#[cfg(target_arch = "x86_64")]
+	#[inline(always)]
+	pub fn serialize_instruction_cache(&self)
+	{
+		__cpuid(0);
+	}
+	
+	/// Unmaps the writable alias of a dual mapping, so the code can no longer be written; does nothing otherwise.
+	#[cfg(target_arch = "x86_64")]
+	#[inline(always)]
+	pub(crate) fn remove_writable_alias(&mut self)
+	{
+		if self.is_dual_mapping()
+		{
+			unsafe { munmap(self.writable_address as *mut _, self.length) };
+			self.writable_address = self.address;
+		}
+	}
+	
+	#[inline(always)]
+	pub(crate) fn writable_offset(&self) -> usize
+	{
+		(self.writable_address as usize).wrapping_sub(self.address as usize)
+	}
+	
 	/// Get an assembler to this anonymous map.
 	#[inline(always)]
 	pub fn instruction_stream(&mut self, instruction_stream_hints: &InstructionStreamHints) -> InstructionStream
@@ -112,22 +282,38 @@ impl ExecutableAnonymousMemoryMap
 	#[inline(always)]
 	pub(crate) fn make_writable(&mut self)
 	{
-		self.mprotect(self.address, self.length, PROT_WRITE)
+		if self.is_dual_mapping()
+		{
+			return
+		}
+		
+		self.mprotect(self.address, self.length, PROT_READ | PROT_WRITE)
 	}
 	
 	#[inline(always)]
 	pub(crate) fn make_executable(&mut self)
 	{
-		self.mprotect(self.address, self.length, PROT_EXEC)
+		if self.is_dual_mapping()
+		{
+			return
+		}
+		
+		self.mprotect(self.address, self.length, PROT_READ | PROT_EXEC)
 	}
 	
 	#[cfg(any(target_os = "android", target_os = "linux"))]
 	#[inline(always)]
 	pub(crate) fn attempt_to_resize_in_place_whilst_writing(&mut self) -> io::Result<usize>
 	{
+		if unlikely!(self.is_dual_mapping())
+		{
+			return Err(io::Error::other("A dual mapping can not be resized in place"))
+		}
+		
 		const NoFlags: i32 = 0;
 		let old_length = self.length;
-		let new_length = self.length * 2;
+		let increment = self.growth_policy.increment(old_length);
+		let new_length = old_length + increment;
 		let old_address = self.address;
 		let new_address = unsafe { mremap(old_address as *mut _, old_length, new_length, NoFlags) };
 		if unlikely!(new_address == MAP_FAILED)
@@ -140,7 +326,7 @@ impl ExecutableAnonymousMemoryMap
 			
 			let new_memory_address = unsafe { old_address.add(old_length) };
 			
-			let result = unsafe { mlock(new_memory_address as *mut _, old_length) };
+			let result = unsafe { mlock(new_memory_address as *mut _, increment) };
 			if unlikely!(!self.ignore_mlock_failure && result != 0)
 			{
 				if likely!(result == -1)
@@ -153,7 +339,7 @@ impl ExecutableAnonymousMemoryMap
 				}
 			}
 
-			self.mprotect(new_memory_address, old_length, PROT_WRITE);
+			self.mprotect(new_memory_address, increment, PROT_READ | PROT_WRITE);
 			self.length = new_length;
 			Ok(new_length)
 		}
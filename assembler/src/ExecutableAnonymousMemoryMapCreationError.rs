@@ -11,6 +11,9 @@ pub enum ExecutableAnonymousMemoryMapCreationError
 
 	/// mlock failed for `usize` aligned bytes.
 	MLockFailed(io::Error, usize),
+
+	/// memfd_create or ftruncate failed for `usize` aligned bytes when creating a dual mapping.
+	SharedMemoryFailed(io::Error, usize),
 }
 
 impl Display for ExecutableAnonymousMemoryMapCreationError
@@ -23,6 +26,7 @@ impl Display for ExecutableAnonymousMemoryMapCreationError
 		{
 			MMapFailed(ref error, bytes) => write!(f, "mmap of {} aligned bytes failed with '{}'", bytes, error),
 			MLockFailed(ref error, bytes) => write!(f, "mlock of {} aligned bytes failed with '{}'", bytes, error),
+			SharedMemoryFailed(ref error, bytes) => write!(f, "shared memory of {} aligned bytes failed with '{}'", bytes, error),
 		}
 	}
 }
@@ -38,6 +42,7 @@ impl Error for ExecutableAnonymousMemoryMapCreationError
 		{
 			MMapFailed(ref error, _) => Some(error),
 			MLockFailed(ref error, _) => Some(error),
+			SharedMemoryFailed(ref error, _) => Some(error),
 		}
 	}
 }
@@ -0,0 +1,159 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// An in-memory symbol file (eg an ELF object) registered with GDB's JIT interface by `InstructionStream.register_with_gdb()`.
+///
+/// Unregisters the symbol file when dropped.
+#[cfg(feature = "gdb")]
+#[derive(Debug)]
+pub struct GdbJitRegistration
+{
+	jit_code_entry: *mut JitCodeEntry,
+	symbol_file: Box<[u8]>,
+}
+
+#[cfg(feature = "gdb")]
+impl Drop for GdbJitRegistration
+{
+	#[inline(always)]
+	fn drop(&mut self)
+	{
+		let _lock = GdbJitInterfaceLock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+		
+		unsafe
+		{
+			let jit_code_entry = &mut *self.jit_code_entry;
+			let jit_debug_descriptor = &mut *addr_of_mut!(__jit_debug_descriptor);
+			
+			if jit_code_entry.previous_entry.is_null()
+			{
+				jit_debug_descriptor.first_entry = jit_code_entry.next_entry;
+			}
+			else
+			{
+				(*jit_code_entry.previous_entry).next_entry = jit_code_entry.next_entry;
+			}
+			
+			if !jit_code_entry.next_entry.is_null()
+			{
+				(*jit_code_entry.next_entry).previous_entry = jit_code_entry.previous_entry;
+			}
+			
+			jit_debug_descriptor.relevant_entry = self.jit_code_entry;
+			jit_debug_descriptor.action_flag = JitUnregisterFunction;
+			__jit_debug_register_code();
+			
+			drop(Box::from_raw(self.jit_code_entry));
+		}
+	}
+}
+
+#[cfg(feature = "gdb")]
+impl GdbJitRegistration
+{
+	#[inline(always)]
+	pub(crate) fn register(symbol_file: &[u8]) -> Self
+	{
+		let symbol_file = symbol_file.to_vec().into_boxed_slice();
+		
+		let _lock = GdbJitInterfaceLock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+		
+		unsafe
+		{
+			let jit_debug_descriptor = &mut *addr_of_mut!(__jit_debug_descriptor);
+			
+			let jit_code_entry = Box::into_raw
+			(
+				Box::new
+				(
+					JitCodeEntry
+					{
+						next_entry: jit_debug_descriptor.first_entry,
+						previous_entry: null_mut(),
+						symbol_file_address: symbol_file.as_ptr(),
+						symbol_file_size: symbol_file.len() as u64,
+					}
+				)
+			);
+			
+			if !jit_debug_descriptor.first_entry.is_null()
+			{
+				(*jit_debug_descriptor.first_entry).previous_entry = jit_code_entry;
+			}
+			
+			jit_debug_descriptor.first_entry = jit_code_entry;
+			jit_debug_descriptor.relevant_entry = jit_code_entry;
+			jit_debug_descriptor.action_flag = JitRegisterFunction;
+			__jit_debug_register_code();
+			
+			Self
+			{
+				jit_code_entry,
+				symbol_file,
+			}
+		}
+	}
+	
+	/// The registered symbol file.
+	#[inline(always)]
+	pub fn symbol_file(&self) -> &[u8]
+	{
+		&self.symbol_file
+	}
+}
+
+#[cfg(feature = "gdb")]
+const JitRegisterFunction: u32 = 1;
+
+#[cfg(feature = "gdb")]
+const JitUnregisterFunction: u32 = 2;
+
+/// Serializes changes to `__jit_debug_descriptor`.
+#[cfg(feature = "gdb")]
+static GdbJitInterfaceLock: Mutex<()> = Mutex::new(());
+
+/// Layout defined by GDB's JIT interface (`struct jit_code_entry`).
+#[cfg(feature = "gdb")]
+#[repr(C)]
+#[derive(Debug)]
+struct JitCodeEntry
+{
+	next_entry: *mut JitCodeEntry,
+	previous_entry: *mut JitCodeEntry,
+	symbol_file_address: *const u8,
+	symbol_file_size: u64,
+}
+
+/// Layout defined by GDB's JIT interface (`struct jit_descriptor`).
+#[cfg(feature = "gdb")]
+#[repr(C)]
+#[derive(Debug)]
+struct JitDescriptor
+{
+	version: u32,
+	action_flag: u32,
+	relevant_entry: *mut JitCodeEntry,
+	first_entry: *mut JitCodeEntry,
+}
+
+/// GDB reads this to find registered symbol files.
+#[cfg(feature = "gdb")]
+#[no_mangle]
+static mut __jit_debug_descriptor: JitDescriptor = JitDescriptor
+{
+	version: 1,
+	action_flag: 0,
+	relevant_entry: null_mut(),
+	first_entry: null_mut(),
+};
+
+/// GDB sets a breakpoint on this to be notified of changes to `__jit_debug_descriptor`.
+#[cfg(feature = "gdb")]
+#[no_mangle]
+#[inline(never)]
+extern "C" fn __jit_debug_register_code()
+{
+	// Prevents calls to this function from being optimized away.
+	unsafe { ::std::arch::asm!("") }
+}
@@ -0,0 +1,50 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// How an `ExecutableAnonymousMemoryMap` grows when an instruction stream runs out of space.
+///
+/// Growth is always in place (the map is never moved), so it fails if the address space after the map is in use; pre-sizing the map (with `ExecutableAnonymousMemoryMap::new()` and `InstructionStreamHints.number_of_bytes`) avoids this.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GrowthPolicy
+{
+	/// Double the length of the map.
+	Double,
+	
+	/// Add a fixed number of bytes to the length of the map, rounded up to a multiple of the page size (4Kb).
+	FixedIncrement(usize),
+}
+
+impl Default for GrowthPolicy
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		GrowthPolicy::Double
+	}
+}
+
+impl GrowthPolicy
+{
+	/// The number of bytes to add to a map of `length` bytes.
+	#[inline(always)]
+	pub fn increment(self, length: usize) -> usize
+	{
+		use self::GrowthPolicy::*;
+		
+		const PageSize: usize = 4096;
+		
+		match self
+		{
+			Double => length,
+			FixedIncrement(increment) => if increment == 0
+			{
+				PageSize
+			}
+			else
+			{
+				(increment + PageSize - 1) & !(PageSize - 1)
+			},
+		}
+	}
+}
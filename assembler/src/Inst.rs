@@ -0,0 +1,80 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// A small instruction representation for assembling from a precomputed list with `InstructionStream.emit_all()`.
+///
+/// Each variant dispatches to the mnemonic method named in its documentation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Inst
+{
+	/// `mov_Register64Bit_Register64Bit_rm64_r64()`.
+	MovRegReg(Register64Bit, Register64Bit),
+	
+	/// `mov_Register64Bit_Immediate64Bit()`.
+	MovRegImm(Register64Bit, i64),
+	
+	/// `add_Register64Bit_Register64Bit()`.
+	AddRegReg(Register64Bit, Register64Bit),
+	
+	/// `add_Register64Bit_Immediate32Bit()`; the immediate is sign-extended.
+	AddRegImm(Register64Bit, i32),
+	
+	/// `sub_Register64Bit_Register64Bit()`.
+	SubRegReg(Register64Bit, Register64Bit),
+	
+	/// `sub_Register64Bit_Immediate32Bit()`; the immediate is sign-extended.
+	SubRegImm(Register64Bit, i32),
+	
+	/// `cmp_Register64Bit_Register64Bit()`.
+	CmpRegReg(Register64Bit, Register64Bit),
+	
+	/// `cmp_Register64Bit_Immediate32Bit()`; the immediate is sign-extended.
+	CmpRegImm(Register64Bit, i32),
+	
+	/// `push_Register64Bit_r64()`.
+	Push(Register64Bit),
+	
+	/// `pop_Register64Bit_r64()`.
+	Pop(Register64Bit),
+	
+	/// `jmp_Label_auto()`.
+	JmpLabel(Label),
+	
+	/// `jcc_Label_auto()`.
+	JccLabel(ConditionCode, Label),
+	
+	/// `call_Label()`.
+	CallLabel(Label),
+	
+	/// `attach_label()`; emits no bytes.
+	AttachLabel(Label),
+	
+	/// `ret()`.
+	Ret,
+	
+	/// `nop()`.
+	Nop,
+}
+
+impl Inst
+{
+	/// The maximum number of bytes emitted for this instruction by `InstructionStream.emit_all()`.
+	#[inline(always)]
+	pub fn maximum_length(self) -> usize
+	{
+		use self::Inst::*;
+		
+		match self
+		{
+			MovRegReg(..) | AddRegReg(..) | SubRegReg(..) | CmpRegReg(..) => 3,
+			MovRegImm(..) => 10,
+			AddRegImm(..) | SubRegImm(..) | CmpRegImm(..) => 7,
+			Push(..) | Pop(..) => 2,
+			JmpLabel(..) | CallLabel(..) => 5,
+			JccLabel(..) => 6,
+			AttachLabel(..) => 0,
+			Ret | Nop => 1,
+		}
+	}
+}
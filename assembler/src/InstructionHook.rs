@@ -0,0 +1,40 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// Holds the hook set by `InstructionStream.set_instruction_hook()`.
+#[derive(Default)]
+pub(crate) struct InstructionHook<'a>(Option<Box<dyn FnMut(InstructionPointer) + 'a>>);
+
+impl<'a> Debug for InstructionHook<'a>
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "InstructionHook({})", if self.0.is_some() { "Some" } else { "None" })
+	}
+}
+
+impl<'a> InstructionHook<'a>
+{
+	#[inline(always)]
+	pub(crate) fn set(&mut self, hook: impl FnMut(InstructionPointer) + 'a)
+	{
+		self.0 = Some(Box::new(hook))
+	}
+	
+	#[inline(always)]
+	pub(crate) fn clear(&mut self)
+	{
+		self.0 = None
+	}
+	
+	#[inline(always)]
+	pub(crate) fn call(&mut self, instruction_pointer: InstructionPointer)
+	{
+		if let Some(ref mut hook) = self.0
+		{
+			hook(instruction_pointer)
+		}
+	}
+}
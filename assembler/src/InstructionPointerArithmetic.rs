@@ -0,0 +1,50 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// Conversions and arithmetic for an `InstructionPointer`, for use when implementing custom relocations or trampolines.
+///
+/// `InstructionPointer` is an alias of `usize`, so this is a trait rather than inherent methods (and `from_pointer()` stands in for `From<*const u8>`).
+pub trait InstructionPointerArithmetic: Sized + Copy
+{
+	/// Creates an instruction pointer from a pointer, eg one returned by `ExecutableAnonymousMemoryMap.writable_alias()` or to a function outside of the instruction stream.
+	fn from_pointer(pointer: *const u8) -> Self;
+	
+	/// The address as an `usize`.
+	fn as_usize(self) -> usize;
+	
+	/// The address as a pointer.
+	fn as_pointer(self) -> *const u8;
+	
+	/// The signed number of bytes from `base` to this instruction pointer; negative if this instruction pointer is before `base`.
+	///
+	/// For a relative displacement, `base` is the end of the instruction.
+	fn offset_from(self, base: Self) -> isize;
+}
+
+impl InstructionPointerArithmetic for InstructionPointer
+{
+	#[inline(always)]
+	fn from_pointer(pointer: *const u8) -> Self
+	{
+		pointer as InstructionPointer
+	}
+	
+	#[inline(always)]
+	fn as_usize(self) -> usize
+	{
+		self
+	}
+	
+	#[inline(always)]
+	fn as_pointer(self) -> *const u8
+	{
+		self as *const u8
+	}
+	
+	#[inline(always)]
+	fn offset_from(self, base: Self) -> isize
+	{
+		self.wrapping_sub(base) as isize
+	}
+}
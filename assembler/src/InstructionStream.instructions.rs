@@ -122,7 +122,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Add with Carry Flag (CF) sign-extended `imm8` to `r/m16`.
 	#[inline(always)]
-	pub fn adc_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: Immediate8Bit)
+	pub fn adc_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -209,7 +209,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Add with Carry Flag (CF) sign-extended `imm8` into `r/m32`.
 	#[inline(always)]
-	pub fn adc_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: Immediate8Bit)
+	pub fn adc_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -296,7 +296,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Add with Carry Flag (CF) sign-extended `imm8` into `r/m64`.
 	#[inline(always)]
-	pub fn adc_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: Immediate8Bit)
+	pub fn adc_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -470,7 +470,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Add with Carry Flag (CF) sign-extended `imm8` to `r/m16`.
 	#[inline(always)]
-	pub fn adc_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: Immediate8Bit)
+	pub fn adc_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -615,7 +615,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Add with Carry Flag (CF) sign-extended `imm8` into `r/m32`.
 	#[inline(always)]
-	pub fn adc_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: Immediate8Bit)
+	pub fn adc_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -760,7 +760,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Add with Carry Flag (CF) sign-extended `imm8` into `r/m64`.
 	#[inline(always)]
-	pub fn adc_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: Immediate8Bit)
+	pub fn adc_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -1369,7 +1369,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Add sign-extended `imm8` to `r/m16`.
 	#[inline(always)]
-	pub fn add_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: Immediate8Bit)
+	pub fn add_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -1456,7 +1456,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Add sign-extended `imm8` to `r/m32`.
 	#[inline(always)]
-	pub fn add_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: Immediate8Bit)
+	pub fn add_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -1543,7 +1543,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Add sign-extended `imm8` to `r/m64`.
 	#[inline(always)]
-	pub fn add_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: Immediate8Bit)
+	pub fn add_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -1717,7 +1717,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Add sign-extended `imm8` to `r/m16`.
 	#[inline(always)]
-	pub fn add_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: Immediate8Bit)
+	pub fn add_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -1862,7 +1862,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Add sign-extended `imm8` to `r/m32`.
 	#[inline(always)]
-	pub fn add_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: Immediate8Bit)
+	pub fn add_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -1982,6 +1982,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted_if_RSP(arg0, -(arg1.0 as isize));
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -2007,10 +2009,12 @@ impl<'a> InstructionStream<'a>
 
 	/// Add sign-extended `imm8` to `r/m64`.
 	#[inline(always)]
-	pub fn add_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: Immediate8Bit)
+	pub fn add_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted_if_RSP(arg0, -(arg1.0 as isize));
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -3312,7 +3316,7 @@ impl<'a> InstructionStream<'a>
 
 	/// `r/m16 && imm8` (sign-extended).
 	#[inline(always)]
-	pub fn and_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: Immediate8Bit)
+	pub fn and_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -3399,7 +3403,7 @@ impl<'a> InstructionStream<'a>
 
 	/// `r/m32 && imm8` (sign-extended).
 	#[inline(always)]
-	pub fn and_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: Immediate8Bit)
+	pub fn and_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -3486,7 +3490,7 @@ impl<'a> InstructionStream<'a>
 
 	/// `r/m64 && imm8` (sign-extended).
 	#[inline(always)]
-	pub fn and_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: Immediate8Bit)
+	pub fn and_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -3660,7 +3664,7 @@ impl<'a> InstructionStream<'a>
 
 	/// `r/m16 && imm8` (sign-extended).
 	#[inline(always)]
-	pub fn and_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: Immediate8Bit)
+	pub fn and_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -3805,7 +3809,7 @@ impl<'a> InstructionStream<'a>
 
 	/// `r/m32 && imm8` (sign-extended).
 	#[inline(always)]
-	pub fn and_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: Immediate8Bit)
+	pub fn and_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -3950,7 +3954,7 @@ impl<'a> InstructionStream<'a>
 
 	/// `r/m64 && imm8` (sign-extended).
 	#[inline(always)]
-	pub fn and_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: Immediate8Bit)
+	pub fn and_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -5842,6 +5846,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Reverses the byte order of a 32-bit register.
+	///
+	/// There is no 16-bit form, as `BSWAP` on a 16-bit register is architecturally undefined; use `bswap_Register16Bit()`, which emits `ROL r16, 8`, instead.
 	#[inline(always)]
 	pub fn bswap_Register32Bit(&mut self, arg0: Register32Bit)
 	{
@@ -5871,6 +5877,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Reverses the byte order of a 64-bit register.
+	///
+	/// There is no 16-bit form, as `BSWAP` on a 16-bit register is architecturally undefined; use `bswap_Register16Bit()`, which emits `ROL r16, 8`, instead.
 	#[inline(always)]
 	pub fn bswap_Register64Bit(&mut self, arg0: Register64Bit)
 	{
@@ -5900,6 +5908,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF).
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn bt_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: Immediate8Bit)
 	{
@@ -5929,6 +5939,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF).
+	///
+	/// The (signed) bit offset in the register is not limited to the operand size, so this can address any bit in memory relative to the operand.
 	#[inline(always)]
 	pub fn bt_Any16BitMemory_Register16Bit(&mut self, arg0: Any16BitMemory, arg1: Register16Bit)
 	{
@@ -5958,6 +5970,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF).
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn bt_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: Immediate8Bit)
 	{
@@ -5987,6 +6001,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF).
+	///
+	/// The (signed) bit offset in the register is not limited to the operand size, so this can address any bit in memory relative to the operand.
 	#[inline(always)]
 	pub fn bt_Any32BitMemory_Register32Bit(&mut self, arg0: Any32BitMemory, arg1: Register32Bit)
 	{
@@ -6016,6 +6032,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF).
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn bt_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: Immediate8Bit)
 	{
@@ -6045,6 +6063,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF).
+	///
+	/// The (signed) bit offset in the register is not limited to the operand size, so this can address any bit in memory relative to the operand.
 	#[inline(always)]
 	pub fn bt_Any64BitMemory_Register64Bit(&mut self, arg0: Any64BitMemory, arg1: Register64Bit)
 	{
@@ -6074,6 +6094,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF).
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn bt_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: Immediate8Bit)
 	{
@@ -6132,6 +6154,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF).
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn bt_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: Immediate8Bit)
 	{
@@ -6190,6 +6214,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF).
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn bt_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: Immediate8Bit)
 	{
@@ -6248,6 +6274,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and complement.
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn btc_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: Immediate8Bit)
 	{
@@ -6277,6 +6305,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and complement.
+	///
+	/// The (signed) bit offset in the register is not limited to the operand size, so this can address any bit in memory relative to the operand.
 	#[inline(always)]
 	pub fn btc_Any16BitMemory_Register16Bit(&mut self, arg0: Any16BitMemory, arg1: Register16Bit)
 	{
@@ -6306,6 +6336,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and complement.
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn btc_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: Immediate8Bit)
 	{
@@ -6335,6 +6367,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and complement.
+	///
+	/// The (signed) bit offset in the register is not limited to the operand size, so this can address any bit in memory relative to the operand.
 	#[inline(always)]
 	pub fn btc_Any32BitMemory_Register32Bit(&mut self, arg0: Any32BitMemory, arg1: Register32Bit)
 	{
@@ -6364,6 +6398,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and complement.
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn btc_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: Immediate8Bit)
 	{
@@ -6393,6 +6429,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and complement.
+	///
+	/// The (signed) bit offset in the register is not limited to the operand size, so this can address any bit in memory relative to the operand.
 	#[inline(always)]
 	pub fn btc_Any64BitMemory_Register64Bit(&mut self, arg0: Any64BitMemory, arg1: Register64Bit)
 	{
@@ -6422,6 +6460,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and complement.
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn btc_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: Immediate8Bit)
 	{
@@ -6480,6 +6520,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and complement.
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn btc_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: Immediate8Bit)
 	{
@@ -6538,6 +6580,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and complement.
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn btc_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: Immediate8Bit)
 	{
@@ -6596,6 +6640,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and clear.
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn btr_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: Immediate8Bit)
 	{
@@ -6625,6 +6671,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and clear.
+	///
+	/// The (signed) bit offset in the register is not limited to the operand size, so this can address any bit in memory relative to the operand.
 	#[inline(always)]
 	pub fn btr_Any16BitMemory_Register16Bit(&mut self, arg0: Any16BitMemory, arg1: Register16Bit)
 	{
@@ -6654,6 +6702,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and clear.
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn btr_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: Immediate8Bit)
 	{
@@ -6683,6 +6733,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and clear.
+	///
+	/// The (signed) bit offset in the register is not limited to the operand size, so this can address any bit in memory relative to the operand.
 	#[inline(always)]
 	pub fn btr_Any32BitMemory_Register32Bit(&mut self, arg0: Any32BitMemory, arg1: Register32Bit)
 	{
@@ -6712,6 +6764,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and clear.
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn btr_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: Immediate8Bit)
 	{
@@ -6741,6 +6795,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and clear.
+	///
+	/// The (signed) bit offset in the register is not limited to the operand size, so this can address any bit in memory relative to the operand.
 	#[inline(always)]
 	pub fn btr_Any64BitMemory_Register64Bit(&mut self, arg0: Any64BitMemory, arg1: Register64Bit)
 	{
@@ -6770,6 +6826,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and clear.
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn btr_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: Immediate8Bit)
 	{
@@ -6828,6 +6886,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and clear.
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn btr_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: Immediate8Bit)
 	{
@@ -6886,6 +6946,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and clear.
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn btr_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: Immediate8Bit)
 	{
@@ -6944,6 +7006,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and set.
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn bts_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: Immediate8Bit)
 	{
@@ -6973,6 +7037,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and set.
+	///
+	/// The (signed) bit offset in the register is not limited to the operand size, so this can address any bit in memory relative to the operand.
 	#[inline(always)]
 	pub fn bts_Any16BitMemory_Register16Bit(&mut self, arg0: Any16BitMemory, arg1: Register16Bit)
 	{
@@ -7002,6 +7068,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and set.
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn bts_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: Immediate8Bit)
 	{
@@ -7031,6 +7099,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and set.
+	///
+	/// The (signed) bit offset in the register is not limited to the operand size, so this can address any bit in memory relative to the operand.
 	#[inline(always)]
 	pub fn bts_Any32BitMemory_Register32Bit(&mut self, arg0: Any32BitMemory, arg1: Register32Bit)
 	{
@@ -7060,6 +7130,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and set.
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn bts_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: Immediate8Bit)
 	{
@@ -7089,6 +7161,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and set.
+	///
+	/// The (signed) bit offset in the register is not limited to the operand size, so this can address any bit in memory relative to the operand.
 	#[inline(always)]
 	pub fn bts_Any64BitMemory_Register64Bit(&mut self, arg0: Any64BitMemory, arg1: Register64Bit)
 	{
@@ -7118,6 +7192,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and set.
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn bts_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: Immediate8Bit)
 	{
@@ -7176,6 +7252,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and set.
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn bts_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: Immediate8Bit)
 	{
@@ -7234,6 +7312,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store selected bit in Carry Flag (CF) and set.
+	///
+	/// The bit offset is taken modulo the operand size.
 	#[inline(always)]
 	pub fn bts_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: Immediate8Bit)
 	{
@@ -7787,6 +7867,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Flushes cache line containing `m8`.
+	///
+	/// Ordered with respect to other stores and `CLFLUSH`es; use `MFENCE` to order it with respect to loads.
 	#[inline(always)]
 	pub fn clflush_Any8BitMemory(&mut self, arg0: Any8BitMemory)
 	{
@@ -7815,7 +7897,40 @@ impl<'a> InstructionStream<'a>
 		// No label displacement.
 	}
 
+	/// Flushes cache line containing `m8`, ordered only by fences (`SFENCE` or `MFENCE`) and not by other stores or `CLFLUSHOPT`s to other cache lines.
+	///
+	/// Requires `CLFLUSHOPT` support (`CPUID.(EAX=7,ECX=0):EBX[23]`).
+	#[inline(always)]
+	pub fn clflushopt_Any8BitMemory(&mut self, arg0: Any8BitMemory)
+	{
+		self.reserve_space_for_instruction();
+
+		// This is not a VEX encoded instruction.
+
+		// No `FWAIT` Prefix.
+
+		self.prefix_group2(arg0);
+
+		self.prefix_group4(arg0);
+
+		self.prefix_group3();
+
+		// No prefix group 1.
+
+		self.rex_2(arg0, 0x00);
+
+		self.opcode_2(0x0F, 0xAE);
+
+		self.mod_rm_sib(arg0, Register64Bit::RDI);
+
+		// No displacement or immediate.
+
+		// No label displacement.
+	}
+
 	/// Clear interrupt flag; interrupts disabled when interrupt flag cleared.
+	///
+	/// Privileged; raises a general protection fault in user mode (unless `IOPL` permits it).
 	#[inline(always)]
 	pub fn cli(&mut self)
 	{
@@ -7844,6 +7959,39 @@ impl<'a> InstructionStream<'a>
 		// No label displacement.
 	}
 
+	/// Writes back the cache line containing `m8` (if modified) to memory, and may retain it in the cache.
+	///
+	/// Ordered only by fences (`SFENCE` or `MFENCE`); follow a sequence of `CLWB`s with an `SFENCE` before relying on the data having reached (persistent) memory.
+	///
+	/// Requires `CLWB` support (`CPUID.(EAX=7,ECX=0):EBX[24]`).
+	#[inline(always)]
+	pub fn clwb_Any8BitMemory(&mut self, arg0: Any8BitMemory)
+	{
+		self.reserve_space_for_instruction();
+
+		// This is not a VEX encoded instruction.
+
+		// No `FWAIT` Prefix.
+
+		self.prefix_group2(arg0);
+
+		self.prefix_group4(arg0);
+
+		self.prefix_group3();
+
+		// No prefix group 1.
+
+		self.rex_2(arg0, 0x00);
+
+		self.opcode_2(0x0F, 0xAE);
+
+		self.mod_rm_sib(arg0, Register64Bit::RSI);
+
+		// No displacement or immediate.
+
+		// No label displacement.
+	}
+
 	/// Complement Carry Flag (CF).
 	#[inline(always)]
 	pub fn cmc(&mut self)
@@ -13211,7 +13359,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Compare `imm8` with `r/m16`.
 	#[inline(always)]
-	pub fn cmp_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: Immediate8Bit)
+	pub fn cmp_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -13298,7 +13446,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Compare `imm8` with `r/m32`.
 	#[inline(always)]
-	pub fn cmp_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: Immediate8Bit)
+	pub fn cmp_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -13385,7 +13533,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Compare `imm8` with `r/m64`.
 	#[inline(always)]
-	pub fn cmp_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: Immediate8Bit)
+	pub fn cmp_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -13559,7 +13707,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Compare `imm8` with `r/m16`.
 	#[inline(always)]
-	pub fn cmp_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: Immediate8Bit)
+	pub fn cmp_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -13704,7 +13852,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Compare `imm8` with `r/m32`.
 	#[inline(always)]
-	pub fn cmp_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: Immediate8Bit)
+	pub fn cmp_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -13849,7 +13997,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Compare `imm8` with `r/m64`.
 	#[inline(always)]
-	pub fn cmp_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: Immediate8Bit)
+	pub fn cmp_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -15250,6 +15398,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 16);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -22864,6 +23014,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 16);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -22893,6 +23045,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 16);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -22922,6 +23076,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 16);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -22951,6 +23107,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 16);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -23759,7 +23917,7 @@ impl<'a> InstructionStream<'a>
 
 	/// word register = `r/m16` * sign-extended `imm8`.
 	#[inline(always)]
-	pub fn imul_Register16Bit_Any16BitMemory_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: Any16BitMemory, arg2: Immediate8Bit)
+	pub fn imul_Register16Bit_Any16BitMemory_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: Any16BitMemory, arg2: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -23846,7 +24004,7 @@ impl<'a> InstructionStream<'a>
 
 	/// word register = `r/m16` * sign-extended `imm8`.
 	#[inline(always)]
-	pub fn imul_Register16Bit_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: Register16Bit, arg2: Immediate8Bit)
+	pub fn imul_Register16Bit_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: Register16Bit, arg2: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -23962,7 +24120,7 @@ impl<'a> InstructionStream<'a>
 
 	/// doubleword register = `r/m32` * sign-extended `imm8`.
 	#[inline(always)]
-	pub fn imul_Register32Bit_Any32BitMemory_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: Any32BitMemory, arg2: Immediate8Bit)
+	pub fn imul_Register32Bit_Any32BitMemory_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: Any32BitMemory, arg2: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -24049,7 +24207,7 @@ impl<'a> InstructionStream<'a>
 
 	/// doubleword register = `r/m32` * sign-extended `imm8`.
 	#[inline(always)]
-	pub fn imul_Register32Bit_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: Register32Bit, arg2: Immediate8Bit)
+	pub fn imul_Register32Bit_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: Register32Bit, arg2: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -24165,7 +24323,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Quadword register = `r/m64` * sign-extended `imm8`.
 	#[inline(always)]
-	pub fn imul_Register64Bit_Any64BitMemory_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: Any64BitMemory, arg2: Immediate8Bit)
+	pub fn imul_Register64Bit_Any64BitMemory_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: Any64BitMemory, arg2: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -24252,7 +24410,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Quadword register = `r/m64` * sign-extended `imm8`.
 	#[inline(always)]
-	pub fn imul_Register64Bit_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: Register64Bit, arg2: Immediate8Bit)
+	pub fn imul_Register64Bit_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: Register64Bit, arg2: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -33139,6 +33297,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store effective address for `m32` in register `r32`.
+	///
+	/// The address is truncated to 32 bits and zero-extended into the 64-bit register (there is no `REX.W`); use a memory operand with 32-bit base and index registers to also compute it with a 32-bit address size (a `0x67` prefix).
 	#[inline(always)]
 	pub fn lea_Register32Bit_Any32BitMemory(&mut self, arg0: Register32Bit, arg1: Any32BitMemory)
 	{
@@ -33168,6 +33328,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Store effective address for `m64` in register `r32`.
+	///
+	/// The address is truncated to 32 bits and zero-extended into the 64-bit register (there is no `REX.W`); use a memory operand with 32-bit base and index registers to also compute it with a 32-bit address size (a `0x67` prefix).
 	#[inline(always)]
 	pub fn lea_Register32Bit_Any64BitMemory(&mut self, arg0: Register32Bit, arg1: Any64BitMemory)
 	{
@@ -35195,6 +35357,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Move segment register to `r/m16`.
+	///
+	/// A segment register is always stored as 16 bits to memory, so no operand-size prefix is needed.
 	#[inline(always)]
 	pub fn mov_Any16BitMemory_SegmentRegister(&mut self, arg0: Any16BitMemory, arg1: SegmentRegister)
 	{
@@ -35208,7 +35372,7 @@ impl<'a> InstructionStream<'a>
 
 		self.prefix_group4(arg0);
 
-		self.prefix_group3();
+		// No prefix group 3.
 
 		// No prefix group 1.
 
@@ -36542,7 +36706,7 @@ impl<'a> InstructionStream<'a>
 
 		self.prefix_group4(arg1);
 
-		self.prefix_group3();
+		// No prefix group 3.
 
 		// No prefix group 1.
 
@@ -41774,7 +41938,7 @@ impl<'a> InstructionStream<'a>
 
 	/// `r/m16` OR `imm8` (sign-extended).
 	#[inline(always)]
-	pub fn or_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: Immediate8Bit)
+	pub fn or_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -41861,7 +42025,7 @@ impl<'a> InstructionStream<'a>
 
 	/// `r/m32` OR `imm8` (sign-extended).
 	#[inline(always)]
-	pub fn or_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: Immediate8Bit)
+	pub fn or_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -41948,7 +42112,7 @@ impl<'a> InstructionStream<'a>
 
 	/// `r/m64` OR `imm8` (sign-extended).
 	#[inline(always)]
-	pub fn or_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: Immediate8Bit)
+	pub fn or_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -42122,7 +42286,7 @@ impl<'a> InstructionStream<'a>
 
 	/// `r/m16` OR `imm8` (sign-extended).
 	#[inline(always)]
-	pub fn or_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: Immediate8Bit)
+	pub fn or_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -42267,7 +42431,7 @@ impl<'a> InstructionStream<'a>
 
 	/// `r/m32` OR `imm8` (sign-extended).
 	#[inline(always)]
-	pub fn or_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: Immediate8Bit)
+	pub fn or_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -42412,7 +42576,7 @@ impl<'a> InstructionStream<'a>
 
 	/// `r/m64` OR `imm8` (sign-extended).
 	#[inline(always)]
-	pub fn or_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: Immediate8Bit)
+	pub fn or_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -45398,6 +45562,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Gives hint to processor that improves performance of spin-wait loops.
+	///
+	/// Encoded as `F3 90`; emit it inside the body of every busy-wait loop (eg one polling a lock word with `CMP` and `Jcc`) rather than a plain `NOP`. It avoids a memory order violation pipeline flush when the loop exits, reduces power consumption and yields execution resources to a sibling hyperthread.
 	#[inline(always)]
 	pub fn pause(&mut self)
 	{
@@ -46243,6 +46409,10 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Perform a packed comparison of string data with explicit lengths, generating an index, and storing the result in `ECX`.
+	///
+	/// The lengths of the first and second strings are read from `EAX` and `EDX`; the index selected by `imm8` (or 16 bytes or 8 words, if there is no match) is written to `ECX` and the result is also reported in the flags.
+	///
+	/// Requires SSE4.2; the memory operand need not be aligned.
 	#[inline(always)]
 	pub fn pcmpestri_XMMRegister_Any128BitMemory_Immediate8Bit(&mut self, arg0: XMMRegister, arg1: Any128BitMemory, arg2: Immediate8Bit)
 	{
@@ -46272,6 +46442,10 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Perform a packed comparison of string data with explicit lengths, generating an index, and storing the result in `ECX`.
+	///
+	/// The lengths of the first and second strings are read from `EAX` and `EDX`; the index selected by `imm8` (or 16 bytes or 8 words, if there is no match) is written to `ECX` and the result is also reported in the flags.
+	///
+	/// Requires SSE4.2; the memory operand need not be aligned.
 	#[inline(always)]
 	pub fn pcmpestri_XMMRegister_XMMRegister_Immediate8Bit(&mut self, arg0: XMMRegister, arg1: XMMRegister, arg2: Immediate8Bit)
 	{
@@ -46301,6 +46475,10 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Perform a packed comparison of string data with explicit lengths, generating a mask, and storing the result in `XMM0`.
+	///
+	/// The lengths of the first and second strings are read from `EAX` and `EDX`; the bit or element mask selected by `imm8` is written to `XMM0` and the result is also reported in the flags.
+	///
+	/// Requires SSE4.2; the memory operand need not be aligned.
 	#[inline(always)]
 	pub fn pcmpestrm_XMMRegister_Any128BitMemory_Immediate8Bit(&mut self, arg0: XMMRegister, arg1: Any128BitMemory, arg2: Immediate8Bit)
 	{
@@ -46330,6 +46508,10 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Perform a packed comparison of string data with explicit lengths, generating a mask, and storing the result in `XMM0`.
+	///
+	/// The lengths of the first and second strings are read from `EAX` and `EDX`; the bit or element mask selected by `imm8` is written to `XMM0` and the result is also reported in the flags.
+	///
+	/// Requires SSE4.2; the memory operand need not be aligned.
 	#[inline(always)]
 	pub fn pcmpestrm_XMMRegister_XMMRegister_Immediate8Bit(&mut self, arg0: XMMRegister, arg1: XMMRegister, arg2: Immediate8Bit)
 	{
@@ -46765,6 +46947,10 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Perform a packed comparison of string data with implicit lengths, generating an index, and storing the result in `ECX`.
+	///
+	/// Each string ends at its first zero element; the index selected by `imm8` (or 16 bytes or 8 words, if there is no match) is written to `ECX` and the result is also reported in the flags.
+	///
+	/// Requires SSE4.2; the memory operand need not be aligned.
 	#[inline(always)]
 	pub fn pcmpistri_XMMRegister_Any128BitMemory_Immediate8Bit(&mut self, arg0: XMMRegister, arg1: Any128BitMemory, arg2: Immediate8Bit)
 	{
@@ -46794,6 +46980,10 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Perform a packed comparison of string data with implicit lengths, generating an index, and storing the result in `ECX`.
+	///
+	/// Each string ends at its first zero element; the index selected by `imm8` (or 16 bytes or 8 words, if there is no match) is written to `ECX` and the result is also reported in the flags.
+	///
+	/// Requires SSE4.2; the memory operand need not be aligned.
 	#[inline(always)]
 	pub fn pcmpistri_XMMRegister_XMMRegister_Immediate8Bit(&mut self, arg0: XMMRegister, arg1: XMMRegister, arg2: Immediate8Bit)
 	{
@@ -46823,6 +47013,10 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Perform a packed comparison of string data with implicit lengths, generating a mask, and storing the result in `XMM0`.
+	///
+	/// Each string ends at its first zero element; the bit or element mask selected by `imm8` is written to `XMM0` and the result is also reported in the flags.
+	///
+	/// Requires SSE4.2; the memory operand need not be aligned.
 	#[inline(always)]
 	pub fn pcmpistrm_XMMRegister_Any128BitMemory_Immediate8Bit(&mut self, arg0: XMMRegister, arg1: Any128BitMemory, arg2: Immediate8Bit)
 	{
@@ -46852,6 +47046,10 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Perform a packed comparison of string data with implicit lengths, generating a mask, and storing the result in `XMM0`.
+	///
+	/// Each string ends at its first zero element; the bit or element mask selected by `imm8` is written to `XMM0` and the result is also reported in the flags.
+	///
+	/// Requires SSE4.2; the memory operand need not be aligned.
 	#[inline(always)]
 	pub fn pcmpistrm_XMMRegister_XMMRegister_Immediate8Bit(&mut self, arg0: XMMRegister, arg1: XMMRegister, arg2: Immediate8Bit)
 	{
@@ -51209,6 +51407,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(-8);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -51238,6 +51438,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(-2);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -51267,6 +51469,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(-8);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -51296,6 +51500,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(-2);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -51325,6 +51531,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(-2);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -51356,6 +51564,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(-8);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -51385,6 +51595,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(-2);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -51414,6 +51626,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(-2);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -51445,6 +51659,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(-8);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -51476,6 +51692,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(-8);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -51679,6 +51897,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(-2);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -51708,6 +51928,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(-8);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -56064,6 +56286,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(8);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -56093,6 +56317,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(8);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -56122,6 +56348,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(2);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -56151,6 +56379,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(8);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -56180,6 +56410,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(2);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -56209,6 +56441,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(2);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -56238,6 +56472,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(8);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -56267,6 +56503,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(8);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -56296,6 +56534,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(2);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -56325,6 +56565,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(8);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -56354,6 +56596,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(8);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -56383,6 +56627,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(8);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -56408,10 +56654,12 @@ impl<'a> InstructionStream<'a>
 
 	/// Push `imm8` (sign-extended to 64-bits).
 	#[inline(always)]
-	pub fn pushq_Immediate8Bit(&mut self, arg0: Immediate8Bit)
+	pub fn pushq_Immediate8Bit(&mut self, arg0: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(8);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -56441,6 +56689,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(2);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -56466,10 +56716,12 @@ impl<'a> InstructionStream<'a>
 
 	/// Push `imm8` (sign-extended to 16-bits).
 	#[inline(always)]
-	pub fn pushw_Immediate8Bit(&mut self, arg0: Immediate8Bit)
+	pub fn pushw_Immediate8Bit(&mut self, arg0: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted(2);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -58436,6 +58688,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Read a 16-bit random number and store in the destination register.
+	///
+	/// Sets `CF` if a random number was available and clears it (and zeroes the destination) if not; callers must check `CF` (eg with `jnc_Label()`) and retry a bounded number of times.
 	#[inline(always)]
 	pub fn rdrand_Register16Bit(&mut self, arg0: Register16Bit)
 	{
@@ -58465,6 +58719,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Read a 32-bit random number and store in the destination register.
+	///
+	/// Sets `CF` if a random number was available and clears it (and zeroes the destination) if not; callers must check `CF` (eg with `jnc_Label()`) and retry a bounded number of times.
 	#[inline(always)]
 	pub fn rdrand_Register32Bit(&mut self, arg0: Register32Bit)
 	{
@@ -58494,6 +58750,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Read a 64-bit random number and store in the destination register.
+	///
+	/// Sets `CF` if a random number was available and clears it (and zeroes the destination) if not; callers must check `CF` (eg with `jnc_Label()`) and retry a bounded number of times.
 	#[inline(always)]
 	pub fn rdrand_Register64Bit(&mut self, arg0: Register64Bit)
 	{
@@ -58522,6 +58780,99 @@ impl<'a> InstructionStream<'a>
 		// No label displacement.
 	}
 
+	/// Read a 16-bit random seed and store in the destination register.
+	///
+	/// Unlike `RDRAND`, the value comes directly from the entropy source and is suitable for seeding another generator. Sets `CF` if a seed was available and clears it (and zeroes the destination) if not; callers must check `CF` (eg with `jnc_Label()`) and retry, possibly after a `PAUSE`, since the entropy source is more easily exhausted than `RDRAND`.
+	#[inline(always)]
+	pub fn rdseed_Register16Bit(&mut self, arg0: Register16Bit)
+	{
+		self.reserve_space_for_instruction();
+
+		// This is not a VEX encoded instruction.
+
+		// No `FWAIT` Prefix.
+
+		// No prefix group 2.
+
+		// No prefix group 4.
+
+		self.prefix_group3();
+
+		// No prefix group 1.
+
+		self.rex_2(arg0, 0x00);
+
+		self.opcode_2(0x0F, 0xC7);
+
+		self.mod_rm_sib(arg0, Register64Bit::RDI);
+
+		// No displacement or immediate.
+
+		// No label displacement.
+	}
+
+	/// Read a 32-bit random seed and store in the destination register.
+	///
+	/// Unlike `RDRAND`, the value comes directly from the entropy source and is suitable for seeding another generator. Sets `CF` if a seed was available and clears it (and zeroes the destination) if not; callers must check `CF` (eg with `jnc_Label()`) and retry, possibly after a `PAUSE`, since the entropy source is more easily exhausted than `RDRAND`.
+	#[inline(always)]
+	pub fn rdseed_Register32Bit(&mut self, arg0: Register32Bit)
+	{
+		self.reserve_space_for_instruction();
+
+		// This is not a VEX encoded instruction.
+
+		// No `FWAIT` Prefix.
+
+		// No prefix group 2.
+
+		// No prefix group 4.
+
+		// No prefix group 3.
+
+		// No prefix group 1.
+
+		self.rex_2(arg0, 0x00);
+
+		self.opcode_2(0x0F, 0xC7);
+
+		self.mod_rm_sib(arg0, Register64Bit::RDI);
+
+		// No displacement or immediate.
+
+		// No label displacement.
+	}
+
+	/// Read a 64-bit random seed and store in the destination register.
+	///
+	/// Unlike `RDRAND`, the value comes directly from the entropy source and is suitable for seeding another generator. Sets `CF` if a seed was available and clears it (and zeroes the destination) if not; callers must check `CF` (eg with `jnc_Label()`) and retry, possibly after a `PAUSE`, since the entropy source is more easily exhausted than `RDRAND`.
+	#[inline(always)]
+	pub fn rdseed_Register64Bit(&mut self, arg0: Register64Bit)
+	{
+		self.reserve_space_for_instruction();
+
+		// This is not a VEX encoded instruction.
+
+		// No `FWAIT` Prefix.
+
+		// No prefix group 2.
+
+		// No prefix group 4.
+
+		// No prefix group 3.
+
+		// No prefix group 1.
+
+		self.rex_2(arg0, Self::REX_W);
+
+		self.opcode_2(0x0F, 0xC7);
+
+		self.mod_rm_sib(arg0, Register64Bit::RDI);
+
+		// No displacement or immediate.
+
+		// No label displacement.
+	}
+
 	/// Input `(E)CX` words from port `DX` into `ES:[(E)DI]`.
 	#[inline(always)]
 	pub fn rep_ins_Any16BitMemory_DX(&mut self, arg0: Option<SegmentRegister>, address_override_for_32_bit: bool)
@@ -63842,7 +64193,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Subtract with borrow sign-extended `imm8` from `r/m16`.
 	#[inline(always)]
-	pub fn sbb_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: Immediate8Bit)
+	pub fn sbb_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -63929,7 +64280,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Subtract with borrow sign-extended `imm8` from `r/m32`.
 	#[inline(always)]
-	pub fn sbb_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: Immediate8Bit)
+	pub fn sbb_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -64016,7 +64367,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Subtract with borrow sign-extended `imm8` from `r/m64`.
 	#[inline(always)]
-	pub fn sbb_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: Immediate8Bit)
+	pub fn sbb_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -64190,7 +64541,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Subtract with borrow sign-extended `imm8` from `r/m16`.
 	#[inline(always)]
-	pub fn sbb_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: Immediate8Bit)
+	pub fn sbb_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -64335,7 +64686,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Subtract with borrow sign-extended `imm8` from `r/m32`.
 	#[inline(always)]
-	pub fn sbb_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: Immediate8Bit)
+	pub fn sbb_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -64480,7 +64831,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Subtract with borrow sign-extended `imm8` from `r/m64`.
 	#[inline(always)]
-	pub fn sbb_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: Immediate8Bit)
+	pub fn sbb_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -70745,6 +71096,8 @@ impl<'a> InstructionStream<'a>
 	/// Set interrupt flag.
 	///
 	/// External, maskable interrupts are enabled at the end of the next instruction.
+	///
+	/// Privileged; raises a general protection fault in user mode (unless `IOPL` permits it).
 	#[inline(always)]
 	pub fn sti(&mut self)
 	{
@@ -71164,7 +71517,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Subtract sign-extended `imm8` from `r/m16`.
 	#[inline(always)]
-	pub fn sub_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: Immediate8Bit)
+	pub fn sub_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -71251,7 +71604,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Subtract sign-extended `imm8` from `r/m32`.
 	#[inline(always)]
-	pub fn sub_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: Immediate8Bit)
+	pub fn sub_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -71338,7 +71691,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Subtract sign-extended `imm8` from `r/m64`.
 	#[inline(always)]
-	pub fn sub_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: Immediate8Bit)
+	pub fn sub_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -71512,7 +71865,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Subtract sign-extended `imm8` from `r/m16`.
 	#[inline(always)]
-	pub fn sub_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: Immediate8Bit)
+	pub fn sub_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -71657,7 +72010,7 @@ impl<'a> InstructionStream<'a>
 
 	/// Subtract sign-extended `imm8` from `r/m32`.
 	#[inline(always)]
-	pub fn sub_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: Immediate8Bit)
+	pub fn sub_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -71777,6 +72130,8 @@ impl<'a> InstructionStream<'a>
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted_if_RSP(arg0, arg1.0 as isize);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -71802,10 +72157,12 @@ impl<'a> InstructionStream<'a>
 
 	/// Subtract sign-extended `imm8` from `r/m64`.
 	#[inline(always)]
-	pub fn sub_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: Immediate8Bit)
+	pub fn sub_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
+		self.stack_delta_adjusted_if_RSP(arg0, arg1.0 as isize);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -73794,6 +74151,8 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Raise invalid opcode exception ('Undefined 2').
+	///
+	/// Use to mark code which should never be executed (eg after a call which does not return, or the default of an exhaustive jump table), so that it faults predictably (`SIGILL`) rather than running into whatever follows.
 	#[inline(always)]
 	pub fn ud2(&mut self)
 	{
@@ -110110,9 +110469,17 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Exchange `r32` with `EAX`.
+	///
+	/// `XCHG EAX, EAX` is encoded as `87 C0`, as the short form, `90`, is `NOP`.
 	#[inline(always)]
 	pub fn xchg_EAX_Register32Bit(&mut self, arg1: Register32Bit)
 	{
+		if arg1 == Register32Bit::EAX
+		{
+			// `90` is `NOP`, which, unlike `XCHG EAX, EAX`, does not zero the upper 32 bits of `RAX`.
+			return self.xchg_Register32Bit_Register32Bit(arg1, arg1)
+		}
+		
 		self.reserve_space_for_instruction();
 
 		// This is not a VEX encoded instruction.
@@ -110400,9 +110767,17 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Exchange `EAX` with `r32`.
+	///
+	/// `XCHG EAX, EAX` is encoded as `87 C0`, as the short form, `90`, is `NOP`.
 	#[inline(always)]
 	pub fn xchg_Register32Bit_EAX(&mut self, arg0: Register32Bit)
 	{
+		if arg0 == Register32Bit::EAX
+		{
+			// `90` is `NOP`, which, unlike `XCHG EAX, EAX`, does not zero the upper 32 bits of `RAX`.
+			return self.xchg_Register32Bit_Register32Bit(arg0, arg0)
+		}
+		
 		self.reserve_space_for_instruction();
 
 		// This is not a VEX encoded instruction.
@@ -111213,7 +111588,7 @@ impl<'a> InstructionStream<'a>
 
 	/// `r/m16` XOR `imm8` (sign-extended).
 	#[inline(always)]
-	pub fn xor_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: Immediate8Bit)
+	pub fn xor_Any16BitMemory_Immediate8Bit(&mut self, arg0: Any16BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -111300,7 +111675,7 @@ impl<'a> InstructionStream<'a>
 
 	/// `r/m32` XOR `imm8` (sign-extended).
 	#[inline(always)]
-	pub fn xor_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: Immediate8Bit)
+	pub fn xor_Any32BitMemory_Immediate8Bit(&mut self, arg0: Any32BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -111387,7 +111762,7 @@ impl<'a> InstructionStream<'a>
 
 	/// `r/m64` XOR `imm8` (sign-extended).
 	#[inline(always)]
-	pub fn xor_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: Immediate8Bit)
+	pub fn xor_Any64BitMemory_Immediate8Bit(&mut self, arg0: Any64BitMemory, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -111561,7 +111936,7 @@ impl<'a> InstructionStream<'a>
 
 	/// `r/m16` XOR `imm8` (sign-extended).
 	#[inline(always)]
-	pub fn xor_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: Immediate8Bit)
+	pub fn xor_Register16Bit_Immediate8Bit(&mut self, arg0: Register16Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -111706,7 +112081,7 @@ impl<'a> InstructionStream<'a>
 
 	/// `r/m32` XOR `imm8` (sign-extended).
 	#[inline(always)]
-	pub fn xor_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: Immediate8Bit)
+	pub fn xor_Register32Bit_Immediate8Bit(&mut self, arg0: Register32Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -111851,7 +112226,7 @@ impl<'a> InstructionStream<'a>
 
 	/// `r/m64` XOR `imm8` (sign-extended).
 	#[inline(always)]
-	pub fn xor_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: Immediate8Bit)
+	pub fn xor_Register64Bit_Immediate8Bit(&mut self, arg0: Register64Bit, arg1: SignExtendedImmediate8Bit)
 	{
 		self.reserve_space_for_instruction();
 
@@ -112488,11 +112863,15 @@ impl<'a> InstructionStream<'a>
 	/// Restore processor extended states from memory.
 	///
 	/// The states are specified by `EDX:EAX`.
+	///
+	/// The operating system must have enabled the states to save or restore in `XCR0` (using `XSETBV`); only states enabled in both `XCR0` and `EDX:EAX` are saved or restored.
 	#[inline(always)]
 	pub fn xrstor_Any16BitMemory(&mut self, arg0: Any16BitMemory)
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 64);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -112519,11 +112898,15 @@ impl<'a> InstructionStream<'a>
 	/// Restore processor extended states from memory.
 	///
 	/// The states are specified by `EDX:EAX`.
+	///
+	/// The operating system must have enabled the states to save or restore in `XCR0` (using `XSETBV`); only states enabled in both `XCR0` and `EDX:EAX` are saved or restored.
 	#[inline(always)]
 	pub fn xrstor_Any32BitMemory(&mut self, arg0: Any32BitMemory)
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 64);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -112550,11 +112933,15 @@ impl<'a> InstructionStream<'a>
 	/// Restore processor extended states from memory.
 	///
 	/// The states are specified by `EDX:EAX`.
+	///
+	/// The operating system must have enabled the states to save or restore in `XCR0` (using `XSETBV`); only states enabled in both `XCR0` and `EDX:EAX` are saved or restored.
 	#[inline(always)]
 	pub fn xrstor_Any64BitMemory(&mut self, arg0: Any64BitMemory)
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 64);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -112581,11 +112968,15 @@ impl<'a> InstructionStream<'a>
 	/// Restore processor extended states from memory.
 	///
 	/// The states are specified by `EDX:EAX`.
+	///
+	/// The operating system must have enabled the states to save or restore in `XCR0` (using `XSETBV`); only states enabled in both `XCR0` and `EDX:EAX` are saved or restored.
 	#[inline(always)]
 	pub fn xrstor64_Any16BitMemory(&mut self, arg0: Any16BitMemory)
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 64);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -112612,11 +113003,15 @@ impl<'a> InstructionStream<'a>
 	/// Restore processor extended states from memory.
 	///
 	/// The states are specified by `EDX:EAX`.
+	///
+	/// The operating system must have enabled the states to save or restore in `XCR0` (using `XSETBV`); only states enabled in both `XCR0` and `EDX:EAX` are saved or restored.
 	#[inline(always)]
 	pub fn xrstor64_Any32BitMemory(&mut self, arg0: Any32BitMemory)
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 64);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -112643,11 +113038,15 @@ impl<'a> InstructionStream<'a>
 	/// Restore processor extended states from memory.
 	///
 	/// The states are specified by `EDX:EAX`.
+	///
+	/// The operating system must have enabled the states to save or restore in `XCR0` (using `XSETBV`); only states enabled in both `XCR0` and `EDX:EAX` are saved or restored.
 	#[inline(always)]
 	pub fn xrstor64_Any64BitMemory(&mut self, arg0: Any64BitMemory)
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 64);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -112674,11 +113073,15 @@ impl<'a> InstructionStream<'a>
 	/// Save processor extended states to memory.
 	///
 	/// The states are specified by `EDX:EAX`.
+	///
+	/// The operating system must have enabled the states to save or restore in `XCR0` (using `XSETBV`); only states enabled in both `XCR0` and `EDX:EAX` are saved or restored.
 	#[inline(always)]
 	pub fn xsave_Any16BitMemory(&mut self, arg0: Any16BitMemory)
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 64);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -112705,11 +113108,15 @@ impl<'a> InstructionStream<'a>
 	/// Save processor extended states to memory.
 	///
 	/// The states are specified by `EDX:EAX`.
+	///
+	/// The operating system must have enabled the states to save or restore in `XCR0` (using `XSETBV`); only states enabled in both `XCR0` and `EDX:EAX` are saved or restored.
 	#[inline(always)]
 	pub fn xsave_Any32BitMemory(&mut self, arg0: Any32BitMemory)
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 64);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -112736,11 +113143,15 @@ impl<'a> InstructionStream<'a>
 	/// Save processor extended states to memory.
 	///
 	/// The states are specified by `EDX:EAX`.
+	///
+	/// The operating system must have enabled the states to save or restore in `XCR0` (using `XSETBV`); only states enabled in both `XCR0` and `EDX:EAX` are saved or restored.
 	#[inline(always)]
 	pub fn xsave_Any64BitMemory(&mut self, arg0: Any64BitMemory)
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 64);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -112767,11 +113178,15 @@ impl<'a> InstructionStream<'a>
 	/// Save processor extended states to memory.
 	///
 	/// The states are specified by `EDX:EAX`.
+	///
+	/// The operating system must have enabled the states to save or restore in `XCR0` (using `XSETBV`); only states enabled in both `XCR0` and `EDX:EAX` are saved or restored.
 	#[inline(always)]
 	pub fn xsave64_Any16BitMemory(&mut self, arg0: Any16BitMemory)
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 64);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -112798,11 +113213,15 @@ impl<'a> InstructionStream<'a>
 	/// Save processor extended states to memory.
 	///
 	/// The states are specified by `EDX:EAX`.
+	///
+	/// The operating system must have enabled the states to save or restore in `XCR0` (using `XSETBV`); only states enabled in both `XCR0` and `EDX:EAX` are saved or restored.
 	#[inline(always)]
 	pub fn xsave64_Any32BitMemory(&mut self, arg0: Any32BitMemory)
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 64);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -112829,11 +113248,15 @@ impl<'a> InstructionStream<'a>
 	/// Save processor extended states to memory.
 	///
 	/// The states are specified by `EDX:EAX`.
+	///
+	/// The operating system must have enabled the states to save or restore in `XCR0` (using `XSETBV`); only states enabled in both `XCR0` and `EDX:EAX` are saved or restored.
 	#[inline(always)]
 	pub fn xsave64_Any64BitMemory(&mut self, arg0: Any64BitMemory)
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 64);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -112858,11 +113281,15 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Save processor extended states specified in `EDX:EAX` to memory, optimizing the state save operation if possible.
+	///
+	/// The operating system must have enabled the states to save or restore in `XCR0` (using `XSETBV`); only states enabled in both `XCR0` and `EDX:EAX` are saved or restored.
 	#[inline(always)]
 	pub fn xsaveopt_Any16BitMemory(&mut self, arg0: Any16BitMemory)
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 64);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -112887,11 +113314,15 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Save processor extended states specified in `EDX:EAX` to memory, optimizing the state save operation if possible.
+	///
+	/// The operating system must have enabled the states to save or restore in `XCR0` (using `XSETBV`); only states enabled in both `XCR0` and `EDX:EAX` are saved or restored.
 	#[inline(always)]
 	pub fn xsaveopt_Any32BitMemory(&mut self, arg0: Any32BitMemory)
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 64);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -112916,11 +113347,15 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Save processor extended states specified in `EDX:EAX` to memory, optimizing the state save operation if possible.
+	///
+	/// The operating system must have enabled the states to save or restore in `XCR0` (using `XSETBV`); only states enabled in both `XCR0` and `EDX:EAX` are saved or restored.
 	#[inline(always)]
 	pub fn xsaveopt_Any64BitMemory(&mut self, arg0: Any64BitMemory)
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 64);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -112945,11 +113380,15 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Save processor extended states specified in `EDX:EAX` to memory, optimizing the state save operation if possible.
+	///
+	/// The operating system must have enabled the states to save or restore in `XCR0` (using `XSETBV`); only states enabled in both `XCR0` and `EDX:EAX` are saved or restored.
 	#[inline(always)]
 	pub fn xsaveopt64_Any16BitMemory(&mut self, arg0: Any16BitMemory)
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 64);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -112974,11 +113413,15 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Save processor extended states specified in `EDX:EAX` to memory, optimizing the state save operation if possible.
+	///
+	/// The operating system must have enabled the states to save or restore in `XCR0` (using `XSETBV`); only states enabled in both `XCR0` and `EDX:EAX` are saved or restored.
 	#[inline(always)]
 	pub fn xsaveopt64_Any32BitMemory(&mut self, arg0: Any32BitMemory)
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 64);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -113003,11 +113446,15 @@ impl<'a> InstructionStream<'a>
 	}
 
 	/// Save processor extended states specified in `EDX:EAX` to memory, optimizing the state save operation if possible.
+	///
+	/// The operating system must have enabled the states to save or restore in `XCR0` (using `XSETBV`); only states enabled in both `XCR0` and `EDX:EAX` are saved or restored.
 	#[inline(always)]
 	pub fn xsaveopt64_Any64BitMemory(&mut self, arg0: Any64BitMemory)
 	{
 		self.reserve_space_for_instruction();
 
+		self.debug_assert_memory_is_aligned(arg0, 64);
+
 		// This is not a VEX encoded instruction.
 
 		// No `FWAIT` Prefix.
@@ -8,24 +8,37 @@
 ///
 /// When finished, call `finish()`.
 ///
-/// When writing 8-bit `Jcc` (`JMP` and conditional `JMP` instructions), a `ShortJmpResult` is returned in error if the target effective address could be resolved and its displacement exceeds the size of an `i8`. In this case, the instruction stream is rolled back to point to just before where the instruction started to be emitted. Use this result to try to make a 8-bit `JMP` and then replace it with a 32-bit one if an error occurs.
+/// Operand widths are checked at compile time: each register parameter is typed by its width (eg `Register64Bit` or `Register32Bit`) and the function name spells out every operand (eg `mov_Register64Bit_Register64Bit_r64_rm64()`), so `mov rax, ecx` can not be expressed. Mnemonics which genuinely mix widths, such as `movzx_Register64Bit_Register8Bit()`, say so in their name.
+///
+/// When writing 8-bit `Jcc` (`JMP` and conditional `JMP` instructions), a `ShortJmpResult` is returned in error if the target effective address could be resolved and its displacement exceeds the size of an `i8`. In this case, the instruction stream is rolled back to point to just before where the instruction started to be emitted, and the `ShortJumpTooFarError` records the label and displacement. Use this result to try to make a 8-bit `JMP` and then replace it with a 32-bit one if an error occurs.
 ///
 /// Note that unresolved labels (ie those yet to be attached to a location in the instruction stream) will not produce such an error. Instead a panic (in debug builds) or silent error will occur when `finish()` is called.
 #[derive(Debug)]
 pub struct InstructionStream<'a>
 {
 	byte_emitter: ByteEmitter,
-	executable_anonymous_memory_map: &'a mut ExecutableAnonymousMemoryMap,
+	#[cfg(feature = "std")]
+	executable_anonymous_memory_map: Option<&'a mut ExecutableAnonymousMemoryMap>,
+	buffer: PhantomData<&'a mut [u8]>,
 	labelled_locations: LabelledLocations,
 	instruction_pointers_to_replace_labels_with_8_bit_displacements: Vec<(Label, InstructionPointer)>,
 	instruction_pointers_to_replace_labels_with_32_bit_displacements: Vec<(Label, InstructionPointer)>,
 	emitted_labels: Vec<(Label, InstructionPointer)>,
+	relaxable_jumps: Vec<(Label, InstructionPointer, u8)>,
+	position_dependent_locations: Vec<InstructionPointer>,
+	sections: Vec<Section>,
+	current_section: SectionId,
+	stack_delta: isize,
+	frame_pointer_stack_delta: isize,
+	start_of_last_instruction: InstructionPointer,
+	instruction_hook: InstructionHook<'a>,
+	annotations: Vec<(InstructionPointer, u32)>,
 }
 
 impl<'a> InstructionStream<'a>
 {
 	/// `REX.W` prefix.
-	const REX_W: u8 = 0x48;
+	pub(crate) const REX_W: u8 = 0x48;
 	
 	/// `REX.R` prefix.
 	pub(crate) const REX_R: u8 = 0x44;
@@ -39,36 +52,123 @@ impl<'a> InstructionStream<'a>
 	/// `REX` prefix.
 	pub(crate) const REX: u8 = 0x40;
 	
+	#[cfg(feature = "std")]
 	#[inline(always)]
 	pub(crate) fn new(executable_anonymous_memory_map: &'a mut ExecutableAnonymousMemoryMap, instruction_stream_hints: &InstructionStreamHints) -> Self
 	{
 		executable_anonymous_memory_map.make_writable();
 		
+		let mut this = Self::with_byte_emitter(ByteEmitter::new(executable_anonymous_memory_map), instruction_stream_hints);
+		this.executable_anonymous_memory_map = Some(executable_anonymous_memory_map);
+		
+		// If the map can not grow, emitting will panic only if the hint was accurate.
+		let _ = this.try_reserve_space(instruction_stream_hints.number_of_bytes);
+		
+		this
+	}
+	
+	/// Creates an instruction stream which emits into `buffer`, eg for use without an operating system (when the crate is `no_std`) or to assemble code that will be executed elsewhere.
+	///
+	/// The buffer can not grow; emitting more than fits panics.
+	/// `finish()` and its variants resolve labels but do not (and can not) make `buffer` executable; `finish_with_entry()` panics.
+	#[inline(always)]
+	pub fn new_in_buffer(buffer: &'a mut [u8], instruction_stream_hints: &InstructionStreamHints) -> Self
+	{
+		Self::with_byte_emitter(ByteEmitter::for_buffer(buffer), instruction_stream_hints)
+	}
+	
+	#[inline(always)]
+	fn with_byte_emitter(byte_emitter: ByteEmitter, instruction_stream_hints: &InstructionStreamHints) -> Self
+	{
+		let start_of_last_instruction = byte_emitter.instruction_pointer;
+		
 		Self
 		{
-			byte_emitter: ByteEmitter::new(executable_anonymous_memory_map),
-			executable_anonymous_memory_map,
+			byte_emitter,
+			#[cfg(feature = "std")]
+			executable_anonymous_memory_map: None,
+			buffer: PhantomData,
 			labelled_locations: LabelledLocations::new(instruction_stream_hints.number_of_labels),
 			instruction_pointers_to_replace_labels_with_8_bit_displacements: Vec::with_capacity(instruction_stream_hints.number_of_8_bit_jumps),
 			instruction_pointers_to_replace_labels_with_32_bit_displacements: Vec::with_capacity(instruction_stream_hints.number_of_32_bit_jumps),
 			emitted_labels: Vec::with_capacity(instruction_stream_hints.number_of_emitted_labels),
+			relaxable_jumps: Vec::new(),
+			position_dependent_locations: Vec::new(),
+			sections: Vec::new(),
+			current_section: SectionId::Default,
+			stack_delta: 0,
+			frame_pointer_stack_delta: 0,
+			start_of_last_instruction,
+			instruction_hook: InstructionHook::default(),
+			annotations: Vec::new(),
+		}
+	}
+	
+	/// Creates an instruction stream in `executable_anonymous_memory_map` which continues from `snapshot`, as if it had been used to emit the snapshot's bytes, create its labels and emit its jumps (including those to labels not yet attached) and labels.
+	///
+	/// Labels are recreated with the same indices, so `Label`s used before the snapshot was taken remain valid.
+	///
+	/// Anything else that depended on the snapshot's original location, such as `RIP`-relative displacements to absolute addresses, is invalid.
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	pub fn from_snapshot(executable_anonymous_memory_map: &'a mut ExecutableAnonymousMemoryMap, snapshot: &AssembledSnapshot) -> Self
+	{
+		let mut this = Self::new(executable_anonymous_memory_map, &snapshot.hints());
+		
+		let start_instruction_pointer = this.start_instruction_pointer();
+		this.emit_bytes(&snapshot.bytes);
+		this.start_of_last_instruction = this.instruction_pointer();
+		
+		for label_offset in snapshot.label_offsets.iter()
+		{
+			let label = this.create_label();
+			if let Some(label_offset) = *label_offset
+			{
+				this.labelled_locations.set(label, start_instruction_pointer + label_offset)
+			}
+		}
+		
+		let rebase = |&(label, offset): &(Label, usize)| (label, start_instruction_pointer + offset);
+		this.instruction_pointers_to_replace_labels_with_8_bit_displacements.extend(snapshot.offsets_to_replace_labels_with_8_bit_displacements.iter().map(rebase));
+		this.instruction_pointers_to_replace_labels_with_32_bit_displacements.extend(snapshot.offsets_to_replace_labels_with_32_bit_displacements.iter().map(rebase));
+		this.emitted_labels.extend(snapshot.emitted_labels.iter().map(rebase));
+		this.relaxable_jumps.extend(snapshot.relaxable_jumps.iter().map(|&(label, offset, short_opcode)| (label, start_instruction_pointer + offset, short_opcode)));
+		
+		for &(label, insert_at_instruction_pointer) in this.emitted_labels.iter()
+		{
+			let target_instruction_pointer = this.target_instruction_pointer(label);
+			if target_instruction_pointer.is_valid()
+			{
+				this.byte_emitter.emit_u64_at(target_instruction_pointer as u64, insert_at_instruction_pointer)
+			}
 		}
+		
+		this
 	}
 	
-	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[cfg(all(feature = "std", any(target_os = "android", target_os = "linux")))]
 	#[inline(always)]
-	fn attempt_to_resize_in_place(&mut self) -> io::Result<()>
+	fn attempt_to_resize_in_place(&mut self) -> Result<(), ()>
 	{
-		let new_length = self.executable_anonymous_memory_map.attempt_to_resize_in_place_whilst_writing()?;
-		self.byte_emitter.end_instruction_pointer += new_length;
+		let executable_anonymous_memory_map = match self.sections.get_mut(self.current_section.0).and_then(|section| section.executable_anonymous_memory_map.as_mut())
+		{
+			Some(executable_anonymous_memory_map) => executable_anonymous_memory_map,
+			None => match self.executable_anonymous_memory_map
+			{
+				Some(ref mut executable_anonymous_memory_map) => &mut **executable_anonymous_memory_map,
+				None => return Err(()),
+			},
+		};
+		let new_length = executable_anonymous_memory_map.attempt_to_resize_in_place_whilst_writing().map_err(|_| ())?;
+		self.byte_emitter.end_instruction_pointer = executable_anonymous_memory_map.address as usize + new_length;
 		Ok(())
 	}
 	
-	#[cfg(not(any(target_os = "android", target_os = "linux")))]
+	#[cfg(not(all(feature = "std", any(target_os = "android", target_os = "linux"))))]
 	#[inline(always)]
-	fn attempt_to_resize_in_place(&mut self) -> io::Result<()>
+	fn attempt_to_resize_in_place(&mut self) -> Result<(), ()>
 	{
-		Err(io::Error::new(io::ErrorKind::Other, "Could not resize in place"))
+		Err(())
 	}
 	
 	/// Returns `(number_of_labels, number_of_8_bit_jumps, number_of_32_bit_jumps)` which can be used as input to tweak the next version.
@@ -81,6 +181,7 @@ impl<'a> InstructionStream<'a>
 			number_of_8_bit_jumps: self.instruction_pointers_to_replace_labels_with_8_bit_displacements.len(),
 			number_of_32_bit_jumps: self.instruction_pointers_to_replace_labels_with_32_bit_displacements.len(),
 			number_of_emitted_labels: self.emitted_labels.len(),
+			number_of_bytes: self.instruction_pointer() - self.start_instruction_pointer(),
 		}
 	}
 	
@@ -94,13 +195,118 @@ impl<'a> InstructionStream<'a>
 	{
 		let hints = self.hints_for_next_instance();
 		
+		self.lay_out_sections();
+		
+		self.resolve_labels_and_make_executable();
+		
+		let length = self.instruction_pointer() - self.start_instruction_pointer();
+		let slice = unsafe { from_raw_parts(self.start_instruction_pointer() as *const u8, length) };
+		(slice, hints)
+	}
+	
+	/// As for `finish()`, but returns the address of the location labelled by each of `entries`, keyed by its name, instead of the instructions encoded.
+	///
+	/// Useful for a compilation unit of several functions; cast the addresses to function pointers to call them.
+	///
+	/// Will panic in debug builds if labels can not be resolved (including those in `entries`), 8-bit JMPs are too far away or 32-bit JMPs have displacements of more than 2Gb!
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	pub fn finish_functions(mut self, entries: &[(&str, Label)]) -> (HashMap<String, *const u8>, InstructionStreamHints)
+	{
+		let hints = self.hints_for_next_instance();
+		
+		self.lay_out_sections();
+		
+		self.resolve_labels_and_make_executable();
+		
+		let addresses = entries.iter().map(|&(name, label)| (name.to_string(), self.valid_target_instruction_pointer(label) as *const u8)).collect();
+		(addresses, hints)
+	}
+	
+	/// As for `finish()`, but also returns the annotations added with `annotate()`, as `(offset, tag)` sorted by offset, where offset is relative to the start of the returned slice.
+	///
+	/// Annotations in sections other than the default are adjusted for where `finish()` moves the section to.
+	#[inline(always)]
+	pub fn finish_with_annotations(mut self) -> (&'a [u8], InstructionStreamHints, Vec<(usize, u32)>)
+	{
+		let hints = self.hints_for_next_instance();
+		
+		self.lay_out_sections();
+		
+		self.resolve_labels_and_make_executable();
+		
+		let start_instruction_pointer = self.start_instruction_pointer();
+		let mut annotations: Vec<(usize, u32)> = self.annotations.iter().map(|&(instruction_pointer, tag)| (instruction_pointer - start_instruction_pointer, tag)).collect();
+		annotations.sort_by_key(|&(offset, _)| offset);
+		
+		let length = self.instruction_pointer() - start_instruction_pointer;
+		let slice = unsafe { from_raw_parts(start_instruction_pointer as *const u8, length) };
+		(slice, hints, annotations)
+	}
+	
+	/// As for `finish()`, but first shrinks near (32-bit) jumps emitted by `jmp_Label_auto()` and `jcc_Label_auto()` to short (8-bit) jumps where the final layout permits.
+	///
+	/// Shrinking one jump moves the code after it, which can bring other jumps into range, so this is repeated until nothing changes or a maximum of 16 passes have been made.
+	/// Each pass costs O(n log n) for n such jumps, followed by a single pass to move the code.
+	///
+	/// Jumps before alignment padding (from `emit_alignment()`, `emit_alignment_with_fill()` or the start of a section) or a patch point from `reserve_patch_point()` are not shrunk, so that these stay where they were emitted.
+	///
+	/// Since code after a shrunk jump moves, anything else that depends on the absolute location of emitted code is invalidated; this includes function pointers created with `*_function_pointer()` (use labels and `finish_with_entry()` instead), `RIP`-relative displacements to absolute addresses and `statically_relative_address()`.
+	/// Labels, and all jumps to labels and labels emitted with `emit_label()`, are adjusted.
+	#[inline(always)]
+	pub fn finish_relaxed(mut self) -> (&'a [u8], InstructionStreamHints)
+	{
+		let hints = self.hints_for_next_instance();
+		
+		self.lay_out_sections();
+		
+		self.relax_jumps();
+		
+		self.resolve_labels_and_make_executable();
+		
+		let length = self.instruction_pointer() - self.start_instruction_pointer();
+		let slice = unsafe { from_raw_parts(self.start_instruction_pointer() as *const u8, length) };
+		(slice, hints)
+	}
+	
+	/// Resolves all remaining labels and makes code executable, returning a function pointer to the location labelled by `entry`.
+	///
+	/// Unlike the `*_function_pointer()` methods, the returned `JitFunction` borrows the executable anonymous memory map, so the function pointer can not outlive it.
+	///
+	/// Will panic in debug builds if labels can not be resolved (including `entry`), 8-bit JMPs are too far away or 32-bit JMPs have displacements of more than 2Gb!
+	///
+	/// Panics if this instruction stream was created with `new_in_buffer()`.
+	///
+	/// # Safety
+	///
+	/// `F` must be an `unsafe extern "C" fn` type, eg `unsafe extern "C" fn(u64) -> u64`, whose signature matches the code emitted at `entry`; it is not possible to check this.
+	/// A safe `fn` type would let safe code call the emitted code.
+	/// A type which is not the size of a pointer fails to compile.
+	#[cfg(all(feature = "std", target_arch = "x86_64"))]
+	#[inline(always)]
+	pub unsafe fn finish_with_entry<F: Copy>(mut self, entry: Label) -> (JitFunction<'a, F>, InstructionStreamHints)
+	{
+		let hints = self.hints_for_next_instance();
+		
+		self.lay_out_sections();
+		
+		self.resolve_labels_and_make_executable();
+		
+		let entry_instruction_pointer = self.valid_target_instruction_pointer(entry);
+		let executable_anonymous_memory_map: &'a ExecutableAnonymousMemoryMap = self.executable_anonymous_memory_map.take().expect("finish_with_entry() needs an instruction stream created from an ExecutableAnonymousMemoryMap");
+		(unsafe { JitFunction::new(executable_anonymous_memory_map, entry_instruction_pointer) }, hints)
+	}
+	
+	#[inline(always)]
+	fn resolve_labels_and_make_executable(&mut self)
+	{
 		for (label, insert_at_instruction_pointer) in self.instruction_pointers_to_replace_labels_with_8_bit_displacements.iter()
 		{
 			let target_instruction_pointer = self.valid_target_instruction_pointer(*label);
 			
-			let result = self.byte_emitter.insert_8_bit_effective_address_displacement(*insert_at_instruction_pointer, target_instruction_pointer);
+			let result = self.byte_emitter.insert_8_bit_effective_address_displacement(*label, *insert_at_instruction_pointer, target_instruction_pointer);
 			
-			debug_assert!(result.is_ok(), "8-bit JMP for {:?} was too far", label)
+			debug_assert!(result.is_ok(), "{}", result.unwrap_err())
 		}
 		
 		for (label, insert_at_instruction_pointer) in self.instruction_pointers_to_replace_labels_with_32_bit_displacements.iter()
@@ -119,11 +325,276 @@ impl<'a> InstructionStream<'a>
 			self.byte_emitter.emit_u64_at(target_instruction_pointer as u64, *insert_at_instruction_pointer)
 		}
 		
-		self.executable_anonymous_memory_map.make_executable();
+		#[cfg(feature = "std")]
+		{
+			if let Some(ref mut executable_anonymous_memory_map) = self.executable_anonymous_memory_map
+			{
+				executable_anonymous_memory_map.make_executable();
+				
+				#[cfg(target_arch = "x86_64")] executable_anonymous_memory_map.serialize_instruction_cache();
+			}
+		}
+	}
+	
+	/// Creates a new section, which is laid out after the default section (and any sections created before it) by `finish()`, aligned to `alignment` bytes.
+	///
+	/// Use `switch_section()` to emit into it.
+	///
+	/// Sections are typically used to keep cold code (eg error handling) or data (eg jump tables) away from hot code.
+	///
+	/// `length` is the initial size of the memory used to hold the section before `finish()`; it is rounded up as for `ExecutableAnonymousMemoryMap::new()`, and can grow on Linux.
+	///
+	/// Labels can be used across sections, but as code in a section other than the default is moved by `finish()`, anything else that depends on its absolute location (eg function pointers created with `*_function_pointer()`, or `RIP`-relative displacements to absolute addresses) is invalid.
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	pub fn create_section(&mut self, alignment: usize, length: usize) -> Result<SectionId, ExecutableAnonymousMemoryMapCreationError>
+	{
+		debug_assert!(alignment.is_power_of_two(), "alignment '{}' is not a power of two", alignment);
+		
+		if self.sections.is_empty()
+		{
+			self.sections.push
+			(
+				Section
+				{
+					byte_emitter: self.byte_emitter.clone(),
+					executable_anonymous_memory_map: None,
+					alignment: 1,
+				}
+			);
+		}
 		
-		let length = self.instruction_pointer() - self.start_instruction_pointer();
-		let slice = unsafe { from_raw_parts(self.start_instruction_pointer() as *const u8, length) };
-		(slice, hints)
+		let mut executable_anonymous_memory_map = ExecutableAnonymousMemoryMap::new(length, false, self.executable_anonymous_memory_map.as_ref().is_some_and(|executable_anonymous_memory_map| executable_anonymous_memory_map.ignore_mlock_failure))?;
+		executable_anonymous_memory_map.make_writable();
+		
+		let section_id = SectionId(self.sections.len());
+		self.sections.push
+		(
+			Section
+			{
+				byte_emitter: ByteEmitter::new(&mut executable_anonymous_memory_map),
+				executable_anonymous_memory_map: Some(executable_anonymous_memory_map),
+				alignment,
+			}
+		);
+		Ok(section_id)
+	}
+	
+	/// Redirects subsequent emission to `section`.
+	///
+	/// `section` must have been created by this instance's `create_section()` (or be `SectionId::Default`).
+	#[inline(always)]
+	pub fn switch_section(&mut self, section: SectionId)
+	{
+		if section == self.current_section
+		{
+			return
+		}
+		
+		debug_assert!(section.0 < self.sections.len(), "{:?} was not created by this instruction stream", section);
+		
+		self.sections[self.current_section.0].byte_emitter = self.byte_emitter.clone();
+		self.byte_emitter = self.sections[section.0].byte_emitter.clone();
+		self.current_section = section;
+		self.start_of_last_instruction = self.instruction_pointer();
+	}
+	
+	/// The section currently being emitted into.
+	#[inline(always)]
+	pub fn current_section(&self) -> SectionId
+	{
+		self.current_section
+	}
+	
+	fn lay_out_sections(&mut self)
+	{
+		if self.sections.is_empty()
+		{
+			return
+		}
+		
+		self.switch_section(SectionId::Default);
+		
+		let sections = take(&mut self.sections);
+		for section in sections.iter().skip(1)
+		{
+			self.emit_alignment(section.alignment);
+			
+			let section_start = section.byte_emitter.start_instruction_pointer;
+			let section_end = section.byte_emitter.end_instruction_pointer;
+			let length = section.byte_emitter.instruction_pointer - section_start;
+			
+			let new_section_start = self.instruction_pointer();
+			self.emit_bytes(unsafe { from_raw_parts(section_start as *const u8, length) });
+			
+			self.relocate_instruction_pointers(|instruction_pointer| if instruction_pointer >= section_start && instruction_pointer < section_end
+			{
+				instruction_pointer - section_start + new_section_start
+			}
+			else
+			{
+				instruction_pointer
+			});
+		}
+	}
+	
+	fn relax_jumps(&mut self)
+	{
+		const MaximumPasses: usize = 16;
+		const ShortJumpLength: usize = 2;
+		
+		let mut relaxable_jumps = take(&mut self.relaxable_jumps);
+		if relaxable_jumps.is_empty()
+		{
+			return
+		}
+		
+		// Jumps in sections other than the default are recorded out of order.
+		relaxable_jumps.sort_unstable_by_key(|&(_, start, _)| start);
+		
+		// Shrinking a jump moves the code after it, which would misalign padding and move patch points.
+		if let Some(&last_position_dependent_location) = self.position_dependent_locations.iter().max()
+		{
+			relaxable_jumps.retain(|&(_, start, _)| start >= last_position_dependent_location);
+		}
+		
+		#[inline(always)]
+		fn near_jump_length(short_opcode: u8) -> usize
+		{
+			if short_opcode == 0xEB
+			{
+				5
+			}
+			else
+			{
+				6
+			}
+		}
+		
+		// `removed_before[index]` is the number of bytes removed by shrinking the jumps before `relaxable_jumps[index]`.
+		#[inline(always)]
+		fn relocate(relaxable_jumps: &[(Label, InstructionPointer, u8)], removed_before: &[usize], instruction_pointer: InstructionPointer) -> InstructionPointer
+		{
+			let index = relaxable_jumps.partition_point(|&(_, start, _)| start < instruction_pointer);
+			instruction_pointer - removed_before[index]
+		}
+		
+		let number_of_relaxable_jumps = relaxable_jumps.len();
+		let mut shrunk = vec![false; number_of_relaxable_jumps];
+		let mut removed_before = vec![0; number_of_relaxable_jumps + 1];
+		
+		for _ in 0 .. MaximumPasses
+		{
+			let mut removed = 0;
+			for index in 0 .. number_of_relaxable_jumps
+			{
+				removed_before[index] = removed;
+				if shrunk[index]
+				{
+					removed += near_jump_length(relaxable_jumps[index].2) - ShortJumpLength;
+				}
+			}
+			removed_before[number_of_relaxable_jumps] = removed;
+			
+			let mut changed = false;
+			for (index, &(label, start, _)) in relaxable_jumps.iter().enumerate()
+			{
+				if shrunk[index]
+				{
+					continue
+				}
+				
+				let end = start - removed_before[index] + ShortJumpLength;
+				let target = relocate(&relaxable_jumps, &removed_before, self.valid_target_instruction_pointer(label));
+				let displacement = (target as isize) - (end as isize);
+				
+				if displacement >= (i8::MIN as isize) && displacement <= (i8::MAX as isize)
+				{
+					shrunk[index] = true;
+					changed = true;
+				}
+			}
+			
+			if !changed
+			{
+				break
+			}
+		}
+		
+		let relaxable_jumps: Vec<_> = relaxable_jumps.into_iter().zip(shrunk).filter(|&(_, shrunk)| shrunk).map(|(relaxable_jump, _)| relaxable_jump).collect();
+		if relaxable_jumps.is_empty()
+		{
+			return
+		}
+		
+		let mut removed_before = Vec::with_capacity(relaxable_jumps.len() + 1);
+		let mut shrunk_near_displacements = Vec::with_capacity(relaxable_jumps.len());
+		let mut short_displacements = Vec::with_capacity(relaxable_jumps.len());
+		let mut removed = 0;
+		let mut read_from = self.start_instruction_pointer();
+		for &(label, start, short_opcode) in relaxable_jumps.iter()
+		{
+			let near_jump_length = near_jump_length(short_opcode);
+			
+			unsafe { copy(read_from as *const u8, self.byte_emitter.writable(read_from - removed) as *mut u8, start - read_from) };
+			
+			let short_jump_start = start - removed;
+			self.byte_emitter.emit_u8_at(short_opcode, short_jump_start);
+			short_displacements.push((label, short_jump_start + 1));
+			shrunk_near_displacements.push(start + near_jump_length - 4);
+			
+			removed_before.push(removed);
+			removed += near_jump_length - ShortJumpLength;
+			read_from = start + near_jump_length;
+		}
+		removed_before.push(removed);
+		
+		let end = self.instruction_pointer();
+		unsafe { copy(read_from as *const u8, self.byte_emitter.writable(read_from - removed) as *mut u8, end - read_from) };
+		self.byte_emitter.instruction_pointer = end - removed;
+		
+		self.instruction_pointers_to_replace_labels_with_32_bit_displacements.retain(|&(_, insert_at_instruction_pointer)| shrunk_near_displacements.binary_search(&insert_at_instruction_pointer).is_err());
+		
+		self.relocate_instruction_pointers(|instruction_pointer| relocate(&relaxable_jumps, &removed_before, instruction_pointer));
+		
+		self.instruction_pointers_to_replace_labels_with_8_bit_displacements.extend(short_displacements);
+	}
+	
+	/// Moves every attached label and every recorded location of a label displacement or emitted label.
+	#[inline(always)]
+	fn relocate_instruction_pointers(&mut self, relocate: impl Fn(InstructionPointer) -> InstructionPointer)
+	{
+		for &mut (_, ref mut insert_at_instruction_pointer) in self.instruction_pointers_to_replace_labels_with_8_bit_displacements.iter_mut()
+		{
+			*insert_at_instruction_pointer = relocate(*insert_at_instruction_pointer);
+		}
+		
+		for &mut (_, ref mut insert_at_instruction_pointer) in self.instruction_pointers_to_replace_labels_with_32_bit_displacements.iter_mut()
+		{
+			*insert_at_instruction_pointer = relocate(*insert_at_instruction_pointer);
+		}
+		
+		for &mut (_, ref mut insert_at_instruction_pointer) in self.emitted_labels.iter_mut()
+		{
+			*insert_at_instruction_pointer = relocate(*insert_at_instruction_pointer);
+		}
+		
+		for &mut (_, ref mut start, _) in self.relaxable_jumps.iter_mut()
+		{
+			*start = relocate(*start);
+		}
+		
+		for position_dependent_location in self.position_dependent_locations.iter_mut()
+		{
+			*position_dependent_location = relocate(*position_dependent_location);
+		}
+		
+		for &mut (ref mut instruction_pointer, _) in self.annotations.iter_mut()
+		{
+			*instruction_pointer = relocate(*instruction_pointer);
+		}
+		
+		self.labelled_locations.relocate(relocate);
 	}
 	
 	#[inline(always)]
@@ -140,6 +611,94 @@ impl<'a> InstructionStream<'a>
 		target_instruction_pointer
 	}
 	
+	/// Discards everything emitted so far, so that the instruction stream can be reused (eg to compile another function) without reallocating.
+	///
+	/// Emission restarts at the start of the buffer or executable anonymous memory map; all labels, pending jumps, emitted labels, annotations and sections are forgotten, and the stack delta is zeroed.
+	/// The memory used to track labels and jumps is kept, as is any hook set with `set_instruction_hook()`.
+	///
+	/// `Label`s created before the reset must not be used afterwards.
+	#[inline(always)]
+	pub fn reset(&mut self)
+	{
+		self.switch_section(SectionId::Default);
+		self.sections.clear();
+		
+		self.byte_emitter.reset();
+		self.labelled_locations.reset();
+		self.instruction_pointers_to_replace_labels_with_8_bit_displacements.clear();
+		self.instruction_pointers_to_replace_labels_with_32_bit_displacements.clear();
+		self.emitted_labels.clear();
+		self.relaxable_jumps.clear();
+		self.position_dependent_locations.clear();
+		self.annotations.clear();
+		self.stack_delta = 0;
+		self.frame_pointer_stack_delta = 0;
+		self.start_of_last_instruction = self.instruction_pointer();
+	}
+	
+	/// Records the state of this instruction stream, so that emission can be speculative, eg to try an optimization and then keep or discard it.
+	///
+	/// Unlike the bookmark used to roll back a short jump that is too far, any number of checkpoints can be held.
+	#[inline(always)]
+	pub fn checkpoint(&self) -> Checkpoint
+	{
+		Checkpoint
+		{
+			section: self.current_section,
+			instruction_pointer: self.instruction_pointer(),
+			start_of_last_instruction: self.start_of_last_instruction,
+			number_of_labels: self.labelled_locations.number_of_labels(),
+			number_of_attached_labels: self.labelled_locations.number_of_attached_labels(),
+			number_of_8_bit_jumps: self.instruction_pointers_to_replace_labels_with_8_bit_displacements.len(),
+			number_of_32_bit_jumps: self.instruction_pointers_to_replace_labels_with_32_bit_displacements.len(),
+			number_of_emitted_labels: self.emitted_labels.len(),
+			number_of_relaxable_jumps: self.relaxable_jumps.len(),
+			number_of_position_dependent_locations: self.position_dependent_locations.len(),
+			number_of_annotations: self.annotations.len(),
+			stack_delta: self.stack_delta,
+			frame_pointer_stack_delta: self.frame_pointer_stack_delta,
+		}
+	}
+	
+	/// Discards everything emitted since `checkpoint` was created.
+	///
+	/// Labels created since are forgotten (and must not be used again), labels attached since are detached, and pending jumps, emitted labels and annotations recorded since are discarded.
+	///
+	/// Must be called in the section `checkpoint` was created in, and anything emitted into other sections since is not discarded (but jumps in it may be); keep speculative emission to one section.
+	#[inline(always)]
+	pub fn rollback_to(&mut self, checkpoint: Checkpoint)
+	{
+		debug_assert_eq!(checkpoint.section, self.current_section, "rollback_to() must be called in the section the checkpoint was created in");
+		debug_assert!(checkpoint.instruction_pointer <= self.instruction_pointer(), "checkpoint is no longer valid");
+		
+		self.byte_emitter.instruction_pointer = checkpoint.instruction_pointer;
+		self.start_of_last_instruction = checkpoint.start_of_last_instruction;
+		self.labelled_locations.roll_back(checkpoint.number_of_labels, checkpoint.number_of_attached_labels);
+		self.instruction_pointers_to_replace_labels_with_8_bit_displacements.truncate(checkpoint.number_of_8_bit_jumps);
+		self.instruction_pointers_to_replace_labels_with_32_bit_displacements.truncate(checkpoint.number_of_32_bit_jumps);
+		self.emitted_labels.truncate(checkpoint.number_of_emitted_labels);
+		self.relaxable_jumps.truncate(checkpoint.number_of_relaxable_jumps);
+		self.position_dependent_locations.truncate(checkpoint.number_of_position_dependent_locations);
+		self.annotations.truncate(checkpoint.number_of_annotations);
+		self.stack_delta = checkpoint.stack_delta;
+		self.frame_pointer_stack_delta = checkpoint.frame_pointer_stack_delta;
+	}
+	
+	/// Calls `emission`, rolling back everything it emitted (as for `rollback_to()`) if it returns an error.
+	///
+	/// Useful for emitting several instructions, some of which can fail (eg `jmp_Label()`), all-or-nothing; on error, it is safe to retry with alternatives.
+	#[inline(always)]
+	pub fn transaction<T, E, F: FnOnce(&mut Self) -> Result<T, E>>(&mut self, emission: F) -> Result<T, E>
+	{
+		let checkpoint = self.checkpoint();
+		let result = emission(self);
+		if result.is_err()
+		{
+			self.rollback_to(checkpoint)
+		}
+		result
+	}
+	
 	/// Creates an unique label and uses it to label the current location.
 	#[inline(always)]
 	pub fn create_and_attach_label(&mut self) -> Label
@@ -158,13 +717,31 @@ impl<'a> InstructionStream<'a>
 		self.labelled_locations.create_label()
 	}
 	
-	/// Labels the current location.
+	/// The number of labels created by this instance (since it was created or last `reset()`).
+	#[inline(always)]
+	pub fn label_count(&self) -> usize
+	{
+		self.labelled_locations.number_of_labels()
+	}
+	
+	/// Could `label` have been created by this instance?
+	///
+	/// Labels are just indices, so a label created by another instance is only detected if this instance has not (yet) created as many labels.
+	#[inline(always)]
+	pub fn owns_label(&self, label: Label) -> bool
+	{
+		self.labelled_locations.owns(label)
+	}
+	
+	/// Labels the current location.
 	///
 	/// It is an error to use the same label to label more than one location (or to label the current location with the same label twice or more).
 	///
 	/// This only checked for in debug builds where it causes a runtime panic.
 	///
-	/// Labels should be created using `self.create_label()`; no checks are made for labels created with another instance and attached to this one.
+	/// Several different labels may label the same location, eg an entry point which is also a loop header.
+	///
+	/// Labels should be created using `self.create_label()`; in debug builds, attaching a label which this instance does not own (see `owns_label()`) causes a runtime panic.
 	#[inline(always)]
 	pub fn attach_label(&mut self, label: Label)
 	{
@@ -185,14 +762,17 @@ impl<'a> InstructionStream<'a>
 	pub fn emit_label(&mut self, label: Label)
 	{
 		let target_instruction_pointer = self.target_instruction_pointer(label);
+		
+		// Also recorded if resolved, as `finish_relaxed()` may move code.
+		let instruction_pointer = self.instruction_pointer();
+		self.emitted_labels.push((label, instruction_pointer));
+		
 		if target_instruction_pointer.is_valid()
 		{
 			self.emit_quad_word(target_instruction_pointer as u64)
 		}
 		else
 		{
-			let instruction_pointer = self.instruction_pointer();
-			self.emitted_labels.push((label, instruction_pointer));
 			self.skip_quad_word();
 		}
 	}
@@ -206,20 +786,199 @@ impl<'a> InstructionStream<'a>
 		
 		self.push_Register64Bit_r64(RBP);
 		self.mov_Register64Bit_Register64Bit_rm64_r64(RBP, RSP);
+		self.frame_pointer_stack_delta = self.stack_delta;
 	}
 	
 	/// Emits a non-leaf function epilogue (which returns) suitable for both the System V Application Binary Interface for AMD64 and the Microsoft x64 Calling Convention.
+	///
+	/// Uses `LEAVE`, which is one byte instead of the four of `MOV RSP, RBP; POP RBP`.
 	#[inline(always)]
 	pub fn pop_stack_frame_and_return(&mut self)
+	{
+		self.emit_leave();
+		self.ret();
+	}
+	
+	/// Emits `LEAVE`, which tears down a stack frame set up by `push_stack_frame()` or `emit_enter()` (`MOV RSP, RBP; POP RBP`).
+	#[inline(always)]
+	pub fn emit_leave(&mut self)
+	{
+		self.leave();
+		self.stack_delta = self.frame_pointer_stack_delta;
+		self.stack_delta_adjusted(-(size_of::<u64>() as isize));
+	}
+	
+	/// Emits `ENTER`, which sets up a stack frame (as for `push_stack_frame()`), copies `level` frame pointers for nested procedures and then reserves `frame` bytes.
+	///
+	/// Only the lowest 5 bits of `level` are used.
+	/// `ENTER` is slower than `push_stack_frame()` followed by a `SUB RSP`, so is best only used when code size matters or `level` is not zero.
+	#[inline(always)]
+	pub fn emit_enter(&mut self, frame: u16, level: u8)
+	{
+		const Slot: isize = size_of::<u64>() as isize;
+		
+		let level = level & 0x1F;
+		self.enter_Immediate8Bit_Immediate16Bit(Immediate8Bit(level as i8), Immediate16Bit(frame as i16));
+		self.stack_delta_adjusted(Slot);
+		self.frame_pointer_stack_delta = self.stack_delta;
+		self.stack_delta_adjusted((level as isize) * Slot + (frame as isize));
+	}
+	
+	/// Emits a `PUSH` for each of `registers`, in order.
+	///
+	/// Use `pop_all()` with the same `registers` to restore them.
+	#[inline(always)]
+	pub fn push_all(&mut self, registers: &[Register64Bit])
+	{
+		for &register in registers.iter()
+		{
+			self.push_Register64Bit_r64(register);
+		}
+	}
+	
+	/// Emits a `POP` for each of `registers`, in reverse order.
+	#[inline(always)]
+	pub fn pop_all(&mut self, registers: &[Register64Bit])
+	{
+		for &register in registers.iter().rev()
+		{
+			self.pop_Register64Bit_r64(register);
+		}
+	}
+	
+	/// The number of bytes the stack has grown by since the entry point of the function being emitted (which is assumed to be the start of the instruction stream, or after the last function returned using an epilogue).
+	///
+	/// Tracks `PUSH`, `POP`, `PUSHF` and `POPF` mnemonics, `ADD RSP, imm` and `SUB RSP, imm`, and the prologue and epilogue helpers; any other change to `RSP` (eg `MOV RSP, RBP` outside of an epilogue helper) is not tracked.
+	/// Tracking is linear, ie it follows the order instructions are emitted in, not control flow.
+	#[inline(always)]
+	pub fn current_stack_delta(&self) -> isize
+	{
+		self.stack_delta
+	}
+	
+	/// `RSP` modulo 16, as tracked by `current_stack_delta()`.
+	///
+	/// On entry to a function, `RSP` modulo 16 is 8, as `CALL` pushes an 8-byte return address to a 16-byte aligned stack.
+	#[inline(always)]
+	pub fn stack_alignment_offset(&self) -> usize
+	{
+		const StackAlignment: isize = 16;
+		const ReturnAddressSize: isize = 8;
+		
+		(ReturnAddressSize - self.stack_delta).rem_euclid(StackAlignment) as usize
+	}
+	
+	/// Panics in debug builds if the stack, as tracked by `current_stack_delta()`, is not 16-byte aligned, as required by both the System V Application Binary Interface for AMD64 and the Microsoft x64 Calling Convention before a `CALL`.
+	#[inline(always)]
+	pub fn debug_assert_stack_aligned_for_call(&self)
+	{
+		debug_assert_eq!(self.stack_alignment_offset(), 0, "stack is not 16-byte aligned for a call (stack delta is {})", self.stack_delta);
+	}
+	
+	#[inline(always)]
+	fn stack_delta_adjusted(&mut self, pushed: isize)
+	{
+		self.stack_delta += pushed;
+	}
+	
+	#[inline(always)]
+	fn stack_delta_adjusted_if_RSP(&mut self, register: Register64Bit, subtracted: isize)
+	{
+		if register == Register64Bit::RSP
+		{
+			self.stack_delta_adjusted(subtracted)
+		}
+	}
+	
+	/// Emits a function prologue which sets up a stack frame (`PUSH RBP; MOV RBP, RSP`) and then reserves `frame_size` bytes for locals, rounded up so that `RSP` is 16-byte aligned for calls.
+	///
+	/// Locals are addressed relative to `RBP`, ie from `[RBP - frame_size]` to `[RBP - 1]`.
+	///
+	/// Use `emit_epilogue()` to return.
+	#[inline(always)]
+	pub fn emit_prologue(&mut self, frame_size: usize)
+	{
+		self.emit_prologue_with_callee_saves(frame_size, &[])
+	}
+	
+	/// Emits a function epilogue which tears down the stack frame set up by `emit_prologue()` and returns.
+	#[inline(always)]
+	pub fn emit_epilogue(&mut self)
+	{
+		self.pop_stack_frame_and_return()
+	}
+	
+	/// As for `emit_prologue()`, but also pushes the callee-saved registers `callee_saves` (eg `RBX` and `R12` to `R15` for the System V Application Binary Interface for AMD64) after setting up the stack frame.
+	///
+	/// Locals are addressed relative to `RBP`, ie from `[RBP - 8 × callee_saves.len() - frame_size]` to `[RBP - 8 × callee_saves.len() - 1]`.
+	///
+	/// Use `emit_epilogue_with_callee_saves()`, with the same `callee_saves`, to return.
+	#[inline(always)]
+	pub fn emit_prologue_with_callee_saves(&mut self, frame_size: usize, callee_saves: &[Register64Bit])
+	{
+		use self::Register64Bit::RSP;
+		
+		const StackAlignment: usize = 16;
+		
+		self.push_stack_frame();
+		
+		self.push_all(callee_saves);
+		
+		let pushed = callee_saves.len() * size_of::<u64>();
+		let adjustment = ((pushed + frame_size + StackAlignment - 1) & !(StackAlignment - 1)) - pushed;
+		debug_assert!(adjustment <= i32::MAX as usize, "frame_size '{}' is too large", frame_size);
+		
+		if adjustment != 0
+		{
+			self.sub_Register64Bit_Immediate32Bit_auto(RSP, Immediate32Bit(adjustment as i32));
+		}
+	}
+	
+	/// Emits a function epilogue which pops `callee_saves` in reverse order, tears down the stack frame set up by `emit_prologue_with_callee_saves()` and returns.
+	#[inline(always)]
+	pub fn emit_epilogue_with_callee_saves(&mut self, callee_saves: &[Register64Bit])
 	{
 		use self::Register64Bit::RBP;
 		use self::Register64Bit::RSP;
 		
-		self.mov_Register64Bit_Register64Bit_rm64_r64(RSP, RBP);
+		if callee_saves.is_empty()
+		{
+			return self.emit_epilogue()
+		}
+		
+		let pushed = callee_saves.len() * size_of::<u64>();
+		self.lea_Register64Bit_Any64BitMemory(RSP, Any64BitMemory::base_64_displacement(RBP, Immediate32Bit(-(pushed as i32))));
+		self.stack_delta = self.frame_pointer_stack_delta + pushed as isize;
+		
+		self.pop_all(callee_saves);
+		
 		self.pop_Register64Bit_r64(RBP);
 		self.ret();
 	}
 	
+	/// Emits a near return (`RET`), for functions whose caller cleans up the stack; the same as `ret()`.
+	#[inline(always)]
+	pub fn emit_ret(&mut self)
+	{
+		self.ret()
+	}
+	
+	/// Emits a near return which also pops `bytes` bytes of arguments from the stack (`RET imm16`), for callee-cleanup calling conventions such as `stdcall`.
+	///
+	/// If `bytes` is zero, the shorter `RET` is emitted instead.
+	#[inline(always)]
+	pub fn emit_ret_pop(&mut self, bytes: u16)
+	{
+		if bytes == 0
+		{
+			self.ret()
+		}
+		else
+		{
+			self.ret_Immediate16Bit(bytes.into())
+		}
+	}
+	
 	/// Zeroes the `RAX` register using the most efficient code (`XOR RAX, RAX`, although could just as easily be `SUB RAX, RAX`).
 	///
 	/// Also equivalent to a C _Bool's false value.
@@ -231,6 +990,55 @@ impl<'a> InstructionStream<'a>
 		self.xor_Register32Bit_Register32Bit(EAX, EAX);
 	}
 	
+	/// Reverses the byte order of a 16-bit register.
+	///
+	/// `BSWAP` on a 16-bit register is architecturally undefined, so this emits `ROL r16, 8` instead.
+	#[inline(always)]
+	pub fn bswap_Register16Bit(&mut self, register: Register16Bit)
+	{
+		self.rol_Register16Bit_Immediate8Bit(register, Immediate8Bit(8))
+	}
+	
+	/// Divides (signed) `RDX:RAX` by `divisor`, after sign extending `RAX` into `RDX` using `CQO`.
+	///
+	/// The quotient is in `RAX` and the remainder in `RDX`.
+	///
+	/// `divisor` can not be `RDX`, as it is overwritten by the sign extension.
+	#[inline(always)]
+	pub fn emit_signed_divide(&mut self, divisor: Register64Bit)
+	{
+		debug_assert_ne!(divisor, Register64Bit::RDX, "divisor can not be RDX");
+		
+		self.cqo();
+		self.idiv_Register64Bit(divisor);
+	}
+	
+	/// Divides (unsigned) `RDX:RAX` by `divisor`, after zeroing `RDX`.
+	///
+	/// The quotient is in `RAX` and the remainder in `RDX`.
+	///
+	/// `divisor` can not be `RDX`, as it is zeroed.
+	#[inline(always)]
+	pub fn emit_unsigned_divide(&mut self, divisor: Register64Bit)
+	{
+		use self::Register32Bit::EDX;
+		
+		debug_assert_ne!(divisor, Register64Bit::RDX, "divisor can not be RDX");
+		
+		self.xor_Register32Bit_Register32Bit(EDX, EDX);
+		self.div_Register64Bit(divisor);
+	}
+	
+	/// Atomically compares `RDX:RAX` with `memory` and, if equal, stores `RCX:RBX` into it (and sets the Zero Flag (ZF)); otherwise loads `memory` into `RDX:RAX` (and clears the Zero Flag (ZF)).
+	///
+	/// `memory` must be aligned to 16 bytes or the instruction faults; this is checked in debug builds if `memory` is an absolute address.
+	#[inline(always)]
+	pub fn lock_cmpxchg16b(&mut self, memory: Any128BitMemory)
+	{
+		self.lock();
+		self.cmpxchg16b_Any128BitMemory(memory)
+	}
+	
 	/// Sets the `RAX` register to be equivalent to a C _Bool's false value using the most efficient code (`MOV AL, 0x00`).
 	///
 	/// Note that this is not necessarily `0x00000000_00000001`, just that the bottom eight bits of `RAX` are `0x00`.
@@ -253,9 +1061,324 @@ impl<'a> InstructionStream<'a>
 		self.mov_Register8Bit_Immediate8Bit(AL, Immediate8Bit::One);
 	}
 	
+	/// Moves `immediate` to `register` using the shortest encoding.
+	///
+	/// This is `MOV r32, imm32` (5 or 6 bytes, which zero extends to 64 bits) if `immediate` fits in an unsigned 32-bit integer, otherwise `MOV r/m64, imm32` (7 bytes, which sign extends to 64 bits) if it fits in a signed 32-bit integer, otherwise `MOV r64, imm64` (10 bytes).
+	#[inline(always)]
+	pub fn mov_Register64Bit_Immediate64Bit_auto(&mut self, register: Register64Bit, immediate: Immediate64Bit)
+	{
+		let value = immediate.0;
+		
+		if (value as u64) <= (u32::MAX as u64)
+		{
+			self.mov_Register32Bit_Immediate32Bit(register.into(), Immediate32Bit(value as u32 as i32))
+		}
+		else if immediate.fits_in_i32()
+		{
+			self.mov_Register64Bit_Immediate32Bit(register, Immediate32Bit(value as i32))
+		}
+		else
+		{
+			self.mov_Register64Bit_Immediate64Bit(register, immediate)
+		}
+	}
+	
+	/// Adds `immediate` to `register` using the shortest encoding: `ADD r/m64, imm8` (sign extended) if `immediate` fits in a signed 8-bit integer, otherwise `ADD RAX, imm32` or `ADD r/m64, imm32`.
+	#[inline(always)]
+	pub fn add_Register64Bit_Immediate32Bit_auto(&mut self, register: Register64Bit, immediate: Immediate32Bit)
+	{
+		self.arithmetic_Immediate32Bit_auto(register, immediate, Register64Bit::RAX, Self::add_Register64Bit_Immediate8Bit, Self::add_RAX_Immediate32Bit, Self::add_Register64Bit_Immediate32Bit)
+	}
+	
+	/// Subtracts `immediate` from `register` using the shortest encoding: `SUB r/m64, imm8` (sign extended) if `immediate` fits in a signed 8-bit integer, otherwise `SUB RAX, imm32` or `SUB r/m64, imm32`.
+	#[inline(always)]
+	pub fn sub_Register64Bit_Immediate32Bit_auto(&mut self, register: Register64Bit, immediate: Immediate32Bit)
+	{
+		self.arithmetic_Immediate32Bit_auto(register, immediate, Register64Bit::RAX, Self::sub_Register64Bit_Immediate8Bit, Self::sub_RAX_Immediate32Bit, Self::sub_Register64Bit_Immediate32Bit)
+	}
+	
+	/// Binary ANDs `immediate` with `register` using the shortest encoding: `AND r/m64, imm8` (sign extended) if `immediate` fits in a signed 8-bit integer, otherwise `AND RAX, imm32` or `AND r/m64, imm32`.
+	#[inline(always)]
+	pub fn and_Register64Bit_Immediate32Bit_auto(&mut self, register: Register64Bit, immediate: Immediate32Bit)
+	{
+		self.arithmetic_Immediate32Bit_auto(register, immediate, Register64Bit::RAX, Self::and_Register64Bit_Immediate8Bit, Self::and_RAX_Immediate32Bit, Self::and_Register64Bit_Immediate32Bit)
+	}
+	
+	/// Binary ORs `immediate` with `register` using the shortest encoding: `OR r/m64, imm8` (sign extended) if `immediate` fits in a signed 8-bit integer, otherwise `OR RAX, imm32` or `OR r/m64, imm32`.
+	#[inline(always)]
+	pub fn or_Register64Bit_Immediate32Bit_auto(&mut self, register: Register64Bit, immediate: Immediate32Bit)
+	{
+		self.arithmetic_Immediate32Bit_auto(register, immediate, Register64Bit::RAX, Self::or_Register64Bit_Immediate8Bit, Self::or_RAX_Immediate32Bit, Self::or_Register64Bit_Immediate32Bit)
+	}
+	
+	/// Binary XORs `immediate` with `register` using the shortest encoding: `XOR r/m64, imm8` (sign extended) if `immediate` fits in a signed 8-bit integer, otherwise `XOR RAX, imm32` or `XOR r/m64, imm32`.
+	#[inline(always)]
+	pub fn xor_Register64Bit_Immediate32Bit_auto(&mut self, register: Register64Bit, immediate: Immediate32Bit)
+	{
+		self.arithmetic_Immediate32Bit_auto(register, immediate, Register64Bit::RAX, Self::xor_Register64Bit_Immediate8Bit, Self::xor_RAX_Immediate32Bit, Self::xor_Register64Bit_Immediate32Bit)
+	}
+	
+	/// Compares `immediate` with `register` using the shortest encoding: `CMP r/m64, imm8` (sign extended) if `immediate` fits in a signed 8-bit integer, otherwise `CMP RAX, imm32` or `CMP r/m64, imm32`.
+	#[inline(always)]
+	pub fn cmp_Register64Bit_Immediate32Bit_auto(&mut self, register: Register64Bit, immediate: Immediate32Bit)
+	{
+		self.arithmetic_Immediate32Bit_auto(register, immediate, Register64Bit::RAX, Self::cmp_Register64Bit_Immediate8Bit, Self::cmp_RAX_Immediate32Bit, Self::cmp_Register64Bit_Immediate32Bit)
+	}
+	
+	/// Adds `immediate` to `register` using the shortest encoding: `ADD r/m32, imm8` (sign extended) if `immediate` fits in a signed 8-bit integer, otherwise `ADD EAX, imm32` or `ADD r/m32, imm32`.
+	#[inline(always)]
+	pub fn add_Register32Bit_Immediate32Bit_auto(&mut self, register: Register32Bit, immediate: Immediate32Bit)
+	{
+		self.arithmetic_Immediate32Bit_auto(register, immediate, Register32Bit::EAX, Self::add_Register32Bit_Immediate8Bit, Self::add_EAX_Immediate32Bit, Self::add_Register32Bit_Immediate32Bit)
+	}
+	
+	/// Subtracts `immediate` from `register` using the shortest encoding: `SUB r/m32, imm8` (sign extended) if `immediate` fits in a signed 8-bit integer, otherwise `SUB EAX, imm32` or `SUB r/m32, imm32`.
+	#[inline(always)]
+	pub fn sub_Register32Bit_Immediate32Bit_auto(&mut self, register: Register32Bit, immediate: Immediate32Bit)
+	{
+		self.arithmetic_Immediate32Bit_auto(register, immediate, Register32Bit::EAX, Self::sub_Register32Bit_Immediate8Bit, Self::sub_EAX_Immediate32Bit, Self::sub_Register32Bit_Immediate32Bit)
+	}
+	
+	/// Binary ANDs `immediate` with `register` using the shortest encoding: `AND r/m32, imm8` (sign extended) if `immediate` fits in a signed 8-bit integer, otherwise `AND EAX, imm32` or `AND r/m32, imm32`.
+	#[inline(always)]
+	pub fn and_Register32Bit_Immediate32Bit_auto(&mut self, register: Register32Bit, immediate: Immediate32Bit)
+	{
+		self.arithmetic_Immediate32Bit_auto(register, immediate, Register32Bit::EAX, Self::and_Register32Bit_Immediate8Bit, Self::and_EAX_Immediate32Bit, Self::and_Register32Bit_Immediate32Bit)
+	}
+	
+	/// Binary ORs `immediate` with `register` using the shortest encoding: `OR r/m32, imm8` (sign extended) if `immediate` fits in a signed 8-bit integer, otherwise `OR EAX, imm32` or `OR r/m32, imm32`.
+	#[inline(always)]
+	pub fn or_Register32Bit_Immediate32Bit_auto(&mut self, register: Register32Bit, immediate: Immediate32Bit)
+	{
+		self.arithmetic_Immediate32Bit_auto(register, immediate, Register32Bit::EAX, Self::or_Register32Bit_Immediate8Bit, Self::or_EAX_Immediate32Bit, Self::or_Register32Bit_Immediate32Bit)
+	}
+	
+	/// Binary XORs `immediate` with `register` using the shortest encoding: `XOR r/m32, imm8` (sign extended) if `immediate` fits in a signed 8-bit integer, otherwise `XOR EAX, imm32` or `XOR r/m32, imm32`.
+	#[inline(always)]
+	pub fn xor_Register32Bit_Immediate32Bit_auto(&mut self, register: Register32Bit, immediate: Immediate32Bit)
+	{
+		self.arithmetic_Immediate32Bit_auto(register, immediate, Register32Bit::EAX, Self::xor_Register32Bit_Immediate8Bit, Self::xor_EAX_Immediate32Bit, Self::xor_Register32Bit_Immediate32Bit)
+	}
+	
+	/// Compares `immediate` with `register` using the shortest encoding: `CMP r/m32, imm8` (sign extended) if `immediate` fits in a signed 8-bit integer, otherwise `CMP EAX, imm32` or `CMP r/m32, imm32`.
+	#[inline(always)]
+	pub fn cmp_Register32Bit_Immediate32Bit_auto(&mut self, register: Register32Bit, immediate: Immediate32Bit)
+	{
+		self.arithmetic_Immediate32Bit_auto(register, immediate, Register32Bit::EAX, Self::cmp_Register32Bit_Immediate8Bit, Self::cmp_EAX_Immediate32Bit, Self::cmp_Register32Bit_Immediate32Bit)
+	}
+	
+	/// Multiplies (signed) `source` by `immediate` into `destination` using the shortest encoding: `IMUL r64, r/m64, imm8` (sign extended) if `immediate` fits in a signed 8-bit integer, otherwise `IMUL r64, r/m64, imm32`.
+	#[inline(always)]
+	pub fn imul_Register64Bit_Register64Bit_Immediate32Bit_auto(&mut self, destination: Register64Bit, source: Register64Bit, immediate: Immediate32Bit)
+	{
+		self.multiply_Immediate32Bit_auto(destination, source, immediate, Self::imul_Register64Bit_Register64Bit_Immediate8Bit, Self::imul_Register64Bit_Register64Bit_Immediate32Bit)
+	}
+	
+	/// Multiplies (signed) `source` by `immediate` into `destination` using the shortest encoding: `IMUL r32, r/m32, imm8` (sign extended) if `immediate` fits in a signed 8-bit integer, otherwise `IMUL r32, r/m32, imm32`.
+	#[inline(always)]
+	pub fn imul_Register32Bit_Register32Bit_Immediate32Bit_auto(&mut self, destination: Register32Bit, source: Register32Bit, immediate: Immediate32Bit)
+	{
+		self.multiply_Immediate32Bit_auto(destination, source, immediate, Self::imul_Register32Bit_Register32Bit_Immediate8Bit, Self::imul_Register32Bit_Register32Bit_Immediate32Bit)
+	}
+	
+	/// Shifts `register` left by `count` using the shortest encoding: `SHL r/m64, 1` if `count` is 1, otherwise `SHL r/m64, imm8`.
+	#[inline(always)]
+	pub fn shl_Register64Bit_Immediate8Bit_auto(&mut self, register: Register64Bit, count: Immediate8Bit)
+	{
+		self.shift_Immediate8Bit_auto(register, count, Self::shl_Register64Bit_One, Self::shl_Register64Bit_Immediate8Bit)
+	}
+	
+	/// Shifts `register` right (unsigned) by `count` using the shortest encoding: `SHR r/m64, 1` if `count` is 1, otherwise `SHR r/m64, imm8`.
+	#[inline(always)]
+	pub fn shr_Register64Bit_Immediate8Bit_auto(&mut self, register: Register64Bit, count: Immediate8Bit)
+	{
+		self.shift_Immediate8Bit_auto(register, count, Self::shr_Register64Bit_One, Self::shr_Register64Bit_Immediate8Bit)
+	}
+	
+	/// Shifts `register` right (signed) by `count` using the shortest encoding: `SAR r/m64, 1` if `count` is 1, otherwise `SAR r/m64, imm8`.
+	#[inline(always)]
+	pub fn sar_Register64Bit_Immediate8Bit_auto(&mut self, register: Register64Bit, count: Immediate8Bit)
+	{
+		self.shift_Immediate8Bit_auto(register, count, Self::sar_Register64Bit_One, Self::sar_Register64Bit_Immediate8Bit)
+	}
+	
+	/// Rotates `register` left by `count` using the shortest encoding: `ROL r/m64, 1` if `count` is 1, otherwise `ROL r/m64, imm8`.
+	#[inline(always)]
+	pub fn rol_Register64Bit_Immediate8Bit_auto(&mut self, register: Register64Bit, count: Immediate8Bit)
+	{
+		self.shift_Immediate8Bit_auto(register, count, Self::rol_Register64Bit_One, Self::rol_Register64Bit_Immediate8Bit)
+	}
+	
+	/// Rotates `register` right by `count` using the shortest encoding: `ROR r/m64, 1` if `count` is 1, otherwise `ROR r/m64, imm8`.
+	#[inline(always)]
+	pub fn ror_Register64Bit_Immediate8Bit_auto(&mut self, register: Register64Bit, count: Immediate8Bit)
+	{
+		self.shift_Immediate8Bit_auto(register, count, Self::ror_Register64Bit_One, Self::ror_Register64Bit_Immediate8Bit)
+	}
+	
+	/// Rotates `register` and the carry flag left by `count` using the shortest encoding: `RCL r/m64, 1` if `count` is 1, otherwise `RCL r/m64, imm8`.
+	#[inline(always)]
+	pub fn rcl_Register64Bit_Immediate8Bit_auto(&mut self, register: Register64Bit, count: Immediate8Bit)
+	{
+		self.shift_Immediate8Bit_auto(register, count, Self::rcl_Register64Bit_One, Self::rcl_Register64Bit_Immediate8Bit)
+	}
+	
+	/// Rotates `register` and the carry flag right by `count` using the shortest encoding: `RCR r/m64, 1` if `count` is 1, otherwise `RCR r/m64, imm8`.
+	#[inline(always)]
+	pub fn rcr_Register64Bit_Immediate8Bit_auto(&mut self, register: Register64Bit, count: Immediate8Bit)
+	{
+		self.shift_Immediate8Bit_auto(register, count, Self::rcr_Register64Bit_One, Self::rcr_Register64Bit_Immediate8Bit)
+	}
+	
+	/// Shifts `register` left by `count` using the shortest encoding: `SHL r/m32, 1` if `count` is 1, otherwise `SHL r/m32, imm8`.
+	#[inline(always)]
+	pub fn shl_Register32Bit_Immediate8Bit_auto(&mut self, register: Register32Bit, count: Immediate8Bit)
+	{
+		self.shift_Immediate8Bit_auto(register, count, Self::shl_Register32Bit_One, Self::shl_Register32Bit_Immediate8Bit)
+	}
+	
+	/// Shifts `register` right (unsigned) by `count` using the shortest encoding: `SHR r/m32, 1` if `count` is 1, otherwise `SHR r/m32, imm8`.
+	#[inline(always)]
+	pub fn shr_Register32Bit_Immediate8Bit_auto(&mut self, register: Register32Bit, count: Immediate8Bit)
+	{
+		self.shift_Immediate8Bit_auto(register, count, Self::shr_Register32Bit_One, Self::shr_Register32Bit_Immediate8Bit)
+	}
+	
+	/// Shifts `register` right (signed) by `count` using the shortest encoding: `SAR r/m32, 1` if `count` is 1, otherwise `SAR r/m32, imm8`.
+	#[inline(always)]
+	pub fn sar_Register32Bit_Immediate8Bit_auto(&mut self, register: Register32Bit, count: Immediate8Bit)
+	{
+		self.shift_Immediate8Bit_auto(register, count, Self::sar_Register32Bit_One, Self::sar_Register32Bit_Immediate8Bit)
+	}
+	
+	/// Rotates `register` left by `count` using the shortest encoding: `ROL r/m32, 1` if `count` is 1, otherwise `ROL r/m32, imm8`.
+	#[inline(always)]
+	pub fn rol_Register32Bit_Immediate8Bit_auto(&mut self, register: Register32Bit, count: Immediate8Bit)
+	{
+		self.shift_Immediate8Bit_auto(register, count, Self::rol_Register32Bit_One, Self::rol_Register32Bit_Immediate8Bit)
+	}
+	
+	/// Rotates `register` right by `count` using the shortest encoding: `ROR r/m32, 1` if `count` is 1, otherwise `ROR r/m32, imm8`.
+	#[inline(always)]
+	pub fn ror_Register32Bit_Immediate8Bit_auto(&mut self, register: Register32Bit, count: Immediate8Bit)
+	{
+		self.shift_Immediate8Bit_auto(register, count, Self::ror_Register32Bit_One, Self::ror_Register32Bit_Immediate8Bit)
+	}
+	
+	/// Rotates `register` and the carry flag left by `count` using the shortest encoding: `RCL r/m32, 1` if `count` is 1, otherwise `RCL r/m32, imm8`.
+	#[inline(always)]
+	pub fn rcl_Register32Bit_Immediate8Bit_auto(&mut self, register: Register32Bit, count: Immediate8Bit)
+	{
+		self.shift_Immediate8Bit_auto(register, count, Self::rcl_Register32Bit_One, Self::rcl_Register32Bit_Immediate8Bit)
+	}
+	
+	/// Rotates `register` and the carry flag right by `count` using the shortest encoding: `RCR r/m32, 1` if `count` is 1, otherwise `RCR r/m32, imm8`.
+	#[inline(always)]
+	pub fn rcr_Register32Bit_Immediate8Bit_auto(&mut self, register: Register32Bit, count: Immediate8Bit)
+	{
+		self.shift_Immediate8Bit_auto(register, count, Self::rcr_Register32Bit_One, Self::rcr_Register32Bit_Immediate8Bit)
+	}
+	
+	/// Exchanges `a` with `b` using the shortest encoding: `XCHG RAX, r64` (2 bytes) if either is `RAX`, otherwise `XCHG r/m64, r64` (3 bytes).
+	#[inline(always)]
+	pub fn xchg_Register64Bit_Register64Bit_auto(&mut self, a: Register64Bit, b: Register64Bit)
+	{
+		use self::Register64Bit::RAX;
+		
+		if a == RAX
+		{
+			self.xchg_RAX_Register64Bit(b)
+		}
+		else if b == RAX
+		{
+			self.xchg_RAX_Register64Bit(a)
+		}
+		else
+		{
+			self.xchg_Register64Bit_Register64Bit(a, b)
+		}
+	}
+	
+	/// Exchanges `a` with `b` using the shortest encoding: `XCHG EAX, r32` (1 or 2 bytes) if either is `EAX` (but not both, as the short form of `XCHG EAX, EAX` is `NOP`), otherwise `XCHG r/m32, r32` (2 or 3 bytes).
+	#[inline(always)]
+	pub fn xchg_Register32Bit_Register32Bit_auto(&mut self, a: Register32Bit, b: Register32Bit)
+	{
+		use self::Register32Bit::EAX;
+		
+		if a == EAX
+		{
+			self.xchg_EAX_Register32Bit(b)
+		}
+		else if b == EAX
+		{
+			self.xchg_EAX_Register32Bit(a)
+		}
+		else
+		{
+			self.xchg_Register32Bit_Register32Bit(a, b)
+		}
+	}
+	
+	/// Computes `destination = base + index × scale + displacement` in one instruction using `LEA`, without affecting flags.
+	///
+	/// `index` can not be `RSP`.
+	#[inline(always)]
+	pub fn lea_compute(&mut self, destination: Register64Bit, base: Register64Bit, index: Register64Bit, scale: IndexScale, displacement: Immediate32Bit)
+	{
+		debug_assert_ne!(index, Register64Bit::RSP, "RSP can not be used as an index register");
+		
+		self.lea_Register64Bit_Any64BitMemory(destination, Any64BitMemory::base_64_index_64_scale_displacement(base, index, scale, displacement))
+	}
+	
+	#[inline(always)]
+	fn arithmetic_Immediate32Bit_auto<R: Register + PartialEq>(&mut self, register: R, immediate: Immediate32Bit, accumulator: R, sign_extended_8_bit: fn(&mut Self, R, SignExtendedImmediate8Bit), accumulator_32_bit: fn(&mut Self, Immediate32Bit), register_32_bit: fn(&mut Self, R, Immediate32Bit))
+	{
+		let value = immediate.0;
+		
+		if immediate.fits_in_i8()
+		{
+			sign_extended_8_bit(self, register, SignExtendedImmediate8Bit(value as i8))
+		}
+		else if register == accumulator
+		{
+			accumulator_32_bit(self, immediate)
+		}
+		else
+		{
+			register_32_bit(self, register, immediate)
+		}
+	}
+	
+	#[inline(always)]
+	fn multiply_Immediate32Bit_auto<R: Register>(&mut self, destination: R, source: R, immediate: Immediate32Bit, sign_extended_8_bit: fn(&mut Self, R, R, SignExtendedImmediate8Bit), immediate_32_bit: fn(&mut Self, R, R, Immediate32Bit))
+	{
+		if immediate.fits_in_i8()
+		{
+			sign_extended_8_bit(self, destination, source, SignExtendedImmediate8Bit(immediate.0 as i8))
+		}
+		else
+		{
+			immediate_32_bit(self, destination, source, immediate)
+		}
+	}
+	
+	#[inline(always)]
+	fn shift_Immediate8Bit_auto<R: Register>(&mut self, register: R, count: Immediate8Bit, by_one: fn(&mut Self, R), by_immediate: fn(&mut Self, R, Immediate8Bit))
+	{
+		if count == Immediate8Bit::One
+		{
+			by_one(self, register)
+		}
+		else
+		{
+			by_immediate(self, register, count)
+		}
+	}
+	
 	/// Creates a function pointer to the current location that takes no arguments and returns a result of type `R`.
 	///
 	/// Resultant function will not execute (and in all likelihood cause an uncaught signal to occur) until `self.finish()` is called.
+	#[cfg(target_arch = "x86_64")]
 	#[inline(always)]
 	pub fn nullary_function_pointer<R>(&self) -> unsafe extern "C" fn() -> R
 	{
@@ -265,6 +1388,7 @@ impl<'a> InstructionStream<'a>
 	/// Creates a function pointer to the current location that takes one argument of type `A` and returns a result of type `R`.
 	///
 	/// Resultant function will not execute (and in all likelihood cause an uncaught signal to occur) until `self.finish()` is called.
+	#[cfg(target_arch = "x86_64")]
 	#[inline(always)]
 	pub fn unary_function_pointer<R, A>(&self) -> unsafe extern "C" fn(A) -> R
 	{
@@ -274,6 +1398,7 @@ impl<'a> InstructionStream<'a>
 	/// Creates a function pointer to the current location that takes two argument of types `A` and `B` and returns a result of type `R`.
 	///
 	/// Resultant function will not execute (and in all likelihood cause an uncaught signal to occur) until `self.finish()` is called.
+	#[cfg(target_arch = "x86_64")]
 	#[inline(always)]
 	pub fn binary_function_pointer<R, A, B>(&self) -> unsafe extern "C" fn(A, B) -> R
 	{
@@ -283,37 +1408,134 @@ impl<'a> InstructionStream<'a>
 	/// Creates a function pointer to the current location that takes three argument of types `A`, `B` and `C` and returns a result of type `R`.
 	///
 	/// Resultant function will not execute (and in all likelihood cause an uncaught signal to occur) until `self.finish()` is called.
+	#[cfg(target_arch = "x86_64")]
 	#[inline(always)]
 	pub fn ternary_function_pointer<R, A, B, C>(&self) -> unsafe extern "C" fn(A, B, C) -> R
 	{
 		unsafe { transmute(self.instruction_pointer()) }
 	}
 	
-	/// Creates a function pointer to the current location that takes four argument of types `A`, `B`, `C` and `D` and returns a result of type `R`.
+	/// Creates a function pointer to the current location that takes four argument of types `A`, `B`, `C` and `D` and returns a result of type `R`.
+	///
+	/// Resultant function will not execute (and in all likelihood cause an uncaught signal to occur) until `self.finish()` is called.
+	#[cfg(target_arch = "x86_64")]
+	#[inline(always)]
+	pub fn quaternary_function_pointer<R, A, B, C, D>(&self) -> unsafe extern "C" fn(A, B, C, D) -> R
+	{
+		unsafe { transmute(self.instruction_pointer()) }
+	}
+	
+	/// Creates a function pointer to the current location that takes five argument of types `A`, `B`, `C`, `D` and `E` and returns a result of type `R`.
+	///
+	/// Resultant function will not execute (and in all likelihood cause an uncaught signal to occur) until `self.finish()` is called.
+	#[cfg(target_arch = "x86_64")]
+	#[inline(always)]
+	pub fn quinary_function_pointer<R, A, B, C, D, E>(&self) -> unsafe extern "C" fn(A, B, C, D, E) -> R
+	{
+		unsafe { transmute(self.instruction_pointer()) }
+	}
+	
+	/// Creates a function pointer to the current location that takes six argument of types `A`, `B`, `C`, `D`, `E` and `F` and returns a result of type `R`.
+	///
+	/// Resultant function will not execute (and in all likelihood cause an uncaught signal to occur) until `self.finish()` is called.
+	#[cfg(target_arch = "x86_64")]
+	#[inline(always)]
+	pub fn senary_function_pointer<R, A, B, C, D, E, F>(&self) -> unsafe extern "C" fn(A, B, C, D, E, F) -> R
+	{
+		unsafe { transmute(self.instruction_pointer()) }
+	}
+	
+	/// Creates a function pointer to the location labelled by `label` that takes no arguments and returns a result of type `R`.
+	///
+	/// Panics if `label` has not yet been attached.
+	///
+	/// Resultant function will not execute (and in all likelihood cause an uncaught signal to occur) until `self.finish()` is called.
+	#[cfg(target_arch = "x86_64")]
+	#[inline(always)]
+	pub fn nullary_function_pointer_at<R>(&self, label: Label) -> unsafe extern "C" fn() -> R
+	{
+		unsafe { transmute(self.attached_label_instruction_pointer(label)) }
+	}
+	
+	/// Creates a function pointer to the location labelled by `label` that takes one argument of type `A` and returns a result of type `R`.
+	///
+	/// Panics if `label` has not yet been attached.
+	///
+	/// Resultant function will not execute (and in all likelihood cause an uncaught signal to occur) until `self.finish()` is called.
+	#[cfg(target_arch = "x86_64")]
+	#[inline(always)]
+	pub fn unary_function_pointer_at<R, A>(&self, label: Label) -> unsafe extern "C" fn(A) -> R
+	{
+		unsafe { transmute(self.attached_label_instruction_pointer(label)) }
+	}
+	
+	/// Creates a function pointer to the location labelled by `label` that takes two argument of types `A` and `B` and returns a result of type `R`.
+	///
+	/// Panics if `label` has not yet been attached.
+	///
+	/// Resultant function will not execute (and in all likelihood cause an uncaught signal to occur) until `self.finish()` is called.
+	#[cfg(target_arch = "x86_64")]
+	#[inline(always)]
+	pub fn binary_function_pointer_at<R, A, B>(&self, label: Label) -> unsafe extern "C" fn(A, B) -> R
+	{
+		unsafe { transmute(self.attached_label_instruction_pointer(label)) }
+	}
+	
+	/// Creates a function pointer to the location labelled by `label` that takes three argument of types `A`, `B` and `C` and returns a result of type `R`.
+	///
+	/// Panics if `label` has not yet been attached.
+	///
+	/// Resultant function will not execute (and in all likelihood cause an uncaught signal to occur) until `self.finish()` is called.
+	#[cfg(target_arch = "x86_64")]
+	#[inline(always)]
+	pub fn ternary_function_pointer_at<R, A, B, C>(&self, label: Label) -> unsafe extern "C" fn(A, B, C) -> R
+	{
+		unsafe { transmute(self.attached_label_instruction_pointer(label)) }
+	}
+	
+	/// Creates a function pointer to the location labelled by `label` that takes four argument of types `A`, `B`, `C` and `D` and returns a result of type `R`.
+	///
+	/// Panics if `label` has not yet been attached.
+	///
+	/// Resultant function will not execute (and in all likelihood cause an uncaught signal to occur) until `self.finish()` is called.
+	#[cfg(target_arch = "x86_64")]
+	#[inline(always)]
+	pub fn quaternary_function_pointer_at<R, A, B, C, D>(&self, label: Label) -> unsafe extern "C" fn(A, B, C, D) -> R
+	{
+		unsafe { transmute(self.attached_label_instruction_pointer(label)) }
+	}
+	
+	/// Creates a function pointer to the location labelled by `label` that takes five argument of types `A`, `B`, `C`, `D` and `E` and returns a result of type `R`.
+	///
+	/// Panics if `label` has not yet been attached.
 	///
 	/// Resultant function will not execute (and in all likelihood cause an uncaught signal to occur) until `self.finish()` is called.
+	#[cfg(target_arch = "x86_64")]
 	#[inline(always)]
-	pub fn quaternary_function_pointer<R, A, B, C, D>(&self) -> unsafe extern "C" fn(A, B, C, D) -> R
+	pub fn quinary_function_pointer_at<R, A, B, C, D, E>(&self, label: Label) -> unsafe extern "C" fn(A, B, C, D, E) -> R
 	{
-		unsafe { transmute(self.instruction_pointer()) }
+		unsafe { transmute(self.attached_label_instruction_pointer(label)) }
 	}
 	
-	/// Creates a function pointer to the current location that takes five argument of types `A`, `B`, `C`, `D` and `E` and returns a result of type `R`.
+	/// Creates a function pointer to the location labelled by `label` that takes six argument of types `A`, `B`, `C`, `D`, `E` and `F` and returns a result of type `R`.
+	///
+	/// Panics if `label` has not yet been attached.
 	///
 	/// Resultant function will not execute (and in all likelihood cause an uncaught signal to occur) until `self.finish()` is called.
+	#[cfg(target_arch = "x86_64")]
 	#[inline(always)]
-	pub fn quinary_function_pointer<R, A, B, C, D, E>(&self) -> unsafe extern "C" fn(A, B, C, D, E) -> R
+	pub fn senary_function_pointer_at<R, A, B, C, D, E, F>(&self, label: Label) -> unsafe extern "C" fn(A, B, C, D, E, F) -> R
 	{
-		unsafe { transmute(self.instruction_pointer()) }
+		unsafe { transmute(self.attached_label_instruction_pointer(label)) }
 	}
 	
-	/// Creates a function pointer to the current location that takes six argument of types `A`, `B`, `C`, `D`, `E` and `F` and returns a result of type `R`.
-	///
-	/// Resultant function will not execute (and in all likelihood cause an uncaught signal to occur) until `self.finish()` is called.
+	#[cfg(target_arch = "x86_64")]
 	#[inline(always)]
-	pub fn senary_function_pointer<R, A, B, C, D, E, F>(&self) -> unsafe extern "C" fn(A, B, C, D, E, F) -> R
+	fn attached_label_instruction_pointer(&self, label: Label) -> InstructionPointer
 	{
-		unsafe { transmute(self.instruction_pointer()) }
+		let target_instruction_pointer = self.target_instruction_pointer(label);
+		assert!(target_instruction_pointer.is_valid(), "{:?} has not been attached", label);
+		target_instruction_pointer
 	}
 	
 	/// Emits (pushes) a byte into the instruction stream at the current location.
@@ -330,7 +1552,7 @@ impl<'a> InstructionStream<'a>
 	///
 	/// The word can be data or instructions.
 	///
-	/// The word will be swapped into the little endian form (a no-op on x64 platforms).
+	/// The word is always emitted in little endian form, whatever the endianness of the host.
 	#[inline(always)]
 	pub fn emit_word(&mut self, word: u16)
 	{
@@ -342,7 +1564,7 @@ impl<'a> InstructionStream<'a>
 	///
 	/// The word can be data or instructions.
 	///
-	/// The word will be swapped into the little endian form (a no-op on x64 platforms).
+	/// The word is always emitted in little endian form, whatever the endianness of the host.
 	#[inline(always)]
 	pub fn emit_double_word(&mut self, double_word: u32)
 	{
@@ -354,7 +1576,7 @@ impl<'a> InstructionStream<'a>
 	///
 	/// The word can be data or instructions.
 	///
-	/// The word will be swapped into the little endian form (a no-op on x64 platforms).
+	/// The word is always emitted in little endian form, whatever the endianness of the host.
 	#[inline(always)]
 	pub fn emit_quad_word(&mut self, quad_word: u64)
 	{
@@ -366,7 +1588,7 @@ impl<'a> InstructionStream<'a>
 	///
 	/// The word can be data or instructions.
 	///
-	/// The word will be swapped into the little endian form (a no-op on x64 platforms).
+	/// The word is always emitted in little endian form, whatever the endianness of the host.
 	#[inline(always)]
 	pub fn emit_double_quad_word(&mut self, double_quad_word: u128)
 	{
@@ -384,6 +1606,78 @@ impl<'a> InstructionStream<'a>
 		self.byte_emitter.emit_bytes(bytes)
 	}
 	
+	/// As for `emit_bytes()`, but returns an error rather than panicking if there is not enough space left for `bytes` and the instruction stream can not be grown.
+	///
+	/// Nothing is emitted if an error is returned.
+	#[inline(always)]
+	pub fn try_emit_bytes(&mut self, bytes: &[u8]) -> Result<(), CapacityExceededError>
+	{
+		self.try_reserve_space(bytes.len())?;
+		self.byte_emitter.emit_bytes(bytes);
+		Ok(())
+	}
+	
+	/// Creates a label at the current location then emits (pushes) `bytes` as data, returning the label and the length of the data.
+	///
+	/// Use `lea_Register64Bit_Label()` to reference the data `RIP`-relatively.
+	/// Data emitted in the same section as code should be placed where it will not be executed, eg after a `RET` or in another section.
+	#[inline(always)]
+	pub fn emit_data(&mut self, bytes: &[u8]) -> (Label, usize)
+	{
+		let label = self.create_and_attach_label();
+		self.emit_bytes(bytes);
+		(label, bytes.len())
+	}
+	
+	/// Emits each of `instructions` in turn by calling the corresponding mnemonic method.
+	///
+	/// Space for all the instructions (using `Inst::maximum_length()`) is reserved first, so an error is returned (and nothing is emitted) if there is not enough space left and the instruction stream can not be grown.
+	/// As each mnemonic method reserves space for the longest possible instruction before emitting, the last instruction needs 15 bytes rather than its own length.
+	#[inline(always)]
+	pub fn emit_all(&mut self, instructions: &[Inst]) -> Result<(), CapacityExceededError>
+	{
+		use self::Inst::*;
+		
+		const MaximumOpcodeLength: usize = 15;
+		let mut required = 0;
+		let mut length = 0;
+		for instruction in instructions
+		{
+			let maximum_length = instruction.maximum_length();
+			if maximum_length != 0
+			{
+				required = length + MaximumOpcodeLength;
+				length += maximum_length;
+			}
+		}
+		self.try_reserve_space(required)?;
+		
+		for instruction in instructions
+		{
+			match *instruction
+			{
+				MovRegReg(destination, source) => self.mov_Register64Bit_Register64Bit_rm64_r64(destination, source),
+				MovRegImm(destination, immediate) => self.mov_Register64Bit_Immediate64Bit(destination, immediate.into()),
+				AddRegReg(destination, source) => self.add_Register64Bit_Register64Bit(destination, source),
+				AddRegImm(destination, immediate) => self.add_Register64Bit_Immediate32Bit(destination, immediate.into()),
+				SubRegReg(destination, source) => self.sub_Register64Bit_Register64Bit(destination, source),
+				SubRegImm(destination, immediate) => self.sub_Register64Bit_Immediate32Bit(destination, immediate.into()),
+				CmpRegReg(left, right) => self.cmp_Register64Bit_Register64Bit(left, right),
+				CmpRegImm(left, immediate) => self.cmp_Register64Bit_Immediate32Bit(left, immediate.into()),
+				Push(register) => self.push_Register64Bit_r64(register),
+				Pop(register) => self.pop_Register64Bit_r64(register),
+				JmpLabel(label) => self.jmp_Label_auto(label),
+				JccLabel(condition_code, label) => self.jcc_Label_auto(condition_code, label),
+				CallLabel(label) => self.call_Label(label),
+				AttachLabel(label) => self.attach_label(label),
+				Ret => self.ret(),
+				Nop => self.nop(),
+			}
+		}
+		
+		Ok(())
+	}
+	
 	/// Rewinds by the length of a byte (1 byte) and then emits `byte`.
 	#[inline(always)]
 	pub fn rewind_to_emit_byte(&mut self, byte: u8)
@@ -693,116 +1987,558 @@ impl<'a> InstructionStream<'a>
 		}
 	}
 	
-	/// Emits (pushes) `NOP`s (No Operation) opcodes into the instruction stream at the current location to ensure the desired `alignment`.
+	/// Emits (pushes) exactly one `NOP` (No Operation) instruction of `length` bytes into the instruction stream at the current location, eg to reserve a patchable region of a known size.
 	///
-	/// Efficient for alignments up to 32 (needed for AVX-2).
+	/// Lengths of 2 to 9 bytes use the recommended `66 90` and `0F 1F /0` forms; longer ones add a `CS` segment override and then operand size prefixes.
+	///
+	/// Panics unless `length` is 1 to 15 (the maximum length of an instruction) inclusive.
+	#[inline(always)]
+	pub fn emit_nop_of_length(&mut self, length: usize)
+	{
+		assert!((1 ..= 15).contains(&length), "A single NOP can not be {} bytes long", length);
+		
+		match length
+		{
+			1 => self.nop_1(),
+			
+			2 => self.nop_2(),
+			
+			3 => self.nop_3(),
+			
+			4 => self.nop_4(),
+			
+			5 => self.nop_5(),
+			
+			6 => self.nop_6(),
+			
+			7 => self.nop_7(),
+			
+			8 => self.nop_8(),
+			
+			9 => self.nop_9(),
+			
+			_ =>
+			{
+				const OperandSizePrefix: u8 = 0x66;
+				const CodeSegmentPrefix: u8 = 0x2E;
+				
+				self.reserve_space(length);
+				for _ in 10 .. length
+				{
+					self.byte_emitter.emit_u8(OperandSizePrefix)
+				}
+				self.byte_emitter.emit_bytes(&[OperandSizePrefix, CodeSegmentPrefix, 0x0F, 0x1F, 0x84, 0x00, 0x00, 0x00, 0x00, 0x00])
+			}
+		}
+	}
+	
+	/// Emits (pushes) `length` bytes of `NOP`s into the instruction stream at the current location, returning a patch point which can be overwritten later with `fill_patch_point()`, eg to specialize guarded code in place.
+	///
+	/// A `length` of up to 15 bytes is a single `NOP` instruction (see `emit_nop_of_length()`), so the region can never be executed part way through.
+	#[inline(always)]
+	pub fn reserve_patch_point(&mut self, length: usize) -> PatchPoint
+	{
+		let instruction_pointer = self.instruction_pointer();
+		self.position_dependent_locations.push(instruction_pointer);
+		
+		self.emit_patchable_nops(length);
+		
+		PatchPoint
+		{
+			instruction_pointer,
+			length,
+		}
+	}
+	
+	/// Overwrites the region reserved by `reserve_patch_point()` with `bytes`, padding any remainder with `NOP`s.
+	///
+	/// `patch_point` must have been reserved in the section currently being emitted into.
+	///
+	/// Panics if `bytes` is longer than the region.
+	#[inline(always)]
+	pub fn fill_patch_point(&mut self, patch_point: PatchPoint, bytes: &[u8])
+	{
+		assert!(bytes.len() <= patch_point.length, "{} bytes do not fit in a patch point of {} bytes", bytes.len(), patch_point.length);
+		
+		let instruction_pointer = self.instruction_pointer();
+		debug_assert!(patch_point.instruction_pointer >= self.byte_emitter.start_instruction_pointer && patch_point.instruction_pointer + patch_point.length <= instruction_pointer, "{:?} was not reserved in the current section", patch_point);
+		self.byte_emitter.instruction_pointer = patch_point.instruction_pointer;
+		
+		self.byte_emitter.emit_bytes(bytes);
+		let remainder = patch_point.length - bytes.len();
+		self.emit_patchable_nops(remainder);
+		
+		self.byte_emitter.instruction_pointer = instruction_pointer
+	}
+	
+	#[inline(always)]
+	fn emit_patchable_nops(&mut self, length: usize)
+	{
+		if length != 0 && length <= 15
+		{
+			self.emit_nop_of_length(length)
+		}
+		else
+		{
+			self.emit_nops(length)
+		}
+	}
+	
+	/// The number of bytes of padding `emit_alignment()` or `emit_alignment_with_fill()` would emit at the current location for `alignment`.
+	///
+	/// Useful to decide whether an alignment (eg of a loop head) is worth it before emitting it.
 	#[inline(always)]
-	pub fn emit_alignment(&mut self, alignment: usize)
+	pub fn padding_for_alignment(&self, alignment: usize) -> usize
 	{
 		let offset = self.instruction_pointer() % alignment;
 		
 		if offset == 0
 		{
-			return
+			0
+		}
+		else
+		{
+			alignment - offset
+		}
+	}
+	
+	/// Emits (pushes) `NOP`s (No Operation) opcodes into the instruction stream at the current location to ensure the desired `alignment`.
+	///
+	/// Efficient for alignments up to 32 (needed for AVX-2).
+	///
+	/// Returns the number of bytes of padding emitted.
+	#[inline(always)]
+	pub fn emit_alignment(&mut self, alignment: usize) -> usize
+	{
+		self.record_alignment(alignment);
+		let count = self.padding_for_alignment(alignment);
+		
+		self.emit_nops(count);
+		
+		count
+	}
+	
+	/// Emits `fill` bytes into the instruction stream at the current location to ensure the desired `alignment`.
+	///
+	/// Use `0xCC` (`INT3`) to pad between functions, so that a stray jump or fall through into the padding faults immediately; use `emit_alignment()` for padding which may be executed.
+	///
+	/// Returns the number of bytes of padding emitted.
+	#[inline(always)]
+	pub fn emit_alignment_with_fill(&mut self, alignment: usize, fill: u8) -> usize
+	{
+		self.record_alignment(alignment);
+		let count = self.padding_for_alignment(alignment);
+		
+		self.reserve_space(count);
+		for _ in 0 .. count
+		{
+			self.byte_emitter.emit_u8(fill)
+		}
+		
+		count
+	}
+	
+	#[inline(always)]
+	fn record_alignment(&mut self, alignment: usize)
+	{
+		if alignment > 1
+		{
+			let instruction_pointer = self.instruction_pointer();
+			self.position_dependent_locations.push(instruction_pointer)
+		}
+	}
+	
+	#[inline(always)]
+	fn nop_1(&mut self)
+	{
+		const NOP: u8 = 0x90;
+		self.emit_byte(NOP)
+	}
+	
+	#[inline(always)]
+	fn nop_2(&mut self)
+	{
+		self.emit_bytes(&[0x66, 0x90])
+	}
+	
+	#[inline(always)]
+	fn nop_3(&mut self)
+	{
+		self.emit_bytes(&[0x0F, 0x1F, 0x00])
+	}
+	
+	#[inline(always)]
+	fn nop_4(&mut self)
+	{
+		self.emit_bytes(&[0x0F, 0x1F, 0x40, 0x00])
+	}
+	
+	#[inline(always)]
+	fn nop_5(&mut self)
+	{
+		self.emit_bytes(&[0x0F, 0x1F, 0x44, 0x00, 0x00])
+	}
+	
+	#[inline(always)]
+	fn nop_6(&mut self)
+	{
+		self.emit_bytes(&[0x66, 0x0F, 0x1F, 0x44, 0x00, 0x00])
+	}
+	
+	#[inline(always)]
+	fn nop_7(&mut self)
+	{
+		self.emit_bytes(&[0x0F, 0x1F, 0x80, 0x00, 0x00, 0x00, 0x00])
+	}
+	
+	#[inline(always)]
+	fn nop_8(&mut self)
+	{
+		self.emit_bytes(&[0x0F, 0x1F, 0x84, 0x00, 0x00, 0x00, 0x00, 0x00])
+	}
+	
+	#[inline(always)]
+	fn nop_9(&mut self)
+	{
+		self.emit_bytes(&[0x66, 0x0F, 0x1F, 0x84, 0x00, 0x00, 0x00, 0x00, 0x00])
+	}
+	
+	#[inline(always)]
+	fn reserve_space(&mut self, length: usize)
+	{
+		if let Err(error) = self.try_reserve_space(length)
+		{
+			panic!("{}", error)
+		}
+	}
+	
+	/// Growing in place may not add enough space for a large `length` in one go.
+	#[inline(always)]
+	fn try_reserve_space(&mut self, length: usize) -> Result<(), CapacityExceededError>
+	{
+		while unlikely!(self.byte_emitter.remaining_space() < length)
+		{
+			if self.attempt_to_resize_in_place().is_err()
+			{
+				return Err(CapacityExceededError { capacity: self.byte_emitter.capacity(), length })
+			}
+		}
+		Ok(())
+	}
+	
+	#[inline(always)]
+	fn reserve_space_for_instruction(&mut self)
+	{
+		const MaximumOpcodeLength: usize = 15;
+		self.reserve_space(MaximumOpcodeLength);
+		
+		self.start_of_last_instruction = self.byte_emitter.instruction_pointer;
+		self.instruction_hook.call(self.byte_emitter.instruction_pointer)
+	}
+	
+	/// Sets a hook which is called with the instruction pointer at the start of each instruction emitted by a mnemonic method (including those emitted by helpers such as `emit_prologue()`, but not bytes emitted by `emit_bytes()`, `emit_alignment()` and the like).
+	///
+	/// Useful for recording which instructions were emitted for which part of a higher level representation.
+	///
+	/// The hook may be called more than once for the same instruction pointer if an instruction is rolled back, eg by a `ShortJmpResult` error or `jmp_Label_auto()`.
+	/// Instruction pointers are not adjusted if code is later moved by `finish()` (for sections other than the default) or `finish_relaxed()`.
+	#[inline(always)]
+	pub fn set_instruction_hook(&mut self, hook: impl FnMut(InstructionPointer) + 'a)
+	{
+		self.instruction_hook.set(hook)
+	}
+	
+	/// Registers the function starting at `entry` and ending at the current instruction pointer with Linux `perf` by appending a line `START SIZE name` to `/tmp/perf-<pid>.map`.
+	///
+	/// Call after emitting the function's last instruction; `entry` must be attached.
+	/// Since the addresses are those before `finish()`, functions in sections other than the default, or in code shrunk by `finish_relaxed()`, will be registered at the wrong address.
+	#[cfg(feature = "perf")]
+	#[inline(always)]
+	pub fn register_with_perf(&self, name: &str, entry: Label) -> io::Result<()>
+	{
+		let start = self.valid_target_instruction_pointer(entry);
+		let size = self.instruction_pointer() - start;
+		
+		let path = format!("/tmp/perf-{}.map", ::std::process::id());
+		let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+		writeln!(file, "{:x} {:x} {}", start, size, name)
+	}
+	
+	/// Registers `symbol_file`, an in-memory object file (eg ELF) describing the code emitted by this instruction stream, with GDB's JIT interface, so that GDB can show symbols for and step through the code.
+	///
+	/// `symbol_file` is copied; it remains registered until the returned `GdbJitRegistration` is dropped.
+	#[cfg(feature = "gdb")]
+	#[inline(always)]
+	pub fn register_with_gdb(&self, symbol_file: &[u8]) -> GdbJitRegistration
+	{
+		GdbJitRegistration::register(symbol_file)
+	}
+	
+	/// The locations emitted so far whose contents depend on where the instructions are finally placed in memory; currently, those written by `emit_label()`.
+	///
+	/// Labels used by relocations must be attached; relocations are only meaningful if `create_section()` has not been used.
+	#[inline(always)]
+	pub fn relocations(&self) -> Vec<Relocation>
+	{
+		debug_assert!(self.sections.is_empty(), "relocations are not supported with sections");
+		
+		let start_instruction_pointer = self.start_instruction_pointer();
+		self.emitted_labels.iter().map(|&(label, instruction_pointer)| Relocation
+		{
+			offset: instruction_pointer - start_instruction_pointer,
+			kind: RelocationKind::Absolute64Bit,
+			target_offset: self.valid_target_instruction_pointer(label) - start_instruction_pointer,
+		}).collect()
+	}
+	
+	/// Takes a position-independent copy of the bytes emitted so far, the labels and the locations still to be patched with them, which can be restored at a new address with `from_snapshot()`.
+	///
+	/// Not supported if `create_section()` has been used.
+	#[inline(always)]
+	pub fn snapshot(&self) -> AssembledSnapshot
+	{
+		debug_assert!(self.sections.is_empty(), "snapshots are not supported with sections");
+		
+		let start_instruction_pointer = self.start_instruction_pointer();
+		let offset = |&(label, instruction_pointer): &(Label, InstructionPointer)| (label, instruction_pointer - start_instruction_pointer);
+		
+		let mut bytes = self.emitted_bytes().to_vec();
+		for &(_, instruction_pointer) in self.emitted_labels.iter()
+		{
+			let offset = instruction_pointer - start_instruction_pointer;
+			bytes[offset .. offset + 8].copy_from_slice(&[0; 8]);
+		}
+		
+		AssembledSnapshot
+		{
+			bytes,
+			label_offsets: (0 .. self.labelled_locations.next_label_index).map(|index|
+			{
+				let target_instruction_pointer = self.target_instruction_pointer(Label(index));
+				if target_instruction_pointer.is_valid()
+				{
+					Some(target_instruction_pointer - start_instruction_pointer)
+				}
+				else
+				{
+					None
+				}
+			}).collect(),
+			offsets_to_replace_labels_with_8_bit_displacements: self.instruction_pointers_to_replace_labels_with_8_bit_displacements.iter().map(offset).collect(),
+			offsets_to_replace_labels_with_32_bit_displacements: self.instruction_pointers_to_replace_labels_with_32_bit_displacements.iter().map(offset).collect(),
+			emitted_labels: self.emitted_labels.iter().map(offset).collect(),
+			relaxable_jumps: self.relaxable_jumps.iter().filter(|&&(_, instruction_pointer, _)| self.position_dependent_locations.iter().all(|&position_dependent_location| instruction_pointer >= position_dependent_location)).map(|&(label, instruction_pointer, short_opcode)| (label, instruction_pointer - start_instruction_pointer, short_opcode)).collect(),
 		}
-		
-		let count = alignment - offset;
-		
-		self.emit_nops(count);
 	}
 	
+	/// Creates a relocatable x86-64 ELF object file, suitable for linking with the system linker, with a `.text` section containing the instructions emitted so far.
+	///
+	/// Each of `symbols` becomes a global function symbol; labels used by `symbols`, jumps and `emit_label()` must be attached.
+	/// Locations written by `emit_label()` become relocations (see `relocations()`); jumps to labels are resolved.
+	///
+	/// Not supported if `create_section()` has been used.
 	#[inline(always)]
-	fn nop_1(&mut self)
+	pub fn emit_elf_object(&self, symbols: &[(String, Label)]) -> Vec<u8>
 	{
-		const NOP: u8 = 0x90;
-		self.emit_byte(NOP)
+		let (text, symbols, relocations) = self.object_contents(symbols);
+		ElfObjectWriter::new(&text, &symbols, &relocations).write()
 	}
 	
+	/// Creates a relocatable x86-64 Mach-O object file, suitable for linking with the system linker on macOS, with a `__TEXT,__text` section containing the instructions emitted so far.
+	///
+	/// Each of `symbols` becomes an external symbol, prefixed with an underscore as is the convention for C symbols on macOS; otherwise, as for `emit_elf_object()`.
 	#[inline(always)]
-	fn nop_2(&mut self)
+	pub fn emit_macho_object(&self, symbols: &[(String, Label)]) -> Vec<u8>
 	{
-		self.emit_word(0x6690)
+		let (text, symbols, relocations) = self.object_contents(symbols);
+		MachOObjectWriter::new(&text, &symbols, &relocations).write()
+	}
+	
+	/// Returns a copy of the instructions emitted so far with jumps to labels resolved and zeros in place of relocations, the offsets of `symbols` and the relocations.
+	fn object_contents(&self, symbols: &[(String, Label)]) -> (Vec<u8>, Vec<(String, usize)>, Vec<Relocation>)
+	{
+		debug_assert!(self.sections.is_empty(), "object files are not supported with sections");
+		
+		let start_instruction_pointer = self.start_instruction_pointer();
+		let mut text = self.emitted_bytes().to_vec();
+		
+		for &(label, insert_at_instruction_pointer) in self.instruction_pointers_to_replace_labels_with_8_bit_displacements.iter()
+		{
+			let displacement = (self.valid_target_instruction_pointer(label) as isize) - ((insert_at_instruction_pointer + 1) as isize);
+			debug_assert!(displacement >= i8::MIN as isize && displacement <= i8::MAX as isize, "8-bit JMP for {:?} was too far", label);
+			
+			text[insert_at_instruction_pointer - start_instruction_pointer] = displacement as u8;
+		}
+		
+		for &(label, insert_at_instruction_pointer) in self.instruction_pointers_to_replace_labels_with_32_bit_displacements.iter()
+		{
+			let displacement = (self.valid_target_instruction_pointer(label) as isize) - ((insert_at_instruction_pointer + 4) as isize);
+			debug_assert!(displacement >= i32::MIN as isize && displacement <= i32::MAX as isize, "32-bit JMP for {:?} was too far", label);
+			
+			let offset = insert_at_instruction_pointer - start_instruction_pointer;
+			text[offset .. offset + 4].copy_from_slice(&(displacement as i32).to_le_bytes());
+		}
+		
+		let relocations = self.relocations();
+		for relocation in relocations.iter()
+		{
+			text[relocation.offset .. relocation.offset + 8].copy_from_slice(&[0; 8]);
+		}
+		
+		let symbols = symbols.iter().map(|&(ref name, label)| (name.clone(), self.valid_target_instruction_pointer(label) - start_instruction_pointer)).collect();
+		
+		(text, symbols, relocations)
 	}
 	
+	/// Records `tag` against the current instruction pointer, eg to identify the higher level construct (such as a function or source line) the instructions emitted next are for.
+	///
+	/// The annotations are returned by `finish_with_annotations()`, and can be used to generate DWARF line tables or `perf` jit-dump entries.
 	#[inline(always)]
-	fn nop_3(&mut self)
+	pub fn annotate(&mut self, tag: u32)
 	{
-		self.emit_bytes(&[0x0F, 0x1F, 0x00])
+		let instruction_pointer = self.instruction_pointer();
+		self.annotations.push((instruction_pointer, tag))
 	}
 	
+	/// Removes any hook set with `set_instruction_hook()`.
 	#[inline(always)]
-	fn nop_4(&mut self)
+	pub fn clear_instruction_hook(&mut self)
 	{
-		self.emit_double_word(0x0F1F4000)
+		self.instruction_hook.clear()
 	}
 	
 	#[inline(always)]
-	fn nop_5(&mut self)
+	fn bookmark(&mut self)
 	{
-		self.emit_bytes(&[0x0F, 0x1F, 0x44, 0x00, 0x00])
+		self.byte_emitter.store_bookmark()
 	}
 	
 	#[inline(always)]
-	fn nop_6(&mut self)
+	fn reset_to_bookmark(&mut self)
 	{
-		self.emit_bytes(&[0x66, 0x0F, 0x1F, 0x44, 0x00, 0x00])
+		self.byte_emitter.reset_to_bookmark()
 	}
 	
+	/// The number of bytes emitted since the start of the last instruction emitted by a mnemonic method, ie the length of that instruction if nothing else has been emitted since.
+	///
+	/// Is zero if no instruction has been emitted into the current section (or since `switch_section()` or `reset()`), or if the last instruction was a short jump which was too far.
 	#[inline(always)]
-	fn nop_7(&mut self)
+	pub fn last_instruction_length(&self) -> usize
 	{
-		self.emit_bytes(&[0x0F, 0x1F, 0x80, 0x00, 0x00, 0x00, 0x00])
+		self.instruction_pointer().saturating_sub(self.start_of_last_instruction)
 	}
 	
+	/// The current instruction pointer.
 	#[inline(always)]
-	fn nop_8(&mut self)
+	pub fn instruction_pointer(&self) -> InstructionPointer
 	{
-		self.emit_quad_word(0x0F1F840000000000)
+		self.byte_emitter.instruction_pointer
 	}
 	
+	/// The bytes emitted so far into the current section (the instruction stream's memory map, unless `switch_section()` has been used).
+	///
+	/// Labels which are not yet attached are not resolved, so their displacements are not yet correct.
 	#[inline(always)]
-	fn nop_9(&mut self)
+	pub fn emitted_bytes(&self) -> &[u8]
 	{
-		self.emit_bytes(&[0x66, 0x0F, 0x1F, 0x84, 0x00, 0x00, 0x00, 0x00, 0x00])
+		let start_instruction_pointer = self.byte_emitter.start_instruction_pointer;
+		let length = self.instruction_pointer() - start_instruction_pointer;
+		unsafe { from_raw_parts(start_instruction_pointer as *const u8, length) }
 	}
 	
+	/// The bytes emitted so far into the current section as upper case hexadecimal pairs separated by spaces, eg `55 48 8B EC`.
+	///
+	/// Suitable for comparing with the output of `objdump` or for pasting into an online disassembler.
 	#[inline(always)]
-	fn reserve_space(&mut self, length: usize)
+	pub fn hexdump(&self) -> String
 	{
-		let remaining_space = self.byte_emitter.remaining_space();
-		if unlikely!(remaining_space < length)
+		let emitted_bytes = self.emitted_bytes();
+		let mut hexdump = String::with_capacity(emitted_bytes.len() * 3);
+		for (index, byte) in emitted_bytes.iter().enumerate()
 		{
-			if self.attempt_to_resize_in_place().is_err()
+			if index != 0
 			{
-				panic!("There is no more space in the buffer and remap failed")
+				hexdump.push(' ');
 			}
+			hexdump.push_str(&format!("{:02X}", byte));
 		}
+		hexdump
 	}
 	
+	/// Disassembles the bytes emitted so far into the current section as Intel syntax assembly, one instruction per line, each prefixed with its offset; attached labels are shown as `label_N:` lines and substituted for the addresses of branch targets.
+	///
+	/// Labels which are not yet attached are not resolved, so jumps to them disassemble with meaningless targets.
+	#[cfg(feature = "disassembly")]
 	#[inline(always)]
-	fn reserve_space_for_instruction(&mut self)
+	pub fn to_intel_syntax(&self) -> String
 	{
-		const MaximumOpcodeLength: usize = 15;
-		self.reserve_space(MaximumOpcodeLength)
+		let mut formatter = IntelFormatter::with_options(Some(Box::new(self.label_symbol_resolver())), None);
+		self.disassemble(&mut formatter)
 	}
 	
+	/// As for `to_intel_syntax()`, but using AT&T (GNU assembler) syntax.
+	#[cfg(feature = "disassembly")]
 	#[inline(always)]
-	fn bookmark(&mut self)
+	pub fn to_att_syntax(&self) -> String
 	{
-		self.byte_emitter.store_bookmark()
+		let mut formatter = GasFormatter::with_options(Some(Box::new(self.label_symbol_resolver())), None);
+		self.disassemble(&mut formatter)
 	}
 	
-	#[inline(always)]
-	fn reset_to_bookmark(&mut self)
+	#[cfg(feature = "disassembly")]
+	fn label_symbol_resolver(&self) -> LabelSymbolResolver
 	{
-		self.byte_emitter.reset_to_bookmark()
+		LabelSymbolResolver::new(self.attached_label_names())
 	}
 	
-	/// The current instruction pointer.
-	#[inline(always)]
-	pub fn instruction_pointer(&self) -> InstructionPointer
+	#[cfg(feature = "disassembly")]
+	fn attached_label_names(&self) -> BTreeMap<InstructionPointer, String>
 	{
-		self.byte_emitter.instruction_pointer
+		let mut names = BTreeMap::new();
+		for index in 0 .. self.labelled_locations.next_label_index
+		{
+			let target_instruction_pointer = self.target_instruction_pointer(Label(index));
+			if target_instruction_pointer.is_valid()
+			{
+				names.entry(target_instruction_pointer).or_insert_with(|| format!("label_{}", index));
+			}
+		}
+		names
+	}
+	
+	#[cfg(feature = "disassembly")]
+	fn disassemble(&self, formatter: &mut dyn IcedFormatter) -> String
+	{
+		const Bitness: u32 = 64;
+		
+		let start_instruction_pointer = self.byte_emitter.start_instruction_pointer;
+		let names = self.attached_label_names();
+		
+		let mut decoder = Decoder::with_ip(Bitness, self.emitted_bytes(), start_instruction_pointer as u64, DecoderOptions::NONE);
+		let mut instruction = Instruction::default();
+		let mut formatted_instruction = String::new();
+		let mut disassembly = String::new();
+		while decoder.can_decode()
+		{
+			decoder.decode_out(&mut instruction);
+			
+			let instruction_pointer = instruction.ip() as InstructionPointer;
+			for (_, name) in names.range(instruction_pointer .. instruction_pointer + instruction.len())
+			{
+				disassembly.push_str(&format!("{}:\n", name));
+			}
+			
+			formatted_instruction.clear();
+			formatter.format(&instruction, &mut formatted_instruction);
+			disassembly.push_str(&format!("{:08X}  {}\n", instruction_pointer - start_instruction_pointer, formatted_instruction));
+		}
+		
+		if let Some(name) = names.get(&self.instruction_pointer())
+		{
+			disassembly.push_str(&format!("{}:\n", name));
+		}
+		
+		disassembly
 	}
 	
 	/// The location of the start of instructions.
@@ -833,6 +2569,13 @@ impl<'a> InstructionStream<'a>
 		}
 	}
 	
+	/// Used by instructions, such as `FXSAVE` and `XSAVE`, that fault if their memory operand is not aligned.
+	#[inline(always)]
+	fn debug_assert_memory_is_aligned(&self, memory: impl HasMemoryOperand, alignment: usize)
+	{
+		memory.memory_operand().debug_assert_absolute_address_is_aligned(alignment)
+	}
+	
 	#[inline(always)]
 	fn prefix_fwait(&mut self, byte: u8)
 	{
@@ -873,16 +2616,63 @@ impl<'a> InstructionStream<'a>
 		}
 	}
 	
+	/// Computes the REX prefix for an instruction with a `reg` operand and a `rm` operand.
+	///
+	/// Returns `None` if no REX prefix is needed.
+	#[inline(always)]
+	pub(crate) fn compute_rex(w: bool, reg: impl Register, rm: impl MemoryOrRegister) -> Option<u8>
+	{
+		let mut rex = if w
+		{
+			Self::REX_W
+		}
+		else
+		{
+			0x00
+		};
+		
+		if reg.requires_rex_byte()
+		{
+			rex |= Self::REX
+		}
+		
+		if reg.requires_rex_bit()
+		{
+			rex |= Self::REX_R
+		}
+		
+		rex |= rm.rex_bits();
+		
+		if rex == 0x00
+		{
+			None
+		}
+		else
+		{
+			Some(rex)
+		}
+	}
+	
 	#[inline(always)]
 	fn rex_3(&mut self, rm: impl MemoryOrRegister, r: impl Register, byte: u8)
 	{
-		rm.emit_rex_3(&mut self.byte_emitter, r, byte)
+		let uses_register_high_8_bits = rm.is_register_high_8_bits() || r.is_register_high_8_bits();
+		let instruction_pointer = self.instruction_pointer();
+		
+		rm.emit_rex_3(&mut self.byte_emitter, r, byte);
+		
+		debug_assert!(!uses_register_high_8_bits || self.instruction_pointer() == instruction_pointer, "AH, CH, DH and BH can not be encoded in an instruction with a REX prefix");
 	}
 	
 	#[inline(always)]
 	fn rex_2(&mut self, rm: impl MemoryOrRegister, byte: u8)
 	{
-		rm.emit_rex_2(&mut self.byte_emitter, byte)
+		let uses_register_high_8_bits = rm.is_register_high_8_bits();
+		let instruction_pointer = self.instruction_pointer();
+		
+		rm.emit_rex_2(&mut self.byte_emitter, byte);
+		
+		debug_assert!(!uses_register_high_8_bits || self.instruction_pointer() == instruction_pointer, "AH, CH, DH and BH can not be encoded in an instruction with a REX prefix");
 	}
 	
 	#[inline(always)]
@@ -940,21 +2730,17 @@ impl<'a> InstructionStream<'a>
 		
 		if target_instruction_pointer.is_valid()
 		{
-			match self.byte_emitter.insert_8_bit_effective_address_displacement(insert_at_instruction_pointer, target_instruction_pointer)
+			let result = self.byte_emitter.insert_8_bit_effective_address_displacement(label, insert_at_instruction_pointer, target_instruction_pointer);
+			if unlikely!(result.is_err())
 			{
-				Ok(()) => Ok(()),
-				Err(()) =>
-				{
-					self.reset_to_bookmark();
-					Err(())
-				}
+				self.reset_to_bookmark();
+				return result
 			}
 		}
-		else
-		{
-			self.instruction_pointers_to_replace_labels_with_8_bit_displacements.push((label, insert_at_instruction_pointer));
-			Ok(())
-		}
+		
+		// Also recorded if resolved, as `finish_relaxed()` may move code.
+		self.instruction_pointers_to_replace_labels_with_8_bit_displacements.push((label, insert_at_instruction_pointer));
+		Ok(())
 	}
 	
 	/// Does not return an error if displacement would exceed 32 bits, but panics in debug builds.
@@ -972,10 +2758,9 @@ impl<'a> InstructionStream<'a>
 		{
 			self.byte_emitter.insert_32_bit_effective_address_displacement(insert_at_instruction_pointer, target_instruction_pointer).expect("32-bit JMP was too far")
 		}
-		else
-		{
-			self.instruction_pointers_to_replace_labels_with_32_bit_displacements.push((label, insert_at_instruction_pointer));
-		}
+		
+		// Also recorded if resolved, as `finish_relaxed()` may move code.
+		self.instruction_pointers_to_replace_labels_with_32_bit_displacements.push((label, insert_at_instruction_pointer));
 	}
 	
 	/// Calculates a relative address from an absolute address, such as a function pointer or static constant pointer.
@@ -1019,7 +2804,7 @@ impl<'a> InstructionStream<'a>
 		
 		let instruction_pointer = self.instruction_pointer();
 		
-		if instruction_pointer <= ::std::i32::MAX as usize
+		if instruction_pointer <= i32::MAX as usize
 		{
 			self.rewind_to_emit_double_word(instruction_pointer as u32);
 			return
@@ -1033,10 +2818,248 @@ impl<'a> InstructionStream<'a>
 		
 		let image_base = self.start_instruction_pointer();
 		let displacement = self.instruction_pointer() - image_base;
-		debug_assert!(displacement <= ::std::i32::MAX as usize, "Jumps of more than 2Gb are not supported");
+		debug_assert!(displacement <= i32::MAX as usize, "Jumps of more than 2Gb are not supported");
 		self.rewind_to_emit_double_word(displacement as u32)
 	}
 	
+	/// Emits a near (32-bit) `JMP` to the absolute address `target`, eg that of a Rust or C function.
+	///
+	/// Returns an error, and emits nothing, if `target` is more than 2Gb away; use `jmp_via_register()` in this case.
+	///
+	/// The displacement is computed from where the instruction is emitted, so is invalidated if the code is moved by `finish_relaxed()` or is in a section other than the default.
+	#[inline(always)]
+	pub fn jmp_absolute_address(&mut self, target: *const u8) -> Result<(), AbsoluteAddressTooFarError>
+	{
+		let relative_address = self.relative_address_32_bit_to_absolute_address(target)?;
+		self.jmp_RelativeAddress32Bit(relative_address);
+		Ok(())
+	}
+	
+	/// Emits a near (32-bit) `CALL` to the absolute address `target`, eg that of a Rust or C function.
+	///
+	/// Returns an error, and emits nothing, if `target` is more than 2Gb away; use `call_via_register()` in this case.
+	///
+	/// The displacement is computed from where the instruction is emitted, so is invalidated if the code is moved by `finish_relaxed()` or is in a section other than the default.
+	#[inline(always)]
+	pub fn call_absolute_address(&mut self, target: *const u8) -> Result<(), AbsoluteAddressTooFarError>
+	{
+		let relative_address = self.relative_address_32_bit_to_absolute_address(target)?;
+		self.call_RelativeAddress32Bit(relative_address);
+		Ok(())
+	}
+	
+	/// Emits `MOV scratch, target` followed by `JMP scratch`, which can jump to any absolute address.
+	#[inline(always)]
+	pub fn jmp_via_register(&mut self, target: *const u8, scratch: Register64Bit)
+	{
+		self.mov_Register64Bit_Immediate64Bit(scratch, Immediate64Bit(target as i64));
+		self.jmp_Register64Bit(scratch)
+	}
+	
+	/// Emits `MOV scratch, target` followed by `CALL scratch`, which can call any absolute address.
+	#[inline(always)]
+	pub fn call_via_register(&mut self, target: *const u8, scratch: Register64Bit)
+	{
+		self.mov_Register64Bit_Immediate64Bit(scratch, Immediate64Bit(target as i64));
+		self.call_Register64Bit(scratch)
+	}
+	
+	/// Emits a `CALL` to the absolute address `target` wherever it is, eg a function in `libc` or the Rust runtime.
+	///
+	/// Uses `CALL rel32` if `target` is within 2Gb, otherwise `MOV scratch, target` followed by `CALL scratch`; `scratch` should be a register the callee is free to clobber, eg `R11`.
+	#[inline(always)]
+	pub fn emit_far_call(&mut self, target: *const u8, scratch: Register64Bit)
+	{
+		if self.call_absolute_address(target).is_err()
+		{
+			self.call_via_register(target, scratch)
+		}
+	}
+	
+	/// Emits a `JMP` to the absolute address `target` wherever it is.
+	///
+	/// Uses `JMP rel32` if `target` is within 2Gb, otherwise `MOV scratch, target` followed by `JMP scratch`.
+	#[inline(always)]
+	pub fn emit_far_jmp(&mut self, target: *const u8, scratch: Register64Bit)
+	{
+		if self.jmp_absolute_address(target).is_err()
+		{
+			self.jmp_via_register(target, scratch)
+		}
+	}
+	
+	/// `JMP rel32` and `CALL rel32` are both 5 bytes long (an opcode byte and a 32-bit displacement).
+	#[inline(always)]
+	fn relative_address_32_bit_to_absolute_address(&self, target: *const u8) -> Result<RelativeAddress32Bit, AbsoluteAddressTooFarError>
+	{
+		const InstructionLength: usize = 5;
+		
+		let end_of_instruction = (self.instruction_pointer() + InstructionLength) as isize;
+		let displacement = (target as isize) - end_of_instruction;
+		
+		if unlikely!(displacement < (i32::MIN as isize) || displacement > (i32::MAX as isize))
+		{
+			return Err(AbsoluteAddressTooFarError { target: target as InstructionPointer, displacement })
+		}
+		
+		Ok(RelativeAddress32Bit(displacement as i32))
+	}
+	
+	/// Would a short (8-bit) `JMP` or `Jcc` to `label`, emitted at the current location, fit?
+	///
+	/// Returns `None` if `label` is not yet attached, otherwise whether the displacement from the end of the 2-byte instruction is within -128 to +127 bytes.
+	/// Nothing is emitted.
+	#[inline(always)]
+	pub fn short_jump_fits(&self, label: Label) -> Option<bool>
+	{
+		const ShortJumpLength: isize = 2;
+		
+		let target_instruction_pointer = self.target_instruction_pointer(label);
+		if target_instruction_pointer.is_valid()
+		{
+			let displacement = (target_instruction_pointer as isize) - (self.instruction_pointer() as isize + ShortJumpLength);
+			Some(displacement >= (i8::MIN as isize) && displacement <= (i8::MAX as isize))
+		}
+		else
+		{
+			None
+		}
+	}
+	
+	/// Emits a `JMP` to `label`, using the short (8-bit) form if `label` is already attached and close enough, otherwise the near (32-bit) form.
+	///
+	/// Labels which are not yet attached (ie forward jumps) always use the near (32-bit) form; use `finish_relaxed()` to shrink these afterwards.
+	#[inline(always)]
+	pub fn jmp_Label_auto(&mut self, label: Label)
+	{
+		if self.short_jump_fits(label) == Some(true) && self.jmp_Label(label).is_ok()
+		{
+			return
+		}
+		
+		let instruction_pointer = self.instruction_pointer();
+		self.relaxable_jumps.push((label, instruction_pointer, 0xEB));
+		self.jmp_Label_1(label)
+	}
+	
+	/// Jump short to `label` if `condition_code` is met.
+	///
+	/// Equivalent to `jo_Label()`, `jno_Label()`, etc, but with the condition chosen at runtime.
+	#[inline(always)]
+	pub fn jcc_Label(&mut self, condition_code: ConditionCode, label: Label) -> ShortJmpResult
+	{
+		let cc: u8 = condition_code.into();
+		
+		self.reserve_space_for_instruction();
+		self.bookmark();
+		self.opcode_1(0x70 | cc);
+		self.displacement_label_8bit(label)
+	}
+	
+	/// Jump near to `label` if `condition_code` is met.
+	///
+	/// Equivalent to `jo_Label_1()`, `jno_Label_1()`, etc, but with the condition chosen at runtime.
+	#[inline(always)]
+	pub fn jcc_Label_1(&mut self, condition_code: ConditionCode, label: Label)
+	{
+		let cc: u8 = condition_code.into();
+		
+		self.reserve_space_for_instruction();
+		self.opcode_2(0x0F, 0x80 | cc);
+		self.displacement_label_32bit(label)
+	}
+	
+	/// Emits a `Jcc` to `label`, using the short (8-bit) form if `label` is already attached and close enough, otherwise the near (32-bit) form.
+	///
+	/// Labels which are not yet attached (ie forward jumps) always use the near (32-bit) form; use `finish_relaxed()` to shrink these afterwards.
+	#[inline(always)]
+	pub fn jcc_Label_auto(&mut self, condition_code: ConditionCode, label: Label)
+	{
+		if self.short_jump_fits(label) == Some(true) && self.jcc_Label(condition_code, label).is_ok()
+		{
+			return
+		}
+		
+		let cc: u8 = condition_code.into();
+		
+		let instruction_pointer = self.instruction_pointer();
+		self.relaxable_jumps.push((label, instruction_pointer, 0x70 | cc));
+		self.jcc_Label_1(condition_code, label)
+	}
+	
+	/// Compares `a` with `b` (`CMP a, b`) then jumps short to `label` if `condition_code` is met, eg `Less` if `a < b` (signed).
+	///
+	/// If the jump is too far, the `CMP` is still emitted but the `Jcc` is not; follow it with `jcc_Label_1()`.
+	#[inline(always)]
+	pub fn cmp_and_branch(&mut self, a: Register64Bit, b: Register64Bit, condition_code: ConditionCode, label: Label) -> ShortJmpResult
+	{
+		self.cmp_Register64Bit_Register64Bit(a, b);
+		self.jcc_Label(condition_code, label)
+	}
+	
+	/// Binary ANDs `r1` with `r2` to set flags (`TEST r1, r2`) then jumps short to `label` if `condition_code` is met, eg `Equal` if `r1` is zero when `r1` and `r2` are the same.
+	///
+	/// If the jump is too far, the `TEST` is still emitted but the `Jcc` is not; follow it with `jcc_Label_1()`.
+	#[inline(always)]
+	pub fn test_and_branch(&mut self, r1: Register64Bit, r2: Register64Bit, condition_code: ConditionCode, label: Label) -> ShortJmpResult
+	{
+		self.test_Register64Bit_Register64Bit(r1, r2);
+		self.jcc_Label(condition_code, label)
+	}
+	
+	/// Loads the address of the location labelled by `label` into `register` using a `RIP`-relative `LEA`.
+	///
+	/// `label` need not yet be attached; the displacement is resolved by `finish()` (and adjusted by `finish_relaxed()`), as for a 32-bit `JMP`.
+	///
+	/// Typically used with `emit_data()` to reference inline tables and strings.
+	#[inline(always)]
+	pub fn lea_Register64Bit_Label(&mut self, register: Register64Bit, label: Label)
+	{
+		self.lea_Register64Bit_Any64BitMemory(register, Any64BitMemory::relative_instruction_pointer_relative());
+		
+		// The displacement is the last 4 bytes of the instruction.
+		self.byte_emitter.instruction_pointer -= 4;
+		self.displacement_label_32bit(label)
+	}
+	
+	/// Loads the 64-bit value at the location labelled by `label` into `register` using a `RIP`-relative `MOV`.
+	///
+	/// `label` need not yet be attached; the displacement is resolved as for `lea_Register64Bit_Label()`.
+	#[inline(always)]
+	pub fn mov_Register64Bit_Label(&mut self, register: Register64Bit, label: Label)
+	{
+		self.mov_Register64Bit_Any64BitMemory(register, Any64BitMemory::relative_instruction_pointer_relative());
+		
+		// The displacement is the last 4 bytes of the instruction.
+		self.byte_emitter.instruction_pointer -= 4;
+		self.displacement_label_32bit(label)
+	}
+	
+	/// Emits a tail call through the 64-bit function pointer in the slot labelled by `slot` (`JMP QWORD PTR [RIP + slot]`), as a Procedure Linkage Table (PLT) stub does through a Global Offset Table (GOT).
+	///
+	/// `slot` need not yet be attached; use `emit_got_slot()` or `emit_label()` to emit the slot itself.
+	#[inline(always)]
+	pub fn tail_call_via_got_slot(&mut self, slot: Label)
+	{
+		self.jmp_Any64BitMemory(Any64BitMemory::relative_instruction_pointer_relative());
+		
+		// The displacement is the last 4 bytes of the instruction.
+		self.byte_emitter.instruction_pointer -= 4;
+		self.displacement_label_32bit(slot)
+	}
+	
+	/// Emits an 8-byte aligned slot holding `address`, eg of a function, returning a label to it for use with `tail_call_via_got_slot()`.
+	///
+	/// The slot can be overwritten later (eg for lazy binding); for code that has been finished, use `ExecutableAnonymousMemoryMap.writable_alias()`.
+	#[inline(always)]
+	pub fn emit_got_slot(&mut self, address: usize) -> Label
+	{
+		self.emit_alignment(size_of::<u64>());
+		let slot = self.create_and_attach_label();
+		self.emit_quad_word(address as u64);
+		slot
+	}
+	
 	/// Typically used for when trying to reference static (global) arrays in memory using an index with instructions such as `MOV` or `VPTEST`.
 	///
 	/// Can be used with `jmp_Any64BitMemory()`, but only if the start of the jump table is known in advance.
@@ -1064,7 +3087,7 @@ impl<'a> InstructionStream<'a>
 	
 	/// Use as follows with `Memory::relative_instruction_pointer_relative()`:-
 	///
-	/// ```
+	/// ```ignore
 	/// instruction_stream.vmovdqa_YMM_Any256BitMemory(ymm_register, Any256BitMemory::relative_instruction_pointer_relative());
 	/// instruction_stream.overwrite_last_displacement_with_relative_address_to(absolute_address);
 	/// ```
@@ -1073,13 +3096,13 @@ impl<'a> InstructionStream<'a>
 	#[inline(always)]
 	pub fn overwrite_last_32bit_displacement_with_relative_address_to(&mut self, location_in_memory: InstructionPointer)
 	{
-		debug_assert!(location_in_memory <= ::std::isize::MAX as usize, "location_in_memory is larger than ::std::isize::MAX");
+		debug_assert!(location_in_memory <= isize::MAX as usize, "location_in_memory is larger than isize::MAX");
 		
 		let instruction_pointer = self.instruction_pointer();
-		debug_assert!(instruction_pointer <= ::std::isize::MAX as usize, "instruction_pointer is larger than ::std::isize::MAX");
+		debug_assert!(instruction_pointer <= isize::MAX as usize, "instruction_pointer is larger than isize::MAX");
 		
 		let offset = (location_in_memory as isize) - (instruction_pointer as isize);
-		debug_assert!(offset <= (::std::i32::MAX as isize) && offset >= (::std::i32::MIN as isize), "offset to location_in_memory is bigger than a 32-bit displacement can hold");
+		debug_assert!(offset <= (i32::MAX as isize) && offset >= (i32::MIN as isize), "offset to location_in_memory is bigger than a 32-bit displacement can hold");
 		
 		self.rewind_to_emit_double_word(offset as i32 as u32);
 	}
@@ -19,6 +19,11 @@ pub struct InstructionStreamHints
 	
 	/// Number of emitted labels.
 	pub number_of_emitted_labels: usize,
+	
+	/// Number of bytes of code.
+	///
+	/// An instruction stream created from an `ExecutableAnonymousMemoryMap` grows the map up-front (if possible) so that this many bytes fit, rather than growing it piecemeal as code is emitted.
+	pub number_of_bytes: usize,
 }
 
 impl Default for InstructionStreamHints
@@ -32,6 +37,7 @@ impl Default for InstructionStreamHints
 			number_of_8_bit_jumps: Self::MinimumValue,
 			number_of_32_bit_jumps: Self::MinimumValue,
 			number_of_emitted_labels: Self::MinimumValue,
+			number_of_bytes: Self::MinimumValue,
 		}
 	}
 }
@@ -48,6 +54,7 @@ impl InstructionStreamHints
 		self.number_of_8_bit_jumps = Self::adjust_value(self.number_of_8_bit_jumps);
 		self.number_of_32_bit_jumps = Self::adjust_value(self.number_of_32_bit_jumps);
 		self.number_of_emitted_labels = Self::adjust_value(self.number_of_emitted_labels);
+		self.number_of_bytes = Self::adjust_value(self.number_of_bytes);
 	}
 	
 	/// Ensures that a maximum high-water-mark is kept.
@@ -75,6 +82,11 @@ impl InstructionStreamHints
 		{
 			self.number_of_emitted_labels = newer_unadjusted_hints.number_of_emitted_labels
 		}
+		
+		if newer_unadjusted_hints.number_of_bytes > self.number_of_bytes
+		{
+			self.number_of_bytes = newer_unadjusted_hints.number_of_bytes
+		}
 	}
 	
 	fn adjust_value(value: usize) -> usize
@@ -0,0 +1,59 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// A function pointer to finished, executable code.
+///
+/// Created using `InstructionStream.finish_with_entry()`.
+///
+/// Borrows the `ExecutableAnonymousMemoryMap` the code was emitted into, so the map can not be dropped (or re-used for another instruction stream) whilst this function pointer is alive.
+///
+/// Dereferences to the function pointer `F`, eg `unsafe { (*jit_function)() }`.
+#[derive(Debug)]
+pub struct JitFunction<'a, F: Copy>
+{
+	function_pointer: F,
+	executable_anonymous_memory_map: &'a ExecutableAnonymousMemoryMap,
+}
+
+impl<'a, F: Copy> Deref for JitFunction<'a, F>
+{
+	type Target = F;
+	
+	#[inline(always)]
+	fn deref(&self) -> &Self::Target
+	{
+		&self.function_pointer
+	}
+}
+
+impl<'a, F: Copy> JitFunction<'a, F>
+{
+	#[inline(always)]
+	pub(crate) unsafe fn new(executable_anonymous_memory_map: &'a ExecutableAnonymousMemoryMap, entry_instruction_pointer: InstructionPointer) -> Self
+	{
+		const { assert!(size_of::<F>() == size_of::<InstructionPointer>(), "F is not a function pointer") };
+		
+		Self
+		{
+			function_pointer: unsafe { transmute_copy(&entry_instruction_pointer) },
+			executable_anonymous_memory_map,
+		}
+	}
+	
+	/// The function pointer.
+	///
+	/// Only valid for as long as this `JitFunction` is; do not let the copy escape.
+	#[inline(always)]
+	pub fn function_pointer(&self) -> F
+	{
+		self.function_pointer
+	}
+	
+	/// The executable anonymous memory map the function pointer points into.
+	#[inline(always)]
+	pub fn executable_anonymous_memory_map(&self) -> &'a ExecutableAnonymousMemoryMap
+	{
+		self.executable_anonymous_memory_map
+	}
+}
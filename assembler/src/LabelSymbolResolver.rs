@@ -0,0 +1,34 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// Names branch targets and `RIP`-relative addresses which are attached labels, eg `label_3`, when disassembling.
+#[cfg(feature = "disassembly")]
+#[derive(Debug, Clone)]
+pub(crate) struct LabelSymbolResolver
+{
+	names: BTreeMap<InstructionPointer, String>,
+}
+
+#[cfg(feature = "disassembly")]
+impl SymbolResolver for LabelSymbolResolver
+{
+	#[inline(always)]
+	fn symbol(&mut self, _instruction: &Instruction, _operand: u32, _instruction_operand: Option<u32>, address: u64, _address_size: u32) -> Option<SymbolResult<'_>>
+	{
+		self.names.get(&(address as InstructionPointer)).map(|name| SymbolResult::with_str(address, name))
+	}
+}
+
+#[cfg(feature = "disassembly")]
+impl LabelSymbolResolver
+{
+	#[inline(always)]
+	pub(crate) fn new(names: BTreeMap<InstructionPointer, String>) -> Self
+	{
+		Self
+		{
+			names,
+		}
+	}
+}
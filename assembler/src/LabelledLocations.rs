@@ -10,6 +10,9 @@ pub(crate) struct LabelledLocations
 	layout: Layout,
 	
 	next_label_index: usize,
+	
+	/// In the order they were attached; used to detach labels when rolling back to a checkpoint.
+	attached_labels: Vec<Label>,
 }
 
 impl Drop for LabelledLocations
@@ -24,7 +27,7 @@ impl Drop for LabelledLocations
 impl LabelledLocations
 {
 	/// Using a value of 0xFFFFFFFF_FFFFFFFF is preferable to 0; this is because it is possible to map anonymous memory at location 0x00 and get a label to it; the limitations of the x64 address model to 48-bit addresses make this larger value impossible.
-	const UnlabelledSentinel: usize = ::std::usize::MAX;
+	const UnlabelledSentinel: usize = usize::MAX;
 	
 	#[inline(always)]
 	pub(crate) fn new(likely_number_of_labels_hint: usize) -> Self
@@ -40,6 +43,7 @@ impl LabelledLocations
 			layout,
 			
 			next_label_index: 0,
+			attached_labels: Vec::with_capacity(length),
 		};
 		
 		this.initialize_newly_allocated_memory(0, length);
@@ -60,13 +64,34 @@ impl LabelledLocations
 		Label(label_index)
 	}
 	
+	#[inline(always)]
+	pub(crate) fn number_of_labels(&self) -> usize
+	{
+		self.next_label_index
+	}
+	
+	#[inline(always)]
+	pub(crate) fn owns(&self, label: Label) -> bool
+	{
+		label.0 < self.next_label_index
+	}
+	
 	#[inline(always)]
 	pub(crate) fn set(&mut self, label: Label, instruction_pointer: InstructionPointer)
 	{
+		debug_assert!(self.owns(label), "Label '{:?}' was not created by this instruction stream", label);
+		
 		let instruction_pointer_pointer = self.pointer_at_index(label.0);
 		
 		debug_assert_eq!(unsafe { *instruction_pointer_pointer }, Self::UnlabelledSentinel, "Label '{:?}' has already been used to label", label);
 		unsafe { *instruction_pointer_pointer = instruction_pointer };
+		self.attached_labels.push(label)
+	}
+	
+	#[inline(always)]
+	pub(crate) fn number_of_attached_labels(&self) -> usize
+	{
+		self.attached_labels.len()
 	}
 	
 	#[inline(always)]
@@ -75,6 +100,49 @@ impl LabelledLocations
 		unsafe { *self.pointer_at_index(label.0) }
 	}
 	
+	/// Forgets all labels, keeping the memory allocated for them.
+	#[inline(always)]
+	pub(crate) fn reset(&mut self)
+	{
+		let number_of_labels = self.next_label_index;
+		self.initialize_newly_allocated_memory(0, number_of_labels);
+		self.next_label_index = 0;
+		self.attached_labels.clear();
+	}
+	
+	/// Detaches labels attached after the first `number_of_attached_labels` then forgets labels created after the first `number_of_labels`.
+	#[inline(always)]
+	pub(crate) fn roll_back(&mut self, number_of_labels: usize, number_of_attached_labels: usize)
+	{
+		debug_assert!(number_of_labels <= self.next_label_index && number_of_attached_labels <= self.attached_labels.len(), "checkpoint is no longer valid");
+		
+		for index in number_of_attached_labels .. self.attached_labels.len()
+		{
+			let label = self.attached_labels[index];
+			unsafe { *self.pointer_at_index(label.0) = Self::UnlabelledSentinel };
+		}
+		self.attached_labels.truncate(number_of_attached_labels);
+		
+		let number_of_forgotten_labels = self.next_label_index - number_of_labels;
+		self.initialize_newly_allocated_memory(number_of_labels, number_of_forgotten_labels);
+		self.next_label_index = number_of_labels;
+	}
+	
+	/// Moves every attached label.
+	#[inline(always)]
+	pub(crate) fn relocate(&mut self, relocate: impl Fn(InstructionPointer) -> InstructionPointer)
+	{
+		for index in 0 .. self.next_label_index
+		{
+			let instruction_pointer_pointer = self.pointer_at_index(index);
+			let instruction_pointer = unsafe { *instruction_pointer_pointer };
+			if instruction_pointer != Self::UnlabelledSentinel
+			{
+				unsafe { *instruction_pointer_pointer = relocate(instruction_pointer) };
+			}
+		}
+	}
+	
 	#[inline(always)]
 	fn resize(&mut self)
 	{
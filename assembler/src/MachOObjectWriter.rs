@@ -0,0 +1,183 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// Writes a relocatable x86-64 Mach-O object file containing a single `__TEXT,__text` section.
+///
+/// See `<mach-o/loader.h>`, `<mach-o/nlist.h>` and `<mach-o/x86_64/reloc.h>`.
+#[derive(Debug)]
+pub(crate) struct MachOObjectWriter<'a>
+{
+	text: &'a [u8],
+	symbols: &'a [(String, usize)],
+	relocations: &'a [Relocation],
+}
+
+impl<'a> MachOObjectWriter<'a>
+{
+	const MH_MAGIC_64: u32 = 0xFEED_FACF;
+	
+	const CPU_TYPE_X86_64: u32 = 0x0100_0007;
+	
+	const CPU_SUBTYPE_X86_64_ALL: u32 = 3;
+	
+	const MH_OBJECT: u32 = 1;
+	
+	const MH_SUBSECTIONS_VIA_SYMBOLS: u32 = 0x2000;
+	
+	const LC_SEGMENT_64: u32 = 0x19;
+	
+	const LC_SYMTAB: u32 = 0x02;
+	
+	const LC_DYSYMTAB: u32 = 0x0B;
+	
+	const LC_BUILD_VERSION: u32 = 0x32;
+	
+	const PLATFORM_MACOS: u32 = 1;
+	
+	/// macOS 10.12.
+	const MinimumOperatingSystemVersion: u32 = 0x000A_0C00;
+	
+	const VM_PROT_ALL: u32 = 0x07;
+	
+	const S_ATTR_PURE_INSTRUCTIONS: u32 = 0x8000_0000;
+	
+	const S_ATTR_SOME_INSTRUCTIONS: u32 = 0x0000_0400;
+	
+	const N_SECT: u8 = 0x0E;
+	
+	const N_EXT: u8 = 0x01;
+	
+	const X86_64_RELOC_UNSIGNED: u32 = 0;
+	
+	const HeaderSize: usize = 32;
+	
+	const SegmentCommandSize: usize = 72;
+	
+	const SectionSize: usize = 80;
+	
+	const SymbolTableCommandSize: usize = 24;
+	
+	const DynamicSymbolTableCommandSize: usize = 80;
+	
+	const BuildVersionCommandSize: usize = 24;
+	
+	const NumberOfCommands: u32 = 4;
+	
+	const TextAlignment: usize = 16;
+	
+	const TextSectionOrdinal: u8 = 1;
+	
+	const RelocationSize: usize = 8;
+	
+	const SymbolSize: usize = 16;
+	
+	#[inline(always)]
+	pub(crate) fn new(text: &'a [u8], symbols: &'a [(String, usize)], relocations: &'a [Relocation]) -> Self
+	{
+		Self
+		{
+			text,
+			symbols,
+			relocations,
+		}
+	}
+	
+	/// Relocations are made against the `__text` section (ie are not external), so the target is stored in the location being relocated and they need no symbol table entries of their own.
+	///
+	/// Symbol names are prefixed with an underscore, as is the convention for C symbols on macOS, and sorted, as external symbols must be.
+	pub(crate) fn write(&self) -> Vec<u8>
+	{
+		let mut symbols: Vec<(String, usize)> = self.symbols.iter().map(|&(ref name, offset)| (format!("_{}", name), offset)).collect();
+		symbols.sort();
+		
+		let mut string_table = vec![0];
+		let mut symbol_table = Vec::with_capacity(symbols.len() * Self::SymbolSize);
+		for &(ref name, offset) in symbols.iter()
+		{
+			symbol_table.extend_from_slice(&(string_table.len() as u32).to_le_bytes());
+			symbol_table.push(Self::N_SECT | Self::N_EXT);
+			symbol_table.push(Self::TextSectionOrdinal);
+			symbol_table.extend_from_slice(&0u16.to_le_bytes());
+			symbol_table.extend_from_slice(&(offset as u64).to_le_bytes());
+			
+			string_table.extend_from_slice(name.as_bytes());
+			string_table.push(0);
+		}
+		string_table.resize(string_table.len().next_multiple_of(8), 0);
+		
+		let mut text = self.text.to_vec();
+		let mut relocation_table = Vec::with_capacity(self.relocations.len() * Self::RelocationSize);
+		for relocation in self.relocations.iter()
+		{
+			let (relocation_type, log2_length) = match relocation.kind
+			{
+				RelocationKind::Absolute64Bit =>
+				{
+					text[relocation.offset .. relocation.offset + 8].copy_from_slice(&(relocation.target_offset as u64).to_le_bytes());
+					(Self::X86_64_RELOC_UNSIGNED, 3)
+				}
+			};
+			
+			// `r_symbolnum` (24 bits), `r_pcrel` (1 bit), `r_length` (2 bits), `r_extern` (1 bit) and `r_type` (4 bits).
+			relocation_table.extend_from_slice(&(relocation.offset as u32).to_le_bytes());
+			relocation_table.extend_from_slice(&((Self::TextSectionOrdinal as u32) | (log2_length << 25) | (relocation_type << 28)).to_le_bytes());
+		}
+		
+		let size_of_commands = Self::SegmentCommandSize + Self::SectionSize + Self::SymbolTableCommandSize + Self::DynamicSymbolTableCommandSize + Self::BuildVersionCommandSize;
+		let text_offset = (Self::HeaderSize + size_of_commands).next_multiple_of(Self::TextAlignment);
+		let relocation_table_offset = (text_offset + text.len()).next_multiple_of(8);
+		let symbol_table_offset = relocation_table_offset + relocation_table.len();
+		let string_table_offset = symbol_table_offset + symbol_table.len();
+		let number_of_symbols = symbols.len() as u32;
+		let mut object = Vec::with_capacity(string_table_offset + string_table.len());
+		
+		Self::write_u32s(&mut object, &[Self::MH_MAGIC_64, Self::CPU_TYPE_X86_64, Self::CPU_SUBTYPE_X86_64_ALL, Self::MH_OBJECT, Self::NumberOfCommands, size_of_commands as u32, Self::MH_SUBSECTIONS_VIA_SYMBOLS, 0]);
+		
+		Self::write_u32s(&mut object, &[Self::LC_SEGMENT_64, (Self::SegmentCommandSize + Self::SectionSize) as u32]);
+		object.extend_from_slice(&[0; 16]);
+		object.extend_from_slice(&0u64.to_le_bytes());
+		object.extend_from_slice(&(text.len() as u64).to_le_bytes());
+		object.extend_from_slice(&(text_offset as u64).to_le_bytes());
+		object.extend_from_slice(&(text.len() as u64).to_le_bytes());
+		Self::write_u32s(&mut object, &[Self::VM_PROT_ALL, Self::VM_PROT_ALL, 1, 0]);
+		
+		Self::write_name(&mut object, b"__text");
+		Self::write_name(&mut object, b"__TEXT");
+		object.extend_from_slice(&0u64.to_le_bytes());
+		object.extend_from_slice(&(text.len() as u64).to_le_bytes());
+		Self::write_u32s(&mut object, &[text_offset as u32, Self::TextAlignment.trailing_zeros(), relocation_table_offset as u32, self.relocations.len() as u32, Self::S_ATTR_PURE_INSTRUCTIONS | Self::S_ATTR_SOME_INSTRUCTIONS, 0, 0, 0]);
+		
+		Self::write_u32s(&mut object, &[Self::LC_SYMTAB, Self::SymbolTableCommandSize as u32, symbol_table_offset as u32, number_of_symbols, string_table_offset as u32, string_table.len() as u32]);
+		
+		Self::write_u32s(&mut object, &[Self::LC_DYSYMTAB, Self::DynamicSymbolTableCommandSize as u32, 0, 0, 0, number_of_symbols, number_of_symbols, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+		
+		Self::write_u32s(&mut object, &[Self::LC_BUILD_VERSION, Self::BuildVersionCommandSize as u32, Self::PLATFORM_MACOS, Self::MinimumOperatingSystemVersion, 0, 0]);
+		
+		object.resize(text_offset, 0);
+		object.extend_from_slice(&text);
+		object.resize(relocation_table_offset, 0);
+		object.extend_from_slice(&relocation_table);
+		object.extend_from_slice(&symbol_table);
+		object.extend_from_slice(&string_table);
+		
+		object
+	}
+	
+	#[inline(always)]
+	fn write_u32s(object: &mut Vec<u8>, values: &[u32])
+	{
+		for value in values.iter()
+		{
+			object.extend_from_slice(&value.to_le_bytes());
+		}
+	}
+	
+	#[inline(always)]
+	fn write_name(object: &mut Vec<u8>, name: &[u8])
+	{
+		let mut padded = [0; 16];
+		padded[.. name.len()].copy_from_slice(name);
+		object.extend_from_slice(&padded);
+	}
+}
@@ -0,0 +1,30 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// A region of `NOP`s reserved with `InstructionStream.reserve_patch_point()`, to be overwritten later with `InstructionStream.fill_patch_point()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PatchPoint
+{
+	instruction_pointer: InstructionPointer,
+	length: usize,
+}
+
+impl PatchPoint
+{
+	/// The location of the start of the region.
+	///
+	/// As for function pointers, this is invalid if the code is moved by `finish()` (for sections other than the default) or `finish_relaxed()`.
+	#[inline(always)]
+	pub fn instruction_pointer(self) -> InstructionPointer
+	{
+		self.instruction_pointer
+	}
+	
+	/// The length of the region in bytes.
+	#[inline(always)]
+	pub fn length(self) -> usize
+	{
+		self.length
+	}
+}
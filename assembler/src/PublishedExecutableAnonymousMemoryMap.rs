@@ -0,0 +1,40 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// A memory map published to a `CodeCache`.
+#[derive(Debug)]
+pub(crate) struct PublishedExecutableAnonymousMemoryMap(ExecutableAnonymousMemoryMap);
+
+/// The memory map is never written to after being published, and is only unmapped when dropped.
+unsafe impl Send for PublishedExecutableAnonymousMemoryMap
+{
+}
+
+/// The memory map is never written to after being published, and is only unmapped when dropped.
+unsafe impl Sync for PublishedExecutableAnonymousMemoryMap
+{
+}
+
+impl Deref for PublishedExecutableAnonymousMemoryMap
+{
+	type Target = ExecutableAnonymousMemoryMap;
+	
+	#[inline(always)]
+	fn deref(&self) -> &Self::Target
+	{
+		&self.0
+	}
+}
+
+impl PublishedExecutableAnonymousMemoryMap
+{
+	/// Makes `executable_anonymous_memory_map` read-only and executable; it can not be written again.
+	#[inline(always)]
+	pub(crate) fn new(mut executable_anonymous_memory_map: ExecutableAnonymousMemoryMap) -> Self
+	{
+		executable_anonymous_memory_map.remove_writable_alias();
+		executable_anonymous_memory_map.make_executable();
+		PublishedExecutableAnonymousMemoryMap(executable_anonymous_memory_map)
+	}
+}
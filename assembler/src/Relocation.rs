@@ -0,0 +1,19 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// A location in emitted code that depends on where the code is finally placed in memory.
+///
+/// Created using `InstructionStream.relocations()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Relocation
+{
+	/// Offset of the location to fix up, relative to the start of instructions.
+	pub offset: usize,
+	
+	/// How to fix up the location.
+	pub kind: RelocationKind,
+	
+	/// Offset of the target, relative to the start of instructions.
+	pub target_offset: usize,
+}
@@ -0,0 +1,13 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// The kind of a relocation, ie how the location being relocated is to be fixed up once the final address of the code is known.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RelocationKind
+{
+	/// A 64-bit absolute address, as written by `InstructionStream.emit_label()`.
+	///
+	/// Equivalent to ELF's `R_X86_64_64`.
+	Absolute64Bit,
+}
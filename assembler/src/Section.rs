@@ -0,0 +1,16 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+#[derive(Debug)]
+pub(crate) struct Section
+{
+	/// Saved whilst another section is being emitted into.
+	byte_emitter: ByteEmitter,
+	
+	/// `None` for the default section, which is emitted directly into the instruction stream's memory map.
+	#[cfg(feature = "std")]
+	executable_anonymous_memory_map: Option<ExecutableAnonymousMemoryMap>,
+	
+	alignment: usize,
+}
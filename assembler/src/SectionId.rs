@@ -0,0 +1,26 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// Identifies a section of an instruction stream.
+///
+/// Created using `InstructionStream.create_section()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SectionId(pub(crate) usize);
+
+impl Default for SectionId
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		SectionId::Default
+	}
+}
+
+impl SectionId
+{
+	/// The section an instruction stream starts in.
+	///
+	/// It is always laid out first.
+	pub const Default: Self = SectionId(0);
+}
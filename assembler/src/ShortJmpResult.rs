@@ -6,5 +6,6 @@
 ///
 /// Will be an error if a label could be resolved and the jump exceeded the (inclusive range) -128 to +127 bytes.
 ///
-/// In this case, the instruction stream will be reset to where it was before the prefixes, opcodes and displacements where emitted for the `Jcc` instruction.
-pub type ShortJmpResult = Result<(), ()>;
+/// In this case, the instruction stream will be reset to where it was before the prefixes, opcodes and displacements where emitted for the `Jcc` instruction, and the error will contain the label and the displacement that was too large.
+/// The usual recovery is to emit the 32-bit (near) form of the same instruction instead.
+pub type ShortJmpResult = Result<(), ShortJumpTooFarError>;
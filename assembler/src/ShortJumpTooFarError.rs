@@ -0,0 +1,28 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// The error of a `ShortJmpResult`.
+///
+/// An 8-bit `JMP` or `Jcc` was attempted to a label whose displacement exceeded the (inclusive range) -128 to +127 bytes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ShortJumpTooFarError
+{
+	/// The label that was jumped to.
+	pub label: Label,
+
+	/// The displacement that was computed, relative to the end of the jump instruction.
+	pub displacement: isize,
+}
+
+impl Display for ShortJumpTooFarError
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "8-bit JMP to {:?} was too far with a displacement of {} bytes", self.label, self.displacement)
+	}
+}
+
+impl Error for ShortJumpTooFarError
+{
+}
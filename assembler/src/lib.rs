@@ -8,6 +8,7 @@
 #![deny(missing_docs)]
 #![deny(unreachable_patterns)]
 #![feature(core_intrinsics)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 
 //! #assembler
@@ -19,9 +20,14 @@
 //!
 //! In addition, labels are supported; use of short (8-bit) labelled jumps is supported, and, where possible, are used.
 //!
+//! The `std` feature, on by default, provides `ExecutableAnonymousMemoryMap`, in which code is emitted and then made executable.
+//! Without it, the crate is `no_std` (but still needs `alloc`), and instruction streams emit into a caller-provided buffer created using `InstructionStream::new_in_buffer()`.
+//!
+//! The crate can also be used as a cross-assembler on hosts which are not x86-64; encoding is host-agnostic, but everything which creates or calls function pointers to emitted code (the `*_function_pointer()` methods, `finish_with_entry()`, `JitFunction` and `CodeCache`) is only available when `target_arch = "x86_64"`.
+//!
 //! ## Example Usage
 //!
-//! ```
+//! ```ignore
 //! extern crate assembler;
 //!
 //! use ::assembler::*;
@@ -56,7 +62,11 @@
 //! ```
 
 
-extern crate libc;
+#[macro_use] extern crate alloc;
+#[cfg(feature = "std")] extern crate core;
+#[cfg(not(feature = "std"))] extern crate core as std;
+#[cfg(any(test, feature = "disassembly"))] extern crate iced_x86;
+#[cfg(feature = "std")] extern crate libc;
 #[macro_use] extern crate likely;
 
 
@@ -66,62 +76,111 @@ use self::mnemonic_parameter_types::memory::*;
 use self::mnemonic_parameter_types::memory_offsets::*;
 use self::mnemonic_parameter_types::registers::*;
 use self::mnemonic_parameter_types::relative_addresses::*;
-use ::libc::*;
-use ::std::alloc::alloc;
-use ::std::alloc::realloc;
-use ::std::alloc::dealloc;
-use ::std::alloc::Layout;
-use ::std::error::Error;
-use ::std::fmt;
-use ::std::fmt::Display;
-use ::std::fmt::Formatter;
-use ::std::io;
-use ::std::mem::align_of;
-use ::std::mem::size_of;
-use ::std::mem::transmute;
-use ::std::ops::Add;
-use ::std::ops::AddAssign;
-use ::std::ops::BitAnd;
-use ::std::ops::BitAndAssign;
-use ::std::ops::BitOr;
-use ::std::ops::BitOrAssign;
-use ::std::ops::BitXor;
-use ::std::ops::BitXorAssign;
-use ::std::ops::Div;
-use ::std::ops::DivAssign;
-use ::std::ops::Mul;
-use ::std::ops::MulAssign;
-use ::std::ops::Neg;
-use ::std::ops::Not;
-use ::std::ops::Rem;
-use ::std::ops::RemAssign;
-use ::std::ops::Shl;
-use ::std::ops::ShlAssign;
-use ::std::ops::Shr;
-use ::std::ops::ShrAssign;
-use ::std::ops::Sub;
-use ::std::ops::SubAssign;
-use ::std::ptr::copy_nonoverlapping;
-use ::std::ptr::NonNull;
-use ::std::ptr::null_mut;
-use ::std::slice::from_raw_parts;
+#[cfg(feature = "disassembly")] use ::iced_x86::Decoder;
+#[cfg(feature = "disassembly")] use ::iced_x86::DecoderOptions;
+#[cfg(feature = "disassembly")] use ::iced_x86::Formatter as IcedFormatter;
+#[cfg(feature = "disassembly")] use ::iced_x86::GasFormatter;
+#[cfg(feature = "disassembly")] use ::iced_x86::Instruction;
+#[cfg(feature = "disassembly")] use ::iced_x86::IntelFormatter;
+#[cfg(feature = "disassembly")] use ::iced_x86::SymbolResolver;
+#[cfg(feature = "disassembly")] use ::iced_x86::SymbolResult;
+#[cfg(feature = "std")] use ::libc::*;
+use ::alloc::alloc::alloc;
+use ::alloc::alloc::realloc;
+use ::alloc::alloc::dealloc;
+use ::alloc::alloc::Layout;
+use ::alloc::boxed::Box;
+#[cfg(feature = "disassembly")] use ::alloc::collections::BTreeMap;
+use ::alloc::string::String;
+use ::alloc::vec::Vec;
+#[cfg(all(feature = "std", target_arch = "x86_64"))] use ::core::arch::x86_64::__cpuid;
+use ::core::error::Error;
+use ::core::fmt;
+use ::core::fmt::Debug;
+use ::core::fmt::Display;
+use ::core::fmt::Formatter;
+use ::core::marker::PhantomData;
+use ::core::mem::align_of;
+use ::core::mem::size_of;
+use ::core::mem::take;
+use ::core::mem::transmute;
+#[cfg(all(feature = "std", target_arch = "x86_64"))] use ::core::mem::transmute_copy;
+use ::core::ops::Add;
+use ::core::ops::AddAssign;
+use ::core::ops::BitAnd;
+use ::core::ops::BitAndAssign;
+use ::core::ops::BitOr;
+use ::core::ops::BitOrAssign;
+use ::core::ops::BitXor;
+use ::core::ops::BitXorAssign;
+#[cfg(all(feature = "std", target_arch = "x86_64"))] use ::core::ops::Deref;
+use ::core::ops::Div;
+use ::core::ops::DivAssign;
+use ::core::ops::Mul;
+use ::core::ops::MulAssign;
+use ::core::ops::Neg;
+use ::core::ops::Not;
+use ::core::ops::Rem;
+use ::core::ops::RemAssign;
+use ::core::ops::Shl;
+use ::core::ops::ShlAssign;
+use ::core::ops::Shr;
+use ::core::ops::ShrAssign;
+use ::core::ops::Sub;
+use ::core::ops::SubAssign;
+use ::core::ptr::copy;
+use ::core::ptr::copy_nonoverlapping;
+use ::core::ptr::NonNull;
+#[cfg(feature = "gdb")] use ::core::ptr::addr_of_mut;
+#[cfg(feature = "std")] use ::core::ptr::null_mut;
+use ::core::slice::from_raw_parts;
+#[cfg(feature = "std")] use ::std::collections::HashMap;
+#[cfg(feature = "perf")] use ::std::fs::OpenOptions;
+#[cfg(feature = "std")] use ::std::io;
+#[cfg(feature = "perf")] use ::std::io::Write;
+#[cfg(all(feature = "std", target_arch = "x86_64"))] use ::std::sync::Arc;
+#[cfg(all(feature = "std", any(target_arch = "x86_64", feature = "gdb")))] use ::std::sync::Mutex;
+#[cfg(all(feature = "std", target_arch = "x86_64"))] use ::std::sync::atomic::fence;
+#[cfg(all(feature = "std", target_arch = "x86_64"))] use ::std::sync::atomic::Ordering;
 
 
 /// Mnemonic parameter types.
 pub mod mnemonic_parameter_types;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests;
 
 
+include!("AbsoluteAddressTooFarError.rs");
+include!("AssembledSnapshot.rs");
 include!("ByteEmitter.rs");
+include!("CapacityExceededError.rs");
+include!("Checkpoint.rs");
+#[cfg(all(feature = "std", target_arch = "x86_64"))] include!("CodeCache.rs");
+#[cfg(all(feature = "std", target_arch = "x86_64"))] include!("CodeCacheFunction.rs");
 include!("Displacement.rs");
-include!("ExecutableAnonymousMemoryMap.rs");
-include!("ExecutableAnonymousMemoryMapCreationError.rs");
+include!("ElfObjectWriter.rs");
+#[cfg(feature = "std")] include!("ExecutableAnonymousMemoryMap.rs");
+#[cfg(feature = "std")] include!("ExecutableAnonymousMemoryMapCreationError.rs");
+include!("GdbJitRegistration.rs");
+#[cfg(feature = "std")] include!("GrowthPolicy.rs");
+include!("Inst.rs");
 include!("InstructionPointer.rs");
+include!("InstructionPointerArithmetic.rs");
+include!("InstructionHook.rs");
 include!("InstructionPointerValidity.rs");
 include!("InstructionStream.rs");
 include!("InstructionStreamHints.rs");
+#[cfg(all(feature = "std", target_arch = "x86_64"))] include!("JitFunction.rs");
+include!("LabelSymbolResolver.rs");
 include!("LabelledLocations.rs");
+include!("MachOObjectWriter.rs");
 include!("NearJmpResult.rs");
+include!("PatchPoint.rs");
+#[cfg(all(feature = "std", target_arch = "x86_64"))] include!("PublishedExecutableAnonymousMemoryMap.rs");
+include!("Relocation.rs");
+include!("RelocationKind.rs");
+include!("Section.rs");
+include!("SectionId.rs");
 include!("ShortJmpResult.rs");
+include!("ShortJumpTooFarError.rs");
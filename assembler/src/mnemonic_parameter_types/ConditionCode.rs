@@ -0,0 +1,113 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// Represents the condition tested by a `Jcc`, `CMOVcc` or `SETcc` instruction.
+///
+/// The value is the `cc` in the opcode, eg `0x70 + cc` for a short `Jcc`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum ConditionCode
+{
+	/// Overflow (`OF=1`); `JO`.
+	Overflow = 0x0,
+	
+	/// Not overflow (`OF=0`); `JNO`.
+	NotOverflow = 0x1,
+	
+	/// Below (`CF=1`); `JB`, `JC` and `JNAE`.
+	Below = 0x2,
+	
+	/// Above or equal (`CF=0`); `JAE`, `JNB` and `JNC`.
+	AboveOrEqual = 0x3,
+	
+	/// Equal (`ZF=1`); `JE` and `JZ`.
+	Equal = 0x4,
+	
+	/// Not equal (`ZF=0`); `JNE` and `JNZ`.
+	NotEqual = 0x5,
+	
+	/// Below or equal (`CF=1 or ZF=1`); `JBE` and `JNA`.
+	BelowOrEqual = 0x6,
+	
+	/// Above (`CF=0 and ZF=0`); `JA` and `JNBE`.
+	Above = 0x7,
+	
+	/// Sign (`SF=1`); `JS`.
+	Sign = 0x8,
+	
+	/// Not sign (`SF=0`); `JNS`.
+	NotSign = 0x9,
+	
+	/// Parity even (`PF=1`); `JP` and `JPE`.
+	ParityEven = 0xA,
+	
+	/// Parity odd (`PF=0`); `JNP` and `JPO`.
+	ParityOdd = 0xB,
+	
+	/// Less (`SF≠OF`); `JL` and `JNGE`.
+	Less = 0xC,
+	
+	/// Greater or equal (`SF=OF`); `JGE` and `JNL`.
+	GreaterOrEqual = 0xD,
+	
+	/// Less or equal (`ZF=1 or SF≠OF`); `JLE` and `JNG`.
+	LessOrEqual = 0xE,
+	
+	/// Greater (`ZF=0 and SF=OF`); `JG` and `JNLE`.
+	Greater = 0xF,
+}
+
+impl Into<u8> for ConditionCode
+{
+	#[inline(always)]
+	fn into(self) -> u8
+	{
+		self as u8
+	}
+}
+
+impl ConditionCode
+{
+	/// All sixteen condition codes, in encoding order.
+	pub const All: [ConditionCode; 16] =
+	[
+		ConditionCode::Overflow,
+		ConditionCode::NotOverflow,
+		ConditionCode::Below,
+		ConditionCode::AboveOrEqual,
+		ConditionCode::Equal,
+		ConditionCode::NotEqual,
+		ConditionCode::BelowOrEqual,
+		ConditionCode::Above,
+		ConditionCode::Sign,
+		ConditionCode::NotSign,
+		ConditionCode::ParityEven,
+		ConditionCode::ParityOdd,
+		ConditionCode::Less,
+		ConditionCode::GreaterOrEqual,
+		ConditionCode::LessOrEqual,
+		ConditionCode::Greater,
+	];
+	
+	/// Alias of `NotOverflow`.
+	pub const NoOverflow: Self = ConditionCode::NotOverflow;
+	
+	/// Alias of `NotSign`.
+	pub const NoSign: Self = ConditionCode::NotSign;
+	
+	/// Alias of `ParityEven`.
+	pub const Parity: Self = ConditionCode::ParityEven;
+	
+	/// Alias of `ParityOdd`.
+	pub const NoParity: Self = ConditionCode::ParityOdd;
+	
+	/// The opposite condition, eg `NotEqual` for `Equal` and `AboveOrEqual` for `Below`.
+	///
+	/// Condition codes come in pairs which differ only in the lowest bit.
+	#[inline(always)]
+	pub fn invert(self) -> Self
+	{
+		unsafe { transmute((self as u8) ^ 0x1) }
+	}
+}
@@ -9,15 +9,43 @@ pub(crate) trait MemoryOrRegister: Sized
 	#[inline(always)]
 	fn emit_mod_rm_sib(self, byte_emitter: &mut ByteEmitter, reg: impl Register);
 	
-	/// Emits REX prefix.
+	/// The `REX.B` and `REX.X` bits (and, for `SPL`, `BPL`, `SIL` and `DIL`, the bare `REX` prefix) needed to encode this as the `rm` operand.
 	#[inline(always)]
-	fn emit_rex_3(self, byte_emitter: &mut ByteEmitter, r: impl Register, byte: u8);
+	fn rex_bits(self) -> u8;
 	
-	/// Emits REX prefix.
+	/// Emits REX prefix, if one is needed.
+	///
+	/// `byte` is either `0x00` or `REX.W`.
 	#[inline(always)]
-	fn emit_rex_2(self, byte_emitter: &mut ByteEmitter, byte: u8);
+	fn emit_rex_3(self, byte_emitter: &mut ByteEmitter, r: impl Register, byte: u8)
+	{
+		if let Some(rex) = InstructionStream::compute_rex(byte == InstructionStream::REX_W, r, self)
+		{
+			byte_emitter.emit_u8(rex)
+		}
+	}
+	
+	/// Emits REX prefix, if one is needed.
+	///
+	/// `byte` is either `0x00` or `REX.W`.
+	///
+	/// The `reg` field either holds an opcode extension or is unused, so register 0 (which never needs a REX prefix) stands in for it.
+	#[inline(always)]
+	fn emit_rex_2(self, byte_emitter: &mut ByteEmitter, byte: u8)
+	{
+		self.emit_rex_3(byte_emitter, Register64Bit::RAX, byte)
+	}
 	
 	/// Emits VEX prefix.
 	#[inline(always)]
 	fn emit_vex_prefix(self, byte_emitter: &mut ByteEmitter, mmmmm: u8, L: u8, pp: u8, w: u8, vvvv: impl Register, r: impl Register);
+	
+	/// Is this one of the legacy high 8-bit registers `AH`, `CH`, `DH` or `BH`?
+	///
+	/// These can not be encoded in an instruction that has a REX prefix.
+	#[inline(always)]
+	fn is_register_high_8_bits(&self) -> bool
+	{
+		false
+	}
 }
@@ -18,9 +18,33 @@ pub trait Immediate: From<i8> + From<u8>
 	const Maximum: Self;
 	
 	/// Signed integer type of the underlying value.
-	type SignedInteger;
+	type SignedInteger: Into<i64>;
 	
 	/// Underlying signed value.
 	#[inline(always)]
 	fn value(self) -> Self::SignedInteger;
+	
+	/// Can the underlying signed value be encoded as a sign-extended 8-bit immediate?
+	#[inline(always)]
+	fn fits_in_i8(self) -> bool
+	{
+		let value = self.value().into();
+		value >= i8::MIN as i64 && value <= i8::MAX as i64
+	}
+	
+	/// Can the underlying signed value be encoded as a sign-extended 16-bit immediate?
+	#[inline(always)]
+	fn fits_in_i16(self) -> bool
+	{
+		let value = self.value().into();
+		value >= i16::MIN as i64 && value <= i16::MAX as i64
+	}
+	
+	/// Can the underlying signed value be encoded as a sign-extended 32-bit immediate?
+	#[inline(always)]
+	fn fits_in_i32(self) -> bool
+	{
+		let value = self.value().into();
+		value >= i32::MIN as i64 && value <= i32::MAX as i64
+	}
 }
@@ -0,0 +1,65 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// An 8-bit immediate which the processor sign extends to the operand size, as used by, for example, `ADD r/m64, imm8`.
+///
+/// `Immediate8Bit` is just 8 raw bits, so `Immediate8Bit::from(0xFFu8)` is `-1` and adds `-1`, not `255`, once sign extended.
+/// This is correct for `MOV r8, imm8`, but is a gotcha for sign-extended instructions; constructing an immediate using `SignExtendedImmediate8Bit::new()` rejects values, such as `0x80` to `0xFF`, which do not survive sign extension.
+///
+/// The sign-extending `imm8` forms of `ADD`, `SUB`, `AND`, `OR`, `XOR`, `CMP`, `ADC`, `SBB` and `IMUL` on 16-bit, 32-bit and 64-bit registers and memory, and of `PUSHQ` and `PUSHW`, take this rather than `Immediate8Bit`, so an unsigned byte can not be passed to them by accident; use `From<i8>` or `new()`.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SignExtendedImmediate8Bit(pub(crate) i8);
+
+impl SignExtendedImmediate8Bit
+{
+	/// The immediate for `value`; a value outside of `-128` to `127` can not be sign extended from 8 bits, and is `None`.
+	#[inline(always)]
+	pub fn new(value: i64) -> Option<Self>
+	{
+		if Immediate64Bit(value).fits_in_i8()
+		{
+			Some(SignExtendedImmediate8Bit(value as i8))
+		}
+		else
+		{
+			None
+		}
+	}
+	
+	/// The value after sign extension.
+	#[inline(always)]
+	pub fn value(self) -> i64
+	{
+		self.0 as i64
+	}
+}
+
+impl From<i8> for SignExtendedImmediate8Bit
+{
+	#[inline(always)]
+	fn from(immediate: i8) -> Self
+	{
+		SignExtendedImmediate8Bit(immediate)
+	}
+}
+
+impl From<SignExtendedImmediate8Bit> for Immediate8Bit
+{
+	#[inline(always)]
+	fn from(immediate: SignExtendedImmediate8Bit) -> Self
+	{
+		Immediate8Bit(immediate.0)
+	}
+}
+
+impl AsDisplacement for SignExtendedImmediate8Bit
+{
+	type D = u8;
+	
+	#[inline(always)]
+	fn displacement(self) -> Self::D
+	{
+		self.0 as u8
+	}
+}
@@ -27,9 +27,9 @@ macro_rules! impl_immediate
 			
 			const One: Self = $name(1);
 			
-			const Minimum: Self = $name(::std::$signed_size::MIN);
+			const Minimum: Self = $name($signed_size::MIN);
 			
-			const Maximum: Self = $name(::std::$signed_size::MAX);
+			const Maximum: Self = $name($signed_size::MAX);
 			
 			type SignedInteger = $signed_size;
 			
@@ -13,3 +13,4 @@ include!("Immediate8Bit.rs");
 include!("Immediate16Bit.rs");
 include!("Immediate32Bit.rs");
 include!("Immediate64Bit.rs");
+include!("SignExtendedImmediate8Bit.rs");
@@ -29,6 +29,32 @@ impl Into<u8> for IndexScale
 	}
 }
 
+impl IndexScale
+{
+	/// The index scale for a multiplier of 1, 2, 4 or 8; any other multiplier can not be encoded, and is `None`.
+	#[inline(always)]
+	pub fn from_multiplier(multiplier: u8) -> Option<Self>
+	{
+		use self::IndexScale::*;
+		
+		match multiplier
+		{
+			1 => Some(x1),
+			2 => Some(x2),
+			4 => Some(x4),
+			8 => Some(x8),
+			_ => None,
+		}
+	}
+	
+	/// The multiplier, ie 1, 2, 4 or 8.
+	#[inline(always)]
+	pub fn multiplier(self) -> u8
+	{
+		1 << (self as u8)
+	}
+}
+
 impl Default for IndexScale
 {
 	#[inline(always)]
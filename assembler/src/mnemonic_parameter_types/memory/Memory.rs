@@ -14,6 +14,8 @@ pub trait Memory: From<MemoryOperand> + Into<MemoryOperand> + Sized
 	fn relative_instruction_pointer_relative_displacement(displacement: Immediate32Bit) -> Self;
 	
 	/// Create a new memory operand using the `displacement` form.
+	///
+	/// This is an absolute address, not a `RIP`-relative one; it is encoded with a scaled index byte (SIB) with no base and no index, as a bare `ModR/M` `disp32` means `RIP+disp32` in 64-bit mode.
 	#[inline(always)]
 	fn displacement(displacement: Immediate32Bit) -> Self;
 	
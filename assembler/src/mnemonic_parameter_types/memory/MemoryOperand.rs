@@ -3,6 +3,8 @@
 
 
 /// A memory operand.
+///
+/// Displays as an Intel-syntax address, eg `[rax + rcx*4 + 0x10]`, `fs:[0x20]` or `[rip + 0x10]`.
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct MemoryOperand
 {
@@ -49,35 +51,11 @@ impl MemoryOrRegister for MemoryOperand
 	}
 	
 	#[inline(always)]
-	fn emit_rex_3(self, byte_emitter: &mut ByteEmitter, r: impl Register, mut byte: u8)
-	{
-		byte |= if r.requires_rex_byte()
-		{
-			InstructionStream::REX
-		}
-		else
-		{
-			0x00
-		};
-		
-		byte |= if r.requires_rex_bit()
-		{
-			InstructionStream::REX_R
-		}
-		else
-		{
-			0x00
-		};
-		
-		self.emit_rex_2(byte_emitter, byte)
-	}
-	
-	#[inline(always)]
-	fn emit_rex_2(self, byte_emitter: &mut ByteEmitter, mut byte: u8)
+	fn rex_bits(self) -> u8
 	{
 		let rm = self;
 		
-		byte |= if rm.has_base_register() && rm.get_base_register().requires_rex_bit()
+		let rex = if rm.has_base_register() && rm.get_base_register().requires_rex_bit()
 		{
 			InstructionStream::REX_B
 		}
@@ -86,16 +64,14 @@ impl MemoryOrRegister for MemoryOperand
 			0x00
 		};
 		
-		byte |= if rm.has_index_register() && rm.get_index_register().requires_rex_bit()
+		rex | if rm.has_index_register() && rm.get_index_register().requires_rex_bit()
 		{
 			InstructionStream::REX_X
 		}
 		else
 		{
 			0x00
-		};
-		
-		byte_emitter.emit_u8_if_not_zero(byte);
+		}
 	}
 	
 	#[inline(always)]
@@ -183,27 +159,21 @@ impl Memory for MemoryOperand
 		Self::new(displacement, Self::NoBaseOrIndexRegister, Some(index_64), scale, Self::NoSegmentRegister, false, false)
 	}
 	
-	/// If `base_64` is `RBP`, then the SIB byte is followed by four-byte `disp32` field and the addressing mode is absolute.
-	///
-	/// If `index_64` is `RSP`, then the index is ignored.
+	/// `index_64` can not be `RSP`; this panics.
 	#[inline(always)]
 	fn base_64_index_64(base_64: Register64Bit, index_64: Register64Bit) -> Self
 	{
 		Self::new(Immediate32Bit(0), Some(base_64), Some(index_64), IndexScale::x1, Self::NoSegmentRegister, false, false)
 	}
 	
-	/// If `base_64` is `RBP`, then the SIB byte is followed by four-byte `disp32` field and the addressing mode is absolute.
-	///
-	/// If `index_64` is `RSP`, then the index is ignored.
+	/// `index_64` can not be `RSP`; this panics.
 	#[inline(always)]
 	fn base_64_index_64_scale(base_64: Register64Bit, index_64: Register64Bit, scale: IndexScale) -> Self
 	{
 		Self::new(Immediate32Bit(0), Some(base_64), Some(index_64), scale, Self::NoSegmentRegister, false, false)
 	}
 	
-	/// If `base_64` is `RBP`, then the SIB byte is followed by four-byte `disp32` field and the addressing mode is absolute.
-	///
-	/// If `index_64` is `RSP`, then the index is ignored.
+	/// `index_64` can not be `RSP`; this panics.
 	#[inline(always)]
 	fn base_64_index_64_scale_displacement(base_64: Register64Bit, index_64: Register64Bit, scale: IndexScale, displacement: Immediate32Bit) -> Self
 	{
@@ -349,12 +319,117 @@ impl Memory for MemoryOperand
 	}
 }
 
+impl Display for MemoryOperand
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		const Register64BitNames: [&str; 16] = ["rax", "rcx", "rdx", "rbx", "rsp", "rbp", "rsi", "rdi", "r8", "r9", "r10", "r11", "r12", "r13", "r14", "r15"];
+		const Register32BitNames: [&str; 16] = ["eax", "ecx", "edx", "ebx", "esp", "ebp", "esi", "edi", "r8d", "r9d", "r10d", "r11d", "r12d", "r13d", "r14d", "r15d"];
+		const SegmentRegisterNames: [&str; 6] = ["es", "cs", "ss", "ds", "fs", "gs"];
+		
+		if let Some(segment_register) = self.segment_register
+		{
+			write!(f, "{}:", SegmentRegisterNames[segment_register as usize])?;
+		}
+		
+		let register_names = if self.address_override_for_32_bit
+		{
+			&Register32BitNames
+		}
+		else
+		{
+			&Register64BitNames
+		};
+		
+		f.write_str("[")?;
+		
+		let mut has_register = false;
+		
+		if self.relative_instruction_pointer_offset
+		{
+			f.write_str("rip")?;
+			has_register = true;
+		}
+		
+		if let Some(base_register) = self.base_register
+		{
+			f.write_str(register_names[base_register as usize])?;
+			has_register = true;
+		}
+		
+		if let Some(index_register) = self.index_register
+		{
+			if has_register
+			{
+				f.write_str(" + ")?;
+			}
+			write!(f, "{}*{}", register_names[index_register as usize], self.index_scale.multiplier())?;
+			has_register = true;
+		}
+		
+		let displacement = self.get_displacement() as i64;
+		match (has_register, displacement < 0)
+		{
+			(false, false) => write!(f, "{:#x}", displacement)?,
+			(false, true) => write!(f, "-{:#x}", -displacement)?,
+			(true, _) if displacement == 0 => (),
+			(true, false) => write!(f, " + {:#x}", displacement)?,
+			(true, true) => write!(f, " - {:#x}", -displacement)?,
+		}
+		
+		f.write_str("]")
+	}
+}
+
+impl MemoryOperand
+{
+	/// Start building a memory operand with no parts.
+	#[inline(always)]
+	pub fn builder() -> MemoryOperandBuilder
+	{
+		MemoryOperandBuilder::default()
+	}
+	
+	/// Start building a memory operand with `base_64` as the base register.
+	#[inline(always)]
+	pub fn base(base_64: Register64Bit) -> MemoryOperandBuilder
+	{
+		Self::builder().base(base_64)
+	}
+	
+	/// Start building a memory operand with `index_64` scaled by `scale` as the index.
+	///
+	/// `index_64` can not be `RSP`; this panics.
+	#[inline(always)]
+	pub fn index(index_64: Register64Bit, scale: IndexScale) -> MemoryOperandBuilder
+	{
+		Self::builder().index(index_64, scale)
+	}
+	
+	/// Does this memory operand use 32-bit base and index registers, and so need the `0x67` address-size override prefix?
+	#[inline(always)]
+	pub fn has_address_override_for_32_bit(self) -> bool
+	{
+		self.address_override_for_32_bit
+	}
+}
+
 impl MemoryOperand
 {
 	const NoBaseOrIndexRegister: Option<Register64Bit> = None;
 	
 	const NoSegmentRegister: Option<SegmentRegister> = None;
 	
+	/// Only absolute addresses (those with neither a base nor an index register and which are not `RIP`-relative) can be checked.
+	#[inline(always)]
+	pub(crate) fn debug_assert_absolute_address_is_aligned(self, alignment: usize)
+	{
+		if !self.relative_instruction_pointer_offset && !self.has_base_register() && !self.has_index_register()
+		{
+			debug_assert_eq!((self.get_displacement() as u32 as usize) % alignment, 0, "absolute address of memory operand must be aligned to {} bytes", alignment);
+		}
+	}
+	
 	#[inline(always)]
 	fn has_base_register(self) -> bool
 	{
@@ -401,6 +476,7 @@ impl MemoryOperand
 	fn new(displacement: Immediate32Bit, base_register: Option<impl GeneralPurposeRegister>, index_register: Option<impl GeneralPurposeRegister>, index_scale: IndexScale, segment_register: Option<SegmentRegister>, address_override_for_32_bit: bool, relative_instruction_pointer_offset: bool) -> Self
 	{
 		debug_assert_eq!(address_override_for_32_bit && relative_instruction_pointer_offset, false, "address_override_for_32_bit and relative_instruction_pointer_offset can not both be specified");
+		assert!(index_register.is_none_or(|index_register| index_register.index() != Self::RegisterRspOrR12), "RSP (or ESP) can not be used as an index register; the scaled index byte (SIB) uses it to mean 'no index'");
 		
 		Self
 		{
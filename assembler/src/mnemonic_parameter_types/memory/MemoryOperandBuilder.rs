@@ -0,0 +1,99 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// A fluent builder for a `[segment:base + index*scale + displacement]` memory operand.
+///
+/// Start one with `MemoryOperand::base()`, `MemoryOperand::index()` or `MemoryOperand::builder()` (which is needed to start with a 32-bit register, as `MemoryOperand::base_32()` is the `Memory` constructor); every part is optional.
+///
+/// Base and index registers are either both 64-bit or both 32-bit; 32-bit registers (eg `[ebx + ecx]`) make the operand use the `0x67` address-size override prefix.
+///
+/// `build()` produces any memory type, eg `Any64BitMemory`, so the result can be passed directly to a mnemonic.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MemoryOperandBuilder
+{
+	displacement: Immediate32Bit,
+	base_register: Option<Register64Bit>,
+	index_register: Option<Register64Bit>,
+	index_scale: IndexScale,
+	segment_register: Option<SegmentRegister>,
+	base_register_is_32_bit: bool,
+	index_register_is_32_bit: bool,
+}
+
+impl MemoryOperandBuilder
+{
+	/// Use `base_64` as the base register, replacing any previous one.
+	#[inline(always)]
+	pub fn base(mut self, base_64: Register64Bit) -> Self
+	{
+		self.base_register = Some(base_64);
+		self.base_register_is_32_bit = false;
+		self
+	}
+	
+	/// Use `base_32` as the base register, replacing any previous one.
+	///
+	/// Any index register must then also be 32-bit (otherwise `build()` panics).
+	#[inline(always)]
+	pub fn base_32(mut self, base_32: Register32Bit) -> Self
+	{
+		self.base_register = Some(unsafe { transmute::<u8, Register64Bit>(base_32.index()) });
+		self.base_register_is_32_bit = true;
+		self
+	}
+	
+	/// Use `index_64` scaled by `scale` as the index, replacing any previous one.
+	///
+	/// `index_64` can not be `RSP` (otherwise `build()` panics).
+	#[inline(always)]
+	pub fn index(mut self, index_64: Register64Bit, scale: IndexScale) -> Self
+	{
+		self.index_register = Some(index_64);
+		self.index_scale = scale;
+		self.index_register_is_32_bit = false;
+		self
+	}
+	
+	/// Use `index_32` scaled by `scale` as the index, replacing any previous one.
+	///
+	/// `index_32` can not be `ESP`, and any base register must then also be 32-bit (otherwise `build()` panics).
+	#[inline(always)]
+	pub fn index_32(mut self, index_32: Register32Bit, scale: IndexScale) -> Self
+	{
+		self.index_register = Some(unsafe { transmute::<u8, Register64Bit>(index_32.index()) });
+		self.index_scale = scale;
+		self.index_register_is_32_bit = true;
+		self
+	}
+	
+	/// Use `displacement`, replacing any previous one.
+	#[inline(always)]
+	pub fn displacement(mut self, displacement: impl Into<Immediate32Bit>) -> Self
+	{
+		self.displacement = displacement.into();
+		self
+	}
+	
+	/// Use `segment_register` as a segment override, replacing any previous one.
+	#[inline(always)]
+	pub fn segment(mut self, segment_register: SegmentRegister) -> Self
+	{
+		self.segment_register = Some(segment_register);
+		self
+	}
+	
+	/// Build the memory operand.
+	///
+	/// Panics if a 32-bit base register is mixed with a 64-bit index register, or vice versa, or if the index register is `RSP` or `ESP`.
+	#[inline(always)]
+	pub fn build<M: Memory>(self) -> M
+	{
+		let base_register_is_32_bit = self.base_register.is_some() && self.base_register_is_32_bit;
+		let index_register_is_32_bit = self.index_register.is_some() && self.index_register_is_32_bit;
+		assert!(self.base_register.is_none() || self.index_register.is_none() || base_register_is_32_bit == index_register_is_32_bit, "32-bit and 64-bit base and index registers can not be mixed in the same memory operand");
+		
+		let address_override_for_32_bit = base_register_is_32_bit || index_register_is_32_bit;
+		M::from(MemoryOperand::new(self.displacement, self.base_register, self.index_register, self.index_scale, self.segment_register, address_override_for_32_bit, false))
+	}
+}
@@ -0,0 +1,90 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of assembler, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+/// A memory operand with a vector index register (`VSIB`), as used by the AVX2 gather instructions.
+///
+/// Each element of the index register is scaled, added to the base register (if any) and the displacement to give the address of one element to gather.
+///
+/// Converts into `Any32BitMemory` (for gathers with doubleword indices, eg `vgatherdps_YMM_Any32BitMemory_YMM()`) and `Any64BitMemory` (for gathers with quadword indices, eg `vgatherqpd_YMM_Any64BitMemory_YMM()`).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VectorMemoryOperand(MemoryOperand);
+
+impl VectorMemoryOperand
+{
+	/// `[base_64 + index × scale + displacement]`, with a `XMM` index register.
+	#[inline(always)]
+	pub fn base_64_xmm_index(base_64: Register64Bit, index: XMMRegister, scale: IndexScale, displacement: Immediate32Bit) -> Self
+	{
+		Self::new(Some(base_64), index.index(), scale, displacement)
+	}
+	
+	/// `[base_64 + index × scale + displacement]`, with a `YMM` index register.
+	#[inline(always)]
+	pub fn base_64_ymm_index(base_64: Register64Bit, index: YMMRegister, scale: IndexScale, displacement: Immediate32Bit) -> Self
+	{
+		Self::new(Some(base_64), index.index(), scale, displacement)
+	}
+	
+	/// `[index × scale + displacement]`, with a `XMM` index register.
+	#[inline(always)]
+	pub fn xmm_index(index: XMMRegister, scale: IndexScale, displacement: Immediate32Bit) -> Self
+	{
+		Self::new(None, index.index(), scale, displacement)
+	}
+	
+	/// `[index × scale + displacement]`, with a `YMM` index register.
+	#[inline(always)]
+	pub fn ymm_index(index: YMMRegister, scale: IndexScale, displacement: Immediate32Bit) -> Self
+	{
+		Self::new(None, index.index(), scale, displacement)
+	}
+	
+	/// The index field of the scaled index byte (SIB) and `VEX.X` encode a vector register number in exactly the same way as a general purpose register number.
+	///
+	/// Unlike a general purpose index register, any vector register (including register 4) can be used as an index, so this does not use the checked `MemoryOperand` constructors.
+	#[inline(always)]
+	fn new(base_64: Option<Register64Bit>, index: u8, scale: IndexScale, displacement: Immediate32Bit) -> Self
+	{
+		VectorMemoryOperand
+		(
+			MemoryOperand
+			{
+				displacement,
+				base_register: base_64,
+				index_register: Some(unsafe { transmute::<u8, Register64Bit>(index) }),
+				index_scale: scale,
+				segment_register: None,
+				address_override_for_32_bit: false,
+				relative_instruction_pointer_offset: false,
+			}
+		)
+	}
+}
+
+impl From<VectorMemoryOperand> for MemoryOperand
+{
+	#[inline(always)]
+	fn from(memory: VectorMemoryOperand) -> Self
+	{
+		memory.0
+	}
+}
+
+impl From<VectorMemoryOperand> for Any32BitMemory
+{
+	#[inline(always)]
+	fn from(memory: VectorMemoryOperand) -> Self
+	{
+		Any32BitMemory(memory.0)
+	}
+}
+
+impl From<VectorMemoryOperand> for Any64BitMemory
+{
+	#[inline(always)]
+	fn from(memory: VectorMemoryOperand) -> Self
+	{
+		Any64BitMemory(memory.0)
+	}
+}
@@ -19,6 +19,15 @@ macro_rules! impl_memory
 			}
 		}
 		
+		impl Display for $struct_name
+		{
+			#[inline(always)]
+			fn fmt(&self, f: &mut Formatter) -> fmt::Result
+			{
+				Display::fmt(&self.0, f)
+			}
+		}
+		
 		impl Into<MemoryOperand> for $struct_name
 		{
 			#[inline(always)]
@@ -253,15 +262,9 @@ macro_rules! impl_memory
 			}
 			
 			#[inline(always)]
-			fn emit_rex_3(self, byte_emitter: &mut ByteEmitter, r: impl Register, byte: u8)
-			{
-				self.memory_operand().emit_rex_3(byte_emitter, r, byte)
-			}
-			
-			#[inline(always)]
-			fn emit_rex_2(self, byte_emitter: &mut ByteEmitter, byte: u8)
+			fn rex_bits(self) -> u8
 			{
-				self.memory_operand().emit_rex_2(byte_emitter, byte)
+				self.memory_operand().rex_bits()
 			}
 			
 			#[inline(always)]
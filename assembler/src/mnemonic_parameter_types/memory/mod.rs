@@ -25,6 +25,8 @@ include!("HasMemoryOperand.rs");
 include!("IndexScale.rs");
 include!("Memory.rs");
 include!("MemoryOperand.rs");
+include!("MemoryOperandBuilder.rs");
+include!("VectorMemoryOperand.rs");
 include!("X87BinaryCodedDecimal80BitMemory.rs");
 include!("X87Float32BitMemory.rs");
 include!("X87Float64BitMemory.rs");
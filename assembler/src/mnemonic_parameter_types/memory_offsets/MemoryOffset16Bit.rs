@@ -3,6 +3,8 @@
 
 
 /// 16-bit memory offset.
+///
+/// The size is that of the value at the offset (eg `AX` in `MOV AX, moffs16`), not of the address, which is always 64-bit in long mode; 16-bit addressing is not available in long mode (an `0x67` address-size override prefix selects 32-bit addressing).
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum MemoryOffset16Bit
 {
@@ -140,6 +142,21 @@ impl From<u64> for MemoryOffset16Bit
 	}
 }
 
+impl Into<Option<SegmentRegister>> for MemoryOffset16Bit
+{
+	#[inline(always)]
+	fn into(self) -> Option<SegmentRegister>
+	{
+		use self::MemoryOffset16Bit::*;
+		
+		match self
+		{
+			SegmentOffsetForm16(segment_register, _) => Some(segment_register),
+			OffsetForm16(_) => None,
+		}
+	}
+}
+
 impl Into<(Option<SegmentRegister>, Immediate64Bit)> for MemoryOffset16Bit
 {
 	#[inline(always)]
@@ -3,6 +3,8 @@
 
 
 /// 64-bit memory offset.
+///
+/// The size is that of the value at the offset (eg `RAX` in `MOV RAX, moffs64`), not of the address, which is always a 64-bit absolute address in long mode; offsets wider than 32 bits are intentional and are encoded in full, never truncated.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum MemoryOffset64Bit
 {
@@ -140,6 +142,21 @@ impl From<u64> for MemoryOffset64Bit
 	}
 }
 
+impl Into<Option<SegmentRegister>> for MemoryOffset64Bit
+{
+	#[inline(always)]
+	fn into(self) -> Option<SegmentRegister>
+	{
+		use self::MemoryOffset64Bit::*;
+		
+		match self
+		{
+			SegmentOffsetForm64(segment_register, _) => Some(segment_register),
+			OffsetForm64(_) => None,
+		}
+	}
+}
+
 impl Into<(Option<SegmentRegister>, Immediate64Bit)> for MemoryOffset64Bit
 {
 	#[inline(always)]
@@ -3,6 +3,8 @@
 
 
 /// 8-bit memory offset.
+///
+/// The size is that of the value at the offset (eg `AL` in `MOV AL, moffs8`), not of the address, which is always a 64-bit absolute address in long mode; offsets wider than 32 bits are intentional and are encoded in full, never truncated.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum MemoryOffset8Bit
 {
@@ -27,7 +27,7 @@ pub enum MMRegister
 	/// A scratch register (also known as a temporary or (function) caller-saved register) when using the System V Application Binary Interface (ABI) for AMD64.
 	MM3 = 3,
 	
-	/// Register 7.
+	/// Register 4.
 	///
 	/// A scratch register (also known as a temporary or (function) caller-saved register) when using the System V Application Binary Interface (ABI) for AMD64.
 	MM4 = 4,
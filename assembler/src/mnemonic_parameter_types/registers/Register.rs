@@ -8,6 +8,9 @@ pub trait Register: Copy + Sized + Into<u8> + Default
 	#[doc(hidden)]
 	const IsRegister8Bit: bool = false;
 	
+	#[doc(hidden)]
+	const IsRegisterHigh8Bits: bool = false;
+	
 	/// Zero-based index of this register.
 	#[inline(always)]
 	fn index(self) -> u8;
@@ -19,6 +22,13 @@ pub trait Register: Copy + Sized + Into<u8> + Default
 		Self::IsRegister8Bit && self.index() > 3
 	}
 	
+	#[doc(hidden)]
+	#[inline(always)]
+	fn is_register_high_8_bits(self) -> bool
+	{
+		Self::IsRegisterHigh8Bits
+	}
+	
 	#[doc(hidden)]
 	#[inline(always)]
 	fn requires_rex_bit(self) -> bool
@@ -49,9 +59,11 @@ impl<R: Register> MemoryOrRegister for R
 	}
 	
 	#[inline(always)]
-	fn emit_rex_3(self, byte_emitter: &mut ByteEmitter, r: impl Register, mut byte: u8)
+	fn rex_bits(self) -> u8
 	{
-		byte |= if r.requires_rex_byte()
+		let rm = self;
+		
+		let rex = if rm.requires_rex_byte()
 		{
 			InstructionStream::REX
 		}
@@ -60,42 +72,20 @@ impl<R: Register> MemoryOrRegister for R
 			0x00
 		};
 		
-		byte |= if r.requires_rex_bit()
+		rex | if rm.requires_rex_bit()
 		{
-			InstructionStream::REX_R
+			InstructionStream::REX_B
 		}
 		else
 		{
 			0x00
-		};
-		
-		self.emit_rex_2(byte_emitter, byte);
+		}
 	}
 	
 	#[inline(always)]
-	fn emit_rex_2(self, byte_emitter: &mut ByteEmitter, mut byte: u8)
+	fn is_register_high_8_bits(&self) -> bool
 	{
-		let rm = self;
-		
-		byte |= if rm.requires_rex_byte()
-		{
-			InstructionStream::REX
-		}
-		else
-		{
-			0x00
-		};
-		
-		byte |= if rm.requires_rex_bit()
-		{
-			InstructionStream::REX_B
-		}
-		else
-		{
-			0x00
-		};
-		
-		byte_emitter.emit_u8_if_not_zero(byte);
+		R::IsRegisterHigh8Bits
 	}
 	
 	#[inline(always)]
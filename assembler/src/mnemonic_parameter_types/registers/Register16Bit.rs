@@ -189,29 +189,29 @@ impl LowGeneralPurposeRegister for Register16Bit
 	const MicrosoftX64CallingConventionIntegerFunctionArgument3: Self = Register16Bit::R9W;
 }
 
-impl Into<Register64Bit> for Register16Bit
+impl From<Register16Bit> for Register64Bit
 {
 	#[inline(always)]
-	fn into(self) -> Register64Bit
+	fn from(register: Register16Bit) -> Self
 	{
-		unsafe { transmute(self) }
+		unsafe { transmute(register) }
 	}
 }
 
-impl Into<Register32Bit> for Register16Bit
+impl From<Register16Bit> for Register32Bit
 {
 	#[inline(always)]
-	fn into(self) -> Register32Bit
+	fn from(register: Register16Bit) -> Self
 	{
-		unsafe { transmute(self) }
+		unsafe { transmute(register) }
 	}
 }
 
-impl Into<Register8Bit> for Register16Bit
+impl From<Register16Bit> for Register8Bit
 {
 	#[inline(always)]
-	fn into(self) -> Register8Bit
+	fn from(register: Register16Bit) -> Self
 	{
-		unsafe { transmute(self) }
+		unsafe { transmute(register) }
 	}
 }
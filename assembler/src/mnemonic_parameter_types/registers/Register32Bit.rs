@@ -189,29 +189,29 @@ impl LowGeneralPurposeRegister for Register32Bit
 	const MicrosoftX64CallingConventionIntegerFunctionArgument3: Self = Register32Bit::R9D;
 }
 
-impl Into<Register64Bit> for Register32Bit
+impl From<Register32Bit> for Register64Bit
 {
 	#[inline(always)]
-	fn into(self) -> Register64Bit
+	fn from(register: Register32Bit) -> Self
 	{
-		unsafe { transmute(self) }
+		unsafe { transmute(register) }
 	}
 }
 
-impl Into<Register16Bit> for Register32Bit
+impl From<Register32Bit> for Register16Bit
 {
 	#[inline(always)]
-	fn into(self) -> Register16Bit
+	fn from(register: Register32Bit) -> Self
 	{
-		unsafe { transmute(self) }
+		unsafe { transmute(register) }
 	}
 }
 
-impl Into<Register8Bit> for Register32Bit
+impl From<Register32Bit> for Register8Bit
 {
 	#[inline(always)]
-	fn into(self) -> Register8Bit
+	fn from(register: Register32Bit) -> Self
 	{
-		unsafe { transmute(self) }
+		unsafe { transmute(register) }
 	}
 }
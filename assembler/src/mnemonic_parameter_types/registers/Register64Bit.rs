@@ -193,30 +193,30 @@ impl LowGeneralPurposeRegister for Register64Bit
 	const MicrosoftX64CallingConventionIntegerFunctionArgument3: Self = Register64Bit::R9;
 }
 
-impl Into<Register32Bit> for Register64Bit
+impl From<Register64Bit> for Register32Bit
 {
 	#[inline(always)]
-	fn into(self) -> Register32Bit
+	fn from(register: Register64Bit) -> Self
 	{
-		unsafe { transmute(self) }
+		unsafe { transmute(register) }
 	}
 }
 
-impl Into<Register16Bit> for Register64Bit
+impl From<Register64Bit> for Register16Bit
 {
 	#[inline(always)]
-	fn into(self) -> Register16Bit
+	fn from(register: Register64Bit) -> Self
 	{
-		unsafe { transmute(self) }
+		unsafe { transmute(register) }
 	}
 }
 
-impl Into<Register8Bit> for Register64Bit
+impl From<Register64Bit> for Register8Bit
 {
 	#[inline(always)]
-	fn into(self) -> Register8Bit
+	fn from(register: Register64Bit) -> Self
 	{
-		unsafe { transmute(self) }
+		unsafe { transmute(register) }
 	}
 }
 
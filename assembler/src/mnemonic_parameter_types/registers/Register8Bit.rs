@@ -193,29 +193,29 @@ impl LowGeneralPurposeRegister for Register8Bit
 	const MicrosoftX64CallingConventionIntegerFunctionArgument3: Self = Register8Bit::R9B;
 }
 
-impl Into<Register64Bit> for Register8Bit
+impl From<Register8Bit> for Register64Bit
 {
 	#[inline(always)]
-	fn into(self) -> Register64Bit
+	fn from(register: Register8Bit) -> Self
 	{
-		unsafe { transmute(self) }
+		unsafe { transmute(register) }
 	}
 }
 
-impl Into<Register32Bit> for Register8Bit
+impl From<Register8Bit> for Register32Bit
 {
 	#[inline(always)]
-	fn into(self) -> Register32Bit
+	fn from(register: Register8Bit) -> Self
 	{
-		unsafe { transmute(self) }
+		unsafe { transmute(register) }
 	}
 }
 
-impl Into<Register16Bit> for Register8Bit
+impl From<Register8Bit> for Register16Bit
 {
 	#[inline(always)]
-	fn into(self) -> Register16Bit
+	fn from(register: Register8Bit) -> Self
 	{
-		unsafe { transmute(self) }
+		unsafe { transmute(register) }
 	}
 }
@@ -3,42 +3,44 @@
 
 
 /// High 8-bits of the first four general purpose registers.
+///
+/// These are encoded using the same register numbers as `SPL`, `BPL`, `SIL` and `DIL` (4 to 7), and so can not be used in an instruction that requires a REX prefix (eg one that also uses `SPL` or `R8B`).
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u8)]
 pub enum RegisterHigh8BitsOf16Bits
 {
-	/// Register 0.
+	/// Register 4 (the high 8 bits of register 0).
 	///
 	/// Contains the integer return value from a function call when using the System V Application Binary Interface (ABI) for AMD64.
 	///
 	/// Contains the integer return value from a function call when using the Microsoft x64 Calling Convention.
 	///
 	/// A scratch register (also known as a temporary or (function) caller-saved register) when using the System V Application Binary Interface (ABI) for AMD64.
-	AH = 0,
+	AH = 4,
 	
-	/// Register 1.
+	/// Register 5 (the high 8 bits of register 1).
 	///
 	/// Contains the fourth integer function argument to a function call when using the System V Application Binary Interface for x86-64.
 	///
 	/// Contains the first integer function argument to a function call when using the Microsoft x64 Calling Convention.
 	///
 	/// A scratch register (also known as a temporary or (function) caller-saved register) when using the System V Application Binary Interface (ABI) for AMD64.
-	CH = 1,
+	CH = 5,
 	
-	/// Register 2.
+	/// Register 6 (the high 8 bits of register 2).
 	///
 	/// Contains the third integer function argument to a function call when using the System V Application Binary Interface for x86-64.
 	///
 	/// Contains the second integer function argument to a function call when using the Microsoft x64 Calling Convention.
 	///
 	/// A scratch register (also known as a temporary or (function) caller-saved register) when using the System V Application Binary Interface (ABI) for AMD64.
-	DH = 2,
+	DH = 6,
 	
-	/// Register 3.
+	/// Register 7 (the high 8 bits of register 3).
 	///
 	/// A callee-saved register: a called function must preserve the value in this register when using the System V Application Binary Interface (ABI) for AMD64.
 	/// In other words, upon returning, it should make sure the value originally in this register when the call was made is the current value of this register.
-	BH = 3,
+	BH = 7,
 }
 
 impl Default for RegisterHigh8BitsOf16Bits
@@ -61,6 +63,8 @@ impl Into<u8> for RegisterHigh8BitsOf16Bits
 
 impl Register for RegisterHigh8BitsOf16Bits
 {
+	const IsRegisterHigh8Bits: bool = true;
+	
 	#[inline(always)]
 	fn index(self) -> u8
 	{
@@ -73,7 +77,7 @@ impl Into<Register64Bit> for RegisterHigh8BitsOf16Bits
 	#[inline(always)]
 	fn into(self) -> Register64Bit
 	{
-		unsafe { transmute(self) }
+		unsafe { transmute(self.index() - 4) }
 	}
 }
 
@@ -82,7 +86,7 @@ impl Into<Register32Bit> for RegisterHigh8BitsOf16Bits
 	#[inline(always)]
 	fn into(self) -> Register32Bit
 	{
-		unsafe { transmute(self) }
+		unsafe { transmute(self.index() - 4) }
 	}
 }
 
@@ -91,7 +95,7 @@ impl Into<Register16Bit> for RegisterHigh8BitsOf16Bits
 	#[inline(always)]
 	fn into(self) -> Register16Bit
 	{
-		unsafe { transmute(self) }
+		unsafe { transmute(self.index() - 4) }
 	}
 }
 
@@ -100,7 +104,7 @@ impl Into<Register8Bit> for RegisterHigh8BitsOf16Bits
 	#[inline(always)]
 	fn into(self) -> Register8Bit
 	{
-		unsafe { transmute(self) }
+		unsafe { transmute(self.index() - 4) }
 	}
 }
 
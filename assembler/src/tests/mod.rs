@@ -3,17 +3,31 @@
 
 
 use self::Register64Bit::*;
+#[cfg(target_arch = "x86_64")] use super::AbsoluteAddressTooFarError;
+#[cfg(target_arch = "x86_64")] use super::AssembledSnapshot;
+use super::CapacityExceededError;
+#[cfg(target_arch = "x86_64")] use super::CodeCache;
 use super::ExecutableAnonymousMemoryMap;
+use super::GrowthPolicy;
+#[cfg(target_arch = "x86_64")] use super::InstructionPointer;
+#[cfg(target_arch = "x86_64")] use super::InstructionPointerArithmetic;
+use super::InstructionStream;
 use super::InstructionStreamHints;
+use super::SectionId;
+use super::ShortJumpTooFarError;
+use super::mnemonic_parameter_types::ConditionCode;
 use super::mnemonic_parameter_types::memory::*;
 use super::mnemonic_parameter_types::registers::*;
 use ::std::io::Write;
 
 
+mod round_trip;
+
+
 #[test]
 pub fn lifecycle()
 {
-	let mut map = ExecutableAnonymousMemoryMap::new(4096, false).expect("Could not anonymously mmap");
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
 	let instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
 	
 	instruction_stream.finish();
@@ -22,7 +36,7 @@ pub fn lifecycle()
 #[test]
 pub fn labelling()
 {
-	let mut map = ExecutableAnonymousMemoryMap::new(4096, false).expect("Could not anonymously mmap");
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
 	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
 	
 	let label1 = instruction_stream.create_label();
@@ -34,9 +48,10 @@ pub fn labelling()
 }
 
 #[test]
+#[cfg(target_arch = "x86_64")]
 pub fn simple_function()
 {
-	let mut map = ExecutableAnonymousMemoryMap::new(4096, false).expect("Could not anonymously mmap");
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
 	
 	let _function_pointer =
 	{
@@ -54,7 +69,7 @@ pub fn simple_function()
 		
 		let (encoded_bytes, _hints) = instruction_stream.finish();
 		
-		assert_eq!(&bytes_to_string(encoded_bytes), "55 48 8B EC 31 C0 48 8B E5 5D C3", "Encoding of a basic function was wrong");
+		assert_eq!(&bytes_to_string(encoded_bytes), "55 48 8B EC 31 C0 C9 C3", "Encoding of a basic function was wrong");
 		
 		function_pointer
 	};
@@ -64,9 +79,2555 @@ pub fn simple_function()
 }
 
 #[test]
+#[cfg(target_arch = "x86_64")]
+pub fn finish_with_entry()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.ud2();
+	
+	let entry = instruction_stream.create_and_attach_label();
+	instruction_stream.mov_Register64Bit_Immediate64Bit(RAX, 42u64.into());
+	instruction_stream.ret();
+	
+	let (jit_function, _hints) = unsafe { instruction_stream.finish_with_entry::<unsafe extern "C" fn() -> u64>(entry) };
+	
+	assert_eq!(unsafe { (*jit_function)() }, 42, "function result was not 42");
+	assert_eq!(jit_function.function_pointer() as usize, jit_function.executable_anonymous_memory_map().address as usize + 2, "entry was not after ud2");
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn snapshot_and_restore()
+{
+	let mut first_map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = first_map.instruction_stream(&InstructionStreamHints::default());
+	
+	let entry = instruction_stream.create_and_attach_label();
+	let body = instruction_stream.create_label();
+	instruction_stream.jmp_Label(body).expect("Could not emit JMP");
+	instruction_stream.emit_label(entry);
+	
+	let serialized = instruction_stream.snapshot().to_bytes();
+	drop(instruction_stream);
+	
+	assert_eq!(AssembledSnapshot::from_bytes(&serialized[.. serialized.len() - 1]), None, "truncated snapshot was deserialized");
+	let snapshot = AssembledSnapshot::from_bytes(&serialized).expect("Could not deserialize snapshot");
+	assert_eq!(&snapshot.bytes()[2 .. ], &[0; 8], "emitted label was not zeroed");
+	
+	let mut second_map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = InstructionStream::from_snapshot(&mut second_map, &snapshot);
+	
+	instruction_stream.attach_label(body);
+	instruction_stream.mov_Register64Bit_Immediate64Bit(RAX, 42u64.into());
+	instruction_stream.ret();
+	
+	let start = instruction_stream.start_instruction_pointer();
+	let (jit_function, _hints) = unsafe { instruction_stream.finish_with_entry::<unsafe extern "C" fn() -> u64>(entry) };
+	
+	assert_eq!(unsafe { (*jit_function)() }, 42, "function result was not 42");
+	assert_eq!(unsafe { ((start + 2) as *const usize).read_unaligned() }, start, "emitted label was not relocated");
+}
+
+#[test]
+pub fn new_in_buffer()
+{
+	let mut buffer = [0xCC; 64];
+	let mut instruction_stream = InstructionStream::new_in_buffer(&mut buffer, &InstructionStreamHints::default());
+	
+	let body = instruction_stream.create_label();
+	instruction_stream.jmp_Label(body).expect("Could not emit JMP");
+	instruction_stream.attach_label(body);
+	instruction_stream.ret();
+	
+	let (encoded_bytes, _hints) = instruction_stream.finish();
+	
+	assert_eq!(&bytes_to_string(encoded_bytes), "EB 00 C3", "Encoding into a buffer was wrong");
+	assert_eq!(buffer[3], 0xCC, "bytes after the instructions were overwritten");
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn code_cache()
+{
+	use ::std::sync::Arc;
+	use ::std::thread;
+	
+	let code_cache = Arc::new(CodeCache::new());
+	
+	let publisher = code_cache.clone();
+	let function = thread::spawn(move ||
+	{
+		let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+		{
+			let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+			instruction_stream.ud2();
+			instruction_stream.mov_Register64Bit_Immediate64Bit(RAX, 42u64.into());
+			instruction_stream.ret();
+			instruction_stream.finish();
+		}
+		unsafe { publisher.publish::<unsafe extern "C" fn() -> u64>(map, 2) }
+	}).join().expect("publishing thread panicked");
+	
+	assert_eq!(code_cache.len(), 1, "map was not kept");
+	
+	let caller = function.clone();
+	let result = thread::spawn(move || unsafe { (*caller)() }).join().expect("calling thread panicked");
+	assert_eq!(result, 42, "function result was not 42");
+	
+	let same_entry = unsafe { function.function_at::<unsafe extern "C" fn() -> u64>(2) };
+	assert_eq!(unsafe { (*same_entry)() }, 42, "function_at() result was not 42");
+	
+	drop(code_cache);
+	assert_eq!(unsafe { (*function)() }, 42, "function did not outlive cache");
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub fn dual_mapping()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new_dual_mapping(4096, true).expect("Could not create dual mapping");
+	assert!(map.is_dual_mapping(), "map was not a dual mapping");
+	
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	let entry = instruction_stream.create_and_attach_label();
+	instruction_stream.mov_Register64Bit_Immediate64Bit(RAX, 42u64.into());
+	instruction_stream.ret();
+	
+	let (jit_function, _hints) = unsafe { instruction_stream.finish_with_entry::<unsafe extern "C" fn() -> u64>(entry) };
+	
+	assert_eq!(unsafe { (*jit_function)() }, 42, "function result was not 42");
+	
+	let immediate = jit_function.executable_anonymous_memory_map().writable_alias(jit_function.function_pointer() as usize + 2);
+	assert_ne!(immediate as usize, jit_function.function_pointer() as usize + 2, "writable alias was the executable alias");
+	unsafe { (immediate as *mut u64).write_unaligned(7) };
+	jit_function.executable_anonymous_memory_map().serialize_instruction_cache();
+	
+	assert_eq!(unsafe { (*jit_function)() }, 7, "function result was not patched to 7");
+}
+
+#[test]
+pub fn sixteen_bit_operands_emit_operand_size_prefix()
+{
+	use self::Register16Bit::*;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.mov_Register16Bit_Register16Bit(AX, BX);
+	instruction_stream.add_Register16Bit_Register16Bit(R8W, CX);
+	instruction_stream.sub_Register16Bit_Register16Bit(AX, BX);
+	instruction_stream.cmp_Register16Bit_Register16Bit(AX, BX);
+	instruction_stream.xor_Register16Bit_Register16Bit(AX, BX);
+	
+	let (encoded_bytes, _hints) = instruction_stream.finish();
+	
+	assert_eq!(&bytes_to_string(encoded_bytes), "66 89 D8 66 41 01 C8 66 29 D8 66 39 D8 66 31 D8", "Encoding of 16-bit operands was wrong");
+}
+
+#[test]
+pub fn automatically_sized_64_bit_immediate_moves()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.mov_Register64Bit_Immediate64Bit_auto(RAX, 0xFFFF_FFFFu64.into());
+	assert_eq!(&instruction_stream.hexdump(), "B8 FF FF FF FF", "Zero extending MOV r32, imm32 was not used");
+	
+	instruction_stream.mov_Register64Bit_Immediate64Bit_auto(R9, (-2i64).into());
+	assert_eq!(&instruction_stream.hexdump()[15 ..], "49 C7 C1 FE FF FF FF", "Sign extending MOV r/m64, imm32 was not used");
+	
+	instruction_stream.mov_Register64Bit_Immediate64Bit_auto(RCX, 0x1_0000_0000u64.into());
+	assert_eq!(&instruction_stream.hexdump()[36 ..], "48 B9 00 00 00 00 01 00 00 00", "MOV r64, imm64 was not used");
+}
+
+#[test]
+pub fn automatically_sized_arithmetic_immediates()
+{
+	use self::Register32Bit::ECX;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.add_Register64Bit_Immediate32Bit_auto(RAX, 1i32.into());
+	instruction_stream.add_Register64Bit_Immediate32Bit_auto(RAX, 0x100i32.into());
+	instruction_stream.add_Register64Bit_Immediate32Bit_auto(RCX, 0x100i32.into());
+	instruction_stream.cmp_Register32Bit_Immediate32Bit_auto(ECX, (-1i32).into());
+	instruction_stream.xor_Register64Bit_Immediate32Bit_auto(R10, (-129i32).into());
+	
+	assert_eq!(&instruction_stream.hexdump(), "48 83 C0 01 48 05 00 01 00 00 48 81 C1 00 01 00 00 83 F9 FF 49 81 F2 7F FF FF FF", "Encoding of automatically sized arithmetic immediates was wrong");
+}
+
+#[test]
+pub fn immediates_fit_in()
+{
+	use super::mnemonic_parameter_types::immediates::*;
+	
+	fn fits_in(immediate: impl Immediate + Copy) -> (bool, bool, bool)
+	{
+		(immediate.fits_in_i8(), immediate.fits_in_i16(), immediate.fits_in_i32())
+	}
+	
+	assert_eq!(fits_in(Immediate8Bit::Minimum), (true, true, true));
+	assert_eq!(fits_in(Immediate8Bit::Maximum), (true, true, true));
+	assert_eq!(fits_in(Immediate8Bit::from(0xFFu8)), (true, true, true));
+	
+	assert_eq!(fits_in(Immediate16Bit(-128)), (true, true, true));
+	assert_eq!(fits_in(Immediate16Bit(128)), (false, true, true));
+	assert_eq!(fits_in(Immediate16Bit::Minimum), (false, true, true));
+	
+	assert_eq!(fits_in(Immediate32Bit(127)), (true, true, true));
+	assert_eq!(fits_in(Immediate32Bit(-129)), (false, true, true));
+	assert_eq!(fits_in(Immediate32Bit(0x8000)), (false, false, true));
+	assert_eq!(fits_in(Immediate32Bit::Maximum), (false, false, true));
+	
+	assert_eq!(fits_in(Immediate64Bit(-1)), (true, true, true));
+	assert_eq!(fits_in(Immediate64Bit(-0x8000)), (false, true, true));
+	assert_eq!(fits_in(Immediate64Bit(i32::MIN as i64)), (false, false, true));
+	assert_eq!(fits_in(Immediate64Bit(0x8000_0000)), (false, false, false));
+	assert_eq!(fits_in(Immediate64Bit::Minimum), (false, false, false));
+}
+
+#[test]
+pub fn sign_extended_immediates()
+{
+	use super::mnemonic_parameter_types::immediates::*;
+	
+	assert_eq!(SignExtendedImmediate8Bit::new(-1).map(SignExtendedImmediate8Bit::value), Some(-1));
+	assert_eq!(SignExtendedImmediate8Bit::new(0x7F).map(SignExtendedImmediate8Bit::value), Some(0x7F));
+	assert_eq!(SignExtendedImmediate8Bit::new(0x80), None, "0x80 does not survive sign extension");
+	assert_eq!(SignExtendedImmediate8Bit::new(0xFF), None, "0xFF does not survive sign extension");
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.add_Register64Bit_Immediate8Bit(RAX, SignExtendedImmediate8Bit::new(-1).unwrap());
+	instruction_stream.add_Register64Bit_Immediate32Bit_auto(RAX, 0xFFi32.into());
+	instruction_stream.mov_Register8Bit_Immediate8Bit(Register8Bit::AL, 0xFFu8.into());
+	instruction_stream.pushq_Immediate8Bit((-1i8).into());
+	instruction_stream.imul_Register64Bit_Register64Bit_Immediate8Bit(RAX, RBX, (-2i8).into());
+	
+	assert_eq!(&instruction_stream.hexdump(), "48 83 C0 FF 48 05 FF 00 00 00 B0 FF 6A FF 48 6B C3 FE", "add rax, -1 and add rax, 0xFF were not different");
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn bit_test_and_bit_scan()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let function =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		instruction_stream.bt_Register64Bit_Immediate8Bit(RAX, 5u8.into());
+		instruction_stream.bsr_Register64Bit_Register64Bit(RCX, RDX);
+		assert_eq!(&instruction_stream.hexdump(), "48 0F BA E0 05 48 0F BD CA", "Encoding of bt rax, 5 and bsr rcx, rdx was wrong");
+		
+		let function: unsafe extern "C" fn(*const u64, u64) -> u8 = instruction_stream.binary_function_pointer();
+		instruction_stream.bt_Any64BitMemory_Register64Bit(Any64BitMemory::base_64(RDI), RSI);
+		instruction_stream.setc_Register8Bit(Register8Bit::AL);
+		instruction_stream.ret();
+		instruction_stream.finish();
+		function
+	};
+	
+	let bits = [0u64, 1 << 6];
+	assert_eq!(unsafe { function(bits.as_ptr(), 70) }, 1, "bit 70 of memory was not set");
+	assert_eq!(unsafe { function(bits.as_ptr(), 6) }, 0, "bit 6 of memory was set");
+}
+
+#[test]
+pub fn automatically_sized_shifts_and_rotates()
+{
+	use self::Register32Bit::EDX;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.shl_Register64Bit_Immediate8Bit_auto(RAX, 1u8.into());
+	instruction_stream.shl_Register64Bit_Immediate8Bit_auto(RAX, 4u8.into());
+	instruction_stream.ror_Register32Bit_Immediate8Bit_auto(EDX, 1u8.into());
+	instruction_stream.sar_Register64Bit_CL(R9);
+	instruction_stream.shld_Register64Bit_Register64Bit_Immediate8Bit(RAX, RCX, 3u8.into());
+	instruction_stream.shrd_Register64Bit_Register64Bit_CL(RAX, RCX);
+	
+	assert_eq!(&instruction_stream.hexdump(), "48 D1 E0 48 C1 E0 04 D1 CA 49 D3 F9 48 0F A4 C8 03 48 0F AD C8", "Encoding of shifts and rotates was wrong");
+}
+
+#[test]
+pub fn signed_multiplication_forms()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.imul_Register64Bit_Register64Bit_Immediate32Bit(RAX, RBX, 10i32.into());
+	instruction_stream.imul_Register64Bit_Register64Bit_Immediate8Bit(RAX, RBX, 3i8.into());
+	instruction_stream.imul_Register64Bit_Register64Bit(RAX, RBX);
+	instruction_stream.imul_Register64Bit(RBX);
+	assert_eq!(&instruction_stream.hexdump(), "48 69 C3 0A 00 00 00 48 6B C3 03 48 0F AF C3 48 F7 EB", "Encoding of IMUL forms was wrong");
+	
+	instruction_stream.imul_Register64Bit_Register64Bit_Immediate32Bit_auto(RAX, RBX, 3i32.into());
+	instruction_stream.imul_Register64Bit_Register64Bit_Immediate32Bit_auto(RAX, RBX, 1000i32.into());
+	assert_eq!(&instruction_stream.hexdump()[54 ..], "48 6B C3 03 48 69 C3 E8 03 00 00", "Encoding of automatically sized IMUL was wrong");
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn division_helpers()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let (signed_divide, unsigned_divide) =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let signed_divide: unsafe extern "C" fn(i64, i64) -> i64 = instruction_stream.binary_function_pointer();
+		instruction_stream.mov_Register64Bit_Register64Bit_r64_rm64(RAX, RDI);
+		instruction_stream.emit_signed_divide(RSI);
+		instruction_stream.ret();
+		
+		let unsigned_divide: unsafe extern "C" fn(u64, u64) -> u64 = instruction_stream.binary_function_pointer();
+		instruction_stream.mov_Register64Bit_Register64Bit_r64_rm64(RAX, RDI);
+		instruction_stream.emit_unsigned_divide(RSI);
+		instruction_stream.ret();
+		
+		assert_eq!(&instruction_stream.hexdump(), "48 89 F8 48 99 48 F7 FE C3 48 89 F8 31 D2 48 F7 F6 C3", "Encoding of division helpers was wrong");
+		
+		instruction_stream.finish();
+		(signed_divide, unsigned_divide)
+	};
+	
+	assert_eq!(unsafe { signed_divide(-42, 5) }, -8, "signed division was wrong");
+	assert_eq!(unsafe { unsigned_divide(u64::MAX, 2) }, u64::MAX / 2, "unsigned division was wrong");
+}
+
+#[test]
+pub fn jcc_with_every_condition_code()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	let backward = instruction_stream.create_and_attach_label();
+	let forward = instruction_stream.create_label();
+	for &condition_code in ConditionCode::All.iter()
+	{
+		instruction_stream.jcc_Label(condition_code, backward).expect("Could not emit short Jcc");
+		instruction_stream.jcc_Label_1(condition_code, forward);
+	}
+	instruction_stream.attach_label(forward);
+	
+	let (encoded_bytes, _hints) = instruction_stream.finish();
+	
+	assert_eq!(ConditionCode::NoParity, ConditionCode::ParityOdd);
+	for (index, instructions) in encoded_bytes.chunks(8).enumerate()
+	{
+		let short_displacement = -8 * (index as i8) - 2;
+		let near_displacement = 8 * (15 - index as u32);
+		let near_displacement = near_displacement.to_le_bytes();
+		assert_eq!(instructions, &[0x70 + index as u8, short_displacement as u8, 0x0F, 0x80 + index as u8, near_displacement[0], near_displacement[1], near_displacement[2], near_displacement[3]], "Encoding of condition code {:?} was wrong", ConditionCode::All[index]);
+	}
+}
+
+#[test]
+pub fn invert_condition_codes()
+{
+	use self::ConditionCode::*;
+	
+	let pairs =
+	[
+		(Overflow, NotOverflow),
+		(Below, AboveOrEqual),
+		(Equal, NotEqual),
+		(BelowOrEqual, Above),
+		(Sign, NotSign),
+		(ParityEven, ParityOdd),
+		(Less, GreaterOrEqual),
+		(LessOrEqual, Greater),
+	];
+	
+	for &(condition_code, opposite) in pairs.iter()
+	{
+		assert_eq!(condition_code.invert(), opposite, "{:?} was not inverted", condition_code);
+		assert_eq!(opposite.invert(), condition_code, "{:?} was not inverted", opposite);
+	}
+	
+	for &condition_code in ConditionCode::All.iter()
+	{
+		assert_eq!(condition_code.invert().invert(), condition_code, "{:?} did not round trip", condition_code);
+	}
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn compare_and_branch()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let minimum =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let minimum: unsafe extern "C" fn(i64, i64) -> i64 = instruction_stream.binary_function_pointer();
+		let second = instruction_stream.create_label();
+		let zero = instruction_stream.create_label();
+		instruction_stream.test_and_branch(RDI, RDI, ConditionCode::Equal, zero).expect("Could not emit TEST and Jcc");
+		instruction_stream.cmp_and_branch(RSI, RDI, ConditionCode::Less, second).expect("Could not emit CMP and Jcc");
+		instruction_stream.mov_Register64Bit_Register64Bit_r64_rm64(RAX, RDI);
+		instruction_stream.ret();
+		instruction_stream.attach_label(second);
+		instruction_stream.mov_Register64Bit_Register64Bit_r64_rm64(RAX, RSI);
+		instruction_stream.ret();
+		instruction_stream.attach_label(zero);
+		instruction_stream.zero_RAX();
+		instruction_stream.ret();
+		
+		let (encoded_bytes, _hints) = instruction_stream.finish();
+		assert_eq!(&bytes_to_string(&encoded_bytes[.. 10]), "48 85 FF 74 0D 48 39 FE 7C 04", "Encoding of TEST and CMP then Jcc was wrong");
+		
+		minimum
+	};
+	
+	assert_eq!(unsafe { minimum(3, -7) }, -7, "minimum was wrong");
+	assert_eq!(unsafe { minimum(3, 7) }, 3, "minimum was wrong");
+	assert_eq!(unsafe { minimum(0, -7) }, 0, "zero did not branch");
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn call_and_jmp_through_memory()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let (dispatch, forty_two) =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		instruction_stream.call_Any64BitMemory(Any64BitMemory::base_64_displacement(RAX, 0x10i32.into()));
+		instruction_stream.jmp_Any64BitMemory(Any64BitMemory::base_64(RBX));
+		assert_eq!(&instruction_stream.hexdump(), "FF 50 10 FF 23", "Encoding of CALL and JMP through memory was wrong");
+		
+		let dispatch: unsafe extern "C" fn(*const usize) -> u64 = instruction_stream.unary_function_pointer();
+		instruction_stream.call_Any64BitMemory(Any64BitMemory::base_64_displacement(RDI, 0x8i32.into()));
+		instruction_stream.add_Register64Bit_Immediate8Bit(RAX, 1i8.into());
+		instruction_stream.ret();
+		
+		let forty_two: unsafe extern "C" fn() -> u64 = instruction_stream.nullary_function_pointer();
+		instruction_stream.mov_Register64Bit_Immediate64Bit(RAX, 42u64.into());
+		instruction_stream.ret();
+		
+		instruction_stream.finish();
+		(dispatch, forty_two)
+	};
+	
+	let vtable = [0, forty_two as usize];
+	assert_eq!(unsafe { dispatch(vtable.as_ptr()) }, 43, "CALL through vtable slot was wrong");
+}
+
+#[test]
+pub fn exchange_and_add()
+{
+	use self::Register32Bit::*;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.lock();
+	instruction_stream.xadd_Any64BitMemory_Register64Bit(Any64BitMemory::base_64(RDI), RAX);
+	instruction_stream.xadd_Register32Bit_Register32Bit(ECX, EDX);
+	instruction_stream.xchg_Any64BitMemory_Register64Bit(Any64BitMemory::base_64(RDI), RSI);
+	instruction_stream.xchg_Register64Bit_Register64Bit_auto(RCX, RAX);
+	instruction_stream.xchg_Register64Bit_Register64Bit_auto(RCX, RDX);
+	instruction_stream.xchg_Register32Bit_Register32Bit_auto(EAX, R9D);
+	instruction_stream.xchg_Register32Bit_Register32Bit_auto(EAX, EAX);
+	instruction_stream.xchg_EAX_Register32Bit(EAX);
+	
+	assert_eq!(&instruction_stream.hexdump(), "F0 48 0F C1 07 0F C1 D1 48 87 37 48 91 48 87 D1 41 91 87 C0 87 C0", "Encoding of XADD and XCHG was wrong");
+}
+
+#[test]
+pub fn gather_with_vector_memory_operand()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.vgatherdps_YMM_Any32BitMemory_YMM(YMMRegister::YMM0, VectorMemoryOperand::base_64_ymm_index(RAX, YMMRegister::YMM1, IndexScale::x4, 0i32.into()).into(), YMMRegister::YMM2);
+	instruction_stream.vgatherdps_YMM_Any32BitMemory_YMM(YMMRegister::YMM0, VectorMemoryOperand::ymm_index(YMMRegister::YMM1, IndexScale::x4, 0x10i32.into()).into(), YMMRegister::YMM2);
+	instruction_stream.vpgatherqq_XMMRegister_Any64BitMemory_XMMRegister(XMMRegister::XMM3, VectorMemoryOperand::base_64_xmm_index(R8, XMMRegister::XMM12, IndexScale::x8, 0i32.into()).into(), XMMRegister::XMM4);
+	
+	assert_eq!(instruction_stream.hexdump(), "C4 E2 6D 92 04 88 C4 E2 6D 92 04 8D 10 00 00 00 C4 82 D9 91 1C E0", "Encoding of gathers was wrong");
+}
+
+#[test]
+pub fn fused_multiply_add()
+{
+	use self::XMMRegister::*;
+	use self::YMMRegister::*;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.vfmadd231pd_YMM_YMM_YMM(YMM0, YMM1, YMM2);
+	instruction_stream.vfmadd132ps_XMMRegister_XMMRegister_XMMRegister(XMM0, XMM1, XMM2);
+	instruction_stream.vfmadd213ps_YMM_YMM_YMM(YMM8, YMM9, YMM10);
+	instruction_stream.vfmadd231ss_XMMRegister_XMMRegister_XMMRegister(XMM5, XMM4, XMM3);
+	instruction_stream.vfmadd213sd_XMMRegister_XMMRegister_Any64BitMemory(XMM0, XMM1, Any64BitMemory::base_64(RAX));
+	instruction_stream.vfnmadd231ps_YMM_YMM_YMM(YMM0, YMM1, YMM2);
+	instruction_stream.vfmsub132pd_XMMRegister_XMMRegister_XMMRegister(XMM0, XMM1, XMM2);
+	instruction_stream.vfnmsub213sd_XMMRegister_XMMRegister_XMMRegister(XMM0, XMM1, XMM2);
+	
+	assert_eq!(instruction_stream.hexdump(), "C4 E2 F5 B8 C2 C4 E2 71 98 C2 C4 42 35 A8 C2 C4 E2 59 B9 EB C4 E2 F1 A9 00 C4 E2 75 BC C2 C4 E2 F1 9A C2 C4 E2 F1 AF C2", "Encoding of fused multiply-adds was wrong");
+}
+
+#[test]
+pub fn horizontal_and_permute()
+{
+	use self::XMMRegister::*;
+	use self::YMMRegister::*;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.pshufd_XMMRegister_XMMRegister_Immediate8Bit(XMM0, XMM1, 0x1Bu8.into());
+	assert_eq!(instruction_stream.hexdump(), "66 0F 70 C1 1B", "Encoding of PSHUFD was wrong");
+	
+	instruction_stream.haddps_XMMRegister_XMMRegister(XMM0, XMM1);
+	instruction_stream.haddpd_XMMRegister_XMMRegister(XMM8, XMM9);
+	instruction_stream.hsubps_XMMRegister_XMMRegister(XMM3, XMM2);
+	instruction_stream.vpermilps_YMM_YMM_Immediate8Bit(YMM0, YMM1, 0xB1u8.into());
+	instruction_stream.vperm2f128_YMM_YMM_YMM_Immediate8Bit(YMM0, YMM1, YMM2, 0x01u8.into());
+	instruction_stream.vshufps_XMMRegister_XMMRegister_XMMRegister_Immediate8Bit(XMM0, XMM1, XMM2, 0x44u8.into());
+	instruction_stream.vpshufd_YMM_YMM_Immediate8Bit(YMM0, YMM1, 0x4Eu8.into());
+	instruction_stream.vpshufb_XMMRegister_XMMRegister_XMMRegister(XMM0, XMM1, XMM2);
+	
+	assert_eq!(instruction_stream.hexdump(), "66 0F 70 C1 1B F2 0F 7C C1 66 45 0F 7C C1 F2 0F 7D DA C4 E3 7D 04 C1 B1 C4 E3 75 06 C2 01 C5 F0 C6 C2 44 C5 FD 70 C1 4E C4 E2 71 00 C2", "Encoding of horizontal and permute instructions was wrong");
+}
+
+#[test]
+pub fn packed_integer_arithmetic()
+{
+	use self::XMMRegister::*;
+	use self::YMMRegister::*;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.paddd_XMMRegister_XMMRegister(XMM0, XMM1);
+	assert_eq!(instruction_stream.hexdump(), "66 0F FE C1", "Encoding of PADDD was wrong");
+	
+	instruction_stream.paddb_XMMRegister_XMMRegister(XMM0, XMM1);
+	instruction_stream.paddw_XMMRegister_XMMRegister(XMM0, XMM1);
+	instruction_stream.paddq_XMMRegister_XMMRegister(XMM0, XMM9);
+	instruction_stream.psubb_XMMRegister_XMMRegister(XMM8, XMM1);
+	instruction_stream.psubq_XMMRegister_XMMRegister(XMM0, XMM1);
+	instruction_stream.pmullw_XMMRegister_XMMRegister(XMM0, XMM1);
+	instruction_stream.pmulld_XMMRegister_XMMRegister(XMM0, XMM1);
+	instruction_stream.pcmpeqb_XMMRegister_XMMRegister(XMM0, XMM1);
+	instruction_stream.pcmpgtd_XMMRegister_XMMRegister(XMM0, XMM1);
+	instruction_stream.pand_XMMRegister_XMMRegister(XMM0, XMM1);
+	instruction_stream.por_XMMRegister_XMMRegister(XMM0, XMM1);
+	instruction_stream.pxor_XMMRegister_XMMRegister(XMM0, XMM1);
+	instruction_stream.pandn_XMMRegister_XMMRegister(XMM0, XMM1);
+	instruction_stream.vpaddd_YMM_YMM_YMM(YMM0, YMM1, YMM2);
+	instruction_stream.vpmulld_XMMRegister_XMMRegister_XMMRegister(XMM0, XMM1, XMM2);
+	instruction_stream.vpcmpgtd_YMM_YMM_YMM(YMM0, YMM1, YMM2);
+	instruction_stream.vpxor_YMM_YMM_YMM(YMM10, YMM11, YMM12);
+	
+	assert_eq!(instruction_stream.hexdump(), "66 0F FE C1 66 0F FC C1 66 0F FD C1 66 41 0F D4 C1 66 44 0F F8 C1 66 0F FB C1 66 0F D5 C1 66 0F 38 40 C1 66 0F 74 C1 66 0F 66 C1 66 0F DB C1 66 0F EB C1 66 0F EF C1 66 0F DF C1 C5 F5 FE C2 C4 E2 71 40 C2 C5 F5 66 C2 C4 41 25 EF D4", "Encoding of packed integer arithmetic was wrong");
+}
+
+#[test]
+pub fn conversions()
+{
+	use self::XMMRegister::*;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.cvtsi2sd_XMMRegister_Register64Bit(XMM0, RAX);
+	assert_eq!(instruction_stream.hexdump(), "F2 48 0F 2A C0", "Encoding of CVTSI2SD was wrong");
+	
+	instruction_stream.cvtsi2sd_XMMRegister_Register32Bit(XMM0, Register32Bit::EAX);
+	instruction_stream.cvtsi2sd_XMMRegister_Any64BitMemory(XMM0, Any64BitMemory::base_64(RAX));
+	instruction_stream.cvtsi2sd_XMMRegister_Any32BitMemory(XMM0, Any32BitMemory::base_64(RAX));
+	instruction_stream.cvtsd2si_Register64Bit_XMMRegister(RAX, XMM1);
+	instruction_stream.cvtsd2si_Register32Bit_XMMRegister(Register32Bit::R8D, XMM9);
+	instruction_stream.cvtdq2ps_XMMRegister_XMMRegister(XMM0, XMM1);
+	instruction_stream.cvtps2dq_XMMRegister_XMMRegister(XMM0, XMM1);
+	instruction_stream.cvttps2dq_XMMRegister_XMMRegister(XMM0, XMM1);
+	instruction_stream.cvtss2sd_XMMRegister_XMMRegister(XMM0, XMM1);
+	instruction_stream.cvtsd2ss_XMMRegister_XMMRegister(XMM0, XMM1);
+	
+	assert_eq!(instruction_stream.hexdump(), "F2 48 0F 2A C0 F2 0F 2A C0 F2 48 0F 2A 00 F2 0F 2A 00 F2 48 0F 2D C1 F2 45 0F 2D C1 0F 5B C1 66 0F 5B C1 F3 0F 5B C1 F3 0F 5A C1 F2 0F 5A C1", "Encoding of conversions was wrong");
+}
+
+#[test]
+pub fn display_memory_operands()
+{
+	assert_eq!(format!("{}", MemoryOperand::base_64_index_64_scale_displacement(RAX, RCX, IndexScale::x4, 0x10i32.into())), "[rax + rcx*4 + 0x10]");
+	assert_eq!(format!("{}", MemoryOperand::segment_displacement(SegmentRegister::FS, 0x20i32.into())), "fs:[0x20]");
+	assert_eq!(format!("{}", MemoryOperand::relative_instruction_pointer_relative_displacement(0x10i32.into())), "[rip + 0x10]");
+	assert_eq!(format!("{}", MemoryOperand::base_64_displacement(RBP, (-8i32).into())), "[rbp - 0x8]");
+	assert_eq!(format!("{}", MemoryOperand::index_64_scale(R12, IndexScale::x8)), "[r12*8]");
+	assert_eq!(format!("{}", Any32BitMemory::base_32(Register32Bit::R9D)), "[r9d]");
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn pause_in_spin_loop()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let count_down =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let count_down: unsafe extern "C" fn(*mut u32) = instruction_stream.unary_function_pointer();
+		let spin = instruction_stream.create_and_attach_label();
+		instruction_stream.pause();
+		instruction_stream.dec_Any32BitMemory(Any32BitMemory::base_64(RDI));
+		instruction_stream.jne_Label(spin).expect("Backwards jump is short");
+		instruction_stream.ret();
+		
+		assert_eq!(instruction_stream.hexdump(), "F3 90 FF 0F 75 FA C3", "Encoding of spin loop was wrong");
+		
+		instruction_stream.finish();
+		count_down
+	};
+	
+	let mut counter = 1000u32;
+	unsafe { count_down(&mut counter) };
+	assert_eq!(counter, 0);
+}
+
+#[test]
+pub fn hardware_random_numbers()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.rdrand_Register64Bit(RAX);
+	instruction_stream.rdrand_Register32Bit(Register32Bit::R9D);
+	instruction_stream.rdrand_Register16Bit(Register16Bit::CX);
+	instruction_stream.rdseed_Register64Bit(RAX);
+	instruction_stream.rdseed_Register32Bit(Register32Bit::R9D);
+	instruction_stream.rdseed_Register16Bit(Register16Bit::CX);
+	
+	assert_eq!(instruction_stream.hexdump(), "48 0F C7 F0 41 0F C7 F1 66 0F C7 F1 48 0F C7 F8 41 0F C7 F9 66 0F C7 F9", "Encoding of RDRAND and RDSEED was wrong");
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn function_pointers_at_labels()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let (double, triple) =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let double_label = instruction_stream.create_and_attach_label();
+		instruction_stream.lea_Register64Bit_Any64BitMemory(RAX, Any64BitMemory::base_64_index_64(RDI, RDI));
+		instruction_stream.ret();
+		
+		let triple_label = instruction_stream.create_and_attach_label();
+		instruction_stream.lea_Register64Bit_Any64BitMemory(RAX, Any64BitMemory::base_64_index_64_scale(RDI, RDI, IndexScale::x2));
+		instruction_stream.ret();
+		
+		let double: unsafe extern "C" fn(u64) -> u64 = instruction_stream.unary_function_pointer_at(double_label);
+		let triple: unsafe extern "C" fn(u64) -> u64 = instruction_stream.unary_function_pointer_at(triple_label);
+		
+		instruction_stream.finish();
+		(double, triple)
+	};
+	
+	assert_eq!(unsafe { double(7) }, 14);
+	assert_eq!(unsafe { triple(7) }, 21);
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+#[should_panic(expected = "has not been attached")]
+pub fn function_pointer_at_unattached_label_panics()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	let label = instruction_stream.create_label();
+	let _: unsafe extern "C" fn() -> u64 = instruction_stream.nullary_function_pointer_at(label);
+}
+
+#[test]
+pub fn try_emit_bytes_beyond_capacity()
+{
+	let mut buffer = [0u8; 16];
+	let mut instruction_stream = InstructionStream::new_in_buffer(&mut buffer, &InstructionStreamHints::default());
+	
+	assert_eq!(instruction_stream.try_emit_bytes(&[0x90; 12]), Ok(()));
+	assert_eq!(instruction_stream.try_emit_bytes(&[0xCC; 5]), Err(CapacityExceededError { capacity: 16, length: 5 }));
+	assert_eq!(instruction_stream.try_emit_bytes(&[0xC3; 4]), Ok(()));
+	assert_eq!(instruction_stream.hexdump(), "90 90 90 90 90 90 90 90 90 90 90 90 C3 C3 C3 C3");
+}
+
+#[test]
+#[should_panic(expected = "instruction stream exceeded mapped capacity of 16 bytes")]
+pub fn emit_bytes_beyond_capacity_panics()
+{
+	let mut buffer = [0u8; 16];
+	let mut instruction_stream = InstructionStream::new_in_buffer(&mut buffer, &InstructionStreamHints::default());
+	
+	instruction_stream.emit_bytes(&[0x90; 17]);
+}
+
+#[test]
+pub fn map_sizing_and_growth_policy()
+{
+	assert_eq!(ExecutableAnonymousMemoryMap::new(100, false, true).expect("Could not anonymously mmap").length(), 4096);
+	assert_eq!(ExecutableAnonymousMemoryMap::new(5000, false, true).expect("Could not anonymously mmap").length(), 8192);
+	
+	assert_eq!(GrowthPolicy::default().increment(8192), 8192);
+	assert_eq!(GrowthPolicy::FixedIncrement(0).increment(8192), 4096);
+	assert_eq!(GrowthPolicy::FixedIncrement(4096).increment(8192), 4096);
+	assert_eq!(GrowthPolicy::FixedIncrement(5000).increment(8192), 8192);
+	
+	let mut hints = InstructionStreamHints { number_of_bytes: 5000, ..InstructionStreamHints::default() };
+	hints.adjust();
+	assert_eq!(hints.number_of_bytes, 8192);
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	map.set_growth_policy(GrowthPolicy::FixedIncrement(4096));
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	instruction_stream.emit_bytes(&[0x90; 100]);
+	instruction_stream.ret();
+	let (_, hints) = instruction_stream.finish();
+	assert_eq!(hints.number_of_bytes, 101);
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn reset_and_reuse()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let answer =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let never_attached = instruction_stream.create_label();
+		instruction_stream.jmp_Label_1(never_attached);
+		instruction_stream.emit_label(never_attached);
+		instruction_stream.push_Register64Bit_r64(RBX);
+		
+		instruction_stream.reset();
+		assert_eq!(instruction_stream.instruction_pointer(), instruction_stream.start_instruction_pointer());
+		assert_eq!(instruction_stream.current_stack_delta(), 0);
+		
+		let answer: unsafe extern "C" fn() -> u64 = instruction_stream.nullary_function_pointer();
+		let skip = instruction_stream.create_label();
+		assert_eq!(skip, never_attached, "Label indices restart after a reset");
+		instruction_stream.mov_Register64Bit_Immediate64Bit(RAX, 42u64.into());
+		instruction_stream.jmp_Label(skip).expect("Forward jump is short");
+		instruction_stream.mov_Register64Bit_Immediate64Bit(RAX, 0u64.into());
+		instruction_stream.attach_label(skip);
+		instruction_stream.ret();
+		
+		instruction_stream.finish();
+		answer
+	};
+	
+	assert_eq!(unsafe { answer() }, 42);
+}
+
+#[test]
+pub fn label_count_and_ownership()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	assert_eq!(instruction_stream.label_count(), 0);
+	let first = instruction_stream.create_label();
+	let second = instruction_stream.create_and_attach_label();
+	assert_eq!(instruction_stream.label_count(), 2);
+	assert!(instruction_stream.owns_label(first));
+	assert!(instruction_stream.owns_label(second));
+	
+	let mut other_map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut other_instruction_stream = other_map.instruction_stream(&InstructionStreamHints::default());
+	other_instruction_stream.create_label();
+	other_instruction_stream.create_label();
+	let foreign = other_instruction_stream.create_label();
+	assert!(!instruction_stream.owns_label(foreign));
+	
+	instruction_stream.reset();
+	assert_eq!(instruction_stream.label_count(), 0);
+	assert!(!instruction_stream.owns_label(first));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "was not created by this instruction stream")]
+pub fn attaching_foreign_label_panics()
+{
+	let mut other_map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut other_instruction_stream = other_map.instruction_stream(&InstructionStreamHints::default());
+	other_instruction_stream.create_label();
+	let foreign = other_instruction_stream.create_label();
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	instruction_stream.attach_label(foreign);
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn several_labels_at_the_same_location()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let count_down =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let count_down: unsafe extern "C" fn(u64) -> u64 = instruction_stream.unary_function_pointer();
+		let entry = instruction_stream.create_label();
+		let loop_header = instruction_stream.create_label();
+		let done = instruction_stream.create_label();
+		
+		instruction_stream.xor_Register32Bit_Register32Bit(Register32Bit::EAX, Register32Bit::EAX);
+		instruction_stream.jmp_Label(entry).expect("Forward jump is short");
+		instruction_stream.attach_label(entry);
+		instruction_stream.attach_label(loop_header);
+		instruction_stream.test_Register64Bit_Register64Bit(RDI, RDI);
+		instruction_stream.je_Label(done).expect("Forward jump is short");
+		instruction_stream.add_Register64Bit_Immediate8Bit(RAX, 3i8.into());
+		instruction_stream.dec_Register64Bit(RDI);
+		instruction_stream.jmp_Label(loop_header).expect("Backwards jump is short");
+		instruction_stream.attach_label(done);
+		instruction_stream.ret();
+		
+		assert_eq!(instruction_stream.target_instruction_pointer(entry), instruction_stream.target_instruction_pointer(loop_header));
+		
+		instruction_stream.finish();
+		count_down
+	};
+	
+	assert_eq!(unsafe { count_down(5) }, 15);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "has already been used to label")]
+pub fn attaching_a_label_twice_panics()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	let label = instruction_stream.create_and_attach_label();
+	instruction_stream.nop();
+	instruction_stream.attach_label(label);
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn emit_data_referenced_rip_relatively()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let (look_up, bytes) =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let look_up: unsafe extern "C" fn(u64) -> u32 = instruction_stream.unary_function_pointer();
+		let table = instruction_stream.create_label();
+		instruction_stream.lea_Register64Bit_Label(RSI, table);
+		instruction_stream.movzx_Register32Bit_Any8BitMemory(Register32Bit::EAX, Any8BitMemory::base_64_index_64(RSI, RDI));
+		instruction_stream.ret();
+		
+		let (primes, length) = instruction_stream.emit_data(&[2, 3, 5, 7, 11]);
+		assert_eq!(length, 5);
+		assert_eq!(instruction_stream.target_instruction_pointer(primes) - instruction_stream.start_instruction_pointer(), 12);
+		instruction_stream.attach_label(table);
+		instruction_stream.emit_data(&[0x10, 0x20, 0x30]);
+		
+		let (bytes, _) = instruction_stream.finish();
+		(look_up, bytes_to_string(bytes))
+	};
+	
+	assert_eq!(bytes, "48 8D 35 0A 00 00 00 0F B6 04 3E C3 02 03 05 07 0B 10 20 30");
+	assert_eq!(unsafe { look_up(0) }, 0x10);
+	assert_eq!(unsafe { look_up(2) }, 0x30);
+}
+
+#[test]
+pub fn alignment_reports_padding()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.emit_bytes(&[0x90; 5]);
+	assert_eq!(instruction_stream.padding_for_alignment(32), 27);
+	assert_eq!(instruction_stream.emit_alignment(32), 27);
+	assert_eq!(instruction_stream.instruction_pointer() % 32, 0);
+	assert_eq!(instruction_stream.padding_for_alignment(32), 0);
+	assert_eq!(instruction_stream.emit_alignment(32), 0);
+}
+
+#[test]
+pub fn nop_of_length()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	for length in 1 ..= 15
+	{
+		let start = instruction_stream.instruction_pointer();
+		instruction_stream.emit_nop_of_length(length);
+		assert_eq!(instruction_stream.instruction_pointer() - start, length, "NOP was not {} bytes long", length);
+	}
+	
+	let hexdump = instruction_stream.hexdump();
+	let bytes: Vec<&str> = hexdump.split(' ').collect();
+	let mut offset = 0;
+	let mut take = |length: usize|
+	{
+		let nop = bytes[offset .. offset + length].join(" ");
+		offset += length;
+		nop
+	};
+	assert_eq!(take(1), "90");
+	assert_eq!(take(2), "66 90");
+	assert_eq!(take(3), "0F 1F 00");
+	assert_eq!(take(4), "0F 1F 40 00");
+	assert_eq!(take(5), "0F 1F 44 00 00");
+	assert_eq!(take(6), "66 0F 1F 44 00 00");
+	assert_eq!(take(7), "0F 1F 80 00 00 00 00");
+	assert_eq!(take(8), "0F 1F 84 00 00 00 00 00");
+	assert_eq!(take(9), "66 0F 1F 84 00 00 00 00 00");
+	assert_eq!(take(10), "66 2E 0F 1F 84 00 00 00 00 00");
+	assert_eq!(take(11), "66 66 2E 0F 1F 84 00 00 00 00 00");
+	assert_eq!(take(12), "66 66 66 2E 0F 1F 84 00 00 00 00 00");
+	assert_eq!(take(13), "66 66 66 66 2E 0F 1F 84 00 00 00 00 00");
+	assert_eq!(take(14), "66 66 66 66 66 2E 0F 1F 84 00 00 00 00 00");
+	assert_eq!(take(15), "66 66 66 66 66 66 2E 0F 1F 84 00 00 00 00 00");
+}
+
+#[test]
+#[should_panic(expected = "A single NOP can not be 16 bytes long")]
+pub fn nop_of_length_16_panics()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.emit_nop_of_length(16);
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn patch_points()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let answer =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let answer: unsafe extern "C" fn() -> u64 = instruction_stream.nullary_function_pointer();
+		instruction_stream.mov_Register64Bit_Immediate64Bit(RAX, 1u64.into());
+		let patch_point = instruction_stream.reserve_patch_point(8);
+		assert_eq!(patch_point.length(), 8);
+		instruction_stream.ret();
+		assert_eq!(&instruction_stream.hexdump()[10 * 3 ..], "0F 1F 84 00 00 00 00 00 C3");
+		
+		// add rax, 41
+		instruction_stream.fill_patch_point(patch_point, &[0x48, 0x83, 0xC0, 0x29]);
+		assert_eq!(&instruction_stream.hexdump()[10 * 3 ..], "48 83 C0 29 0F 1F 40 00 C3");
+		
+		instruction_stream.finish();
+		answer
+	};
+	
+	assert_eq!(unsafe { answer() }, 42);
+}
+
+#[test]
+#[should_panic(expected = "5 bytes do not fit in a patch point of 4 bytes")]
+pub fn overfilling_patch_point_panics()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	let patch_point = instruction_stream.reserve_patch_point(4);
+	instruction_stream.fill_patch_point(patch_point, &[0x90; 5]);
+}
+
+#[test]
+pub fn sign_and_zero_extension()
+{
+	use self::Register8Bit::*;
+	use self::Register16Bit::*;
+	use self::Register32Bit::*;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.movzx_Register32Bit_Any8BitMemory(EAX, Any8BitMemory::base_64(RDI));
+	assert_eq!(instruction_stream.hexdump(), "0F B6 07", "Encoding of MOVZX EAX, BYTE PTR [RDI] was wrong");
+	
+	instruction_stream.movzx_Register16Bit_Any8BitMemory(AX, Any8BitMemory::base_64(RDI));
+	instruction_stream.movzx_Register64Bit_Any8BitMemory(R8, Any8BitMemory::base_64(RDI));
+	instruction_stream.movzx_Register32Bit_Any16BitMemory(EAX, Any16BitMemory::base_64(RDI));
+	instruction_stream.movzx_Register64Bit_Register16Bit(RAX, CX);
+	instruction_stream.movzx_Register32Bit_Register8Bit(EAX, SIL);
+	instruction_stream.movzx_Register32Bit_RegisterHigh8BitsOf16Bits(ECX, RegisterHigh8BitsOf16Bits::AH);
+	instruction_stream.movsx_Register32Bit_Any8BitMemory(EAX, Any8BitMemory::base_64(RDI));
+	instruction_stream.movsx_Register16Bit_Register8Bit(AX, CL);
+	instruction_stream.movsx_Register64Bit_Register8Bit(RAX, CL);
+	instruction_stream.movsx_Register32Bit_Any16BitMemory(EAX, Any16BitMemory::base_64(RDI));
+	instruction_stream.movsx_Register64Bit_Register16Bit(R9, CX);
+	instruction_stream.movsxd_Register64Bit_Any32BitMemory(RAX, Any32BitMemory::base_64(RDI));
+	instruction_stream.movsxd_Register64Bit_Register32Bit(RAX, ECX);
+	instruction_stream.movsxd_Register64Bit_Register32Bit(R11, R10D);
+	
+	assert_eq!(instruction_stream.hexdump(), "0F B6 07 66 0F B6 07 4C 0F B6 07 0F B7 07 48 0F B7 C1 40 0F B6 C6 0F B6 CC 0F BE 07 66 0F BE C1 48 0F BE C1 0F BF 07 4C 0F BF C9 48 63 07 48 63 C1 4D 63 DA", "Encoding of sign and zero extensions was wrong");
+}
+
+#[test]
+pub fn special_base_and_index_registers()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.mov_Register64Bit_Any64BitMemory(RAX, Any64BitMemory::base_64(RSP));
+	instruction_stream.mov_Register64Bit_Any64BitMemory(RAX, Any64BitMemory::base_64(RBP));
+	instruction_stream.mov_Register64Bit_Any64BitMemory(RAX, Any64BitMemory::base_64(R13));
+	instruction_stream.mov_Register64Bit_Any64BitMemory(RAX, Any64BitMemory::base_64(R12));
+	instruction_stream.mov_Register64Bit_Any64BitMemory(RAX, Any64BitMemory::base_64_displacement(RSP, 8i32.into()));
+	instruction_stream.mov_Register64Bit_Any64BitMemory(RAX, Any64BitMemory::base_64_index_64_scale(RBP, RCX, IndexScale::x2));
+	instruction_stream.mov_Register64Bit_Any64BitMemory(RAX, Any64BitMemory::base_64_index_64(R13, R12));
+	instruction_stream.mov_Register64Bit_Any64BitMemory(RAX, Any64BitMemory::index_64_scale(RCX, IndexScale::x4));
+	
+	assert_eq!(instruction_stream.hexdump(), "48 8B 04 24 48 8B 45 00 49 8B 45 00 49 8B 04 24 48 8B 44 24 08 48 8B 44 4D 00 4B 8B 44 25 00 48 8B 04 8D 00 00 00 00", "Encoding of RSP, RBP, R12 and R13 special cases was wrong");
+}
+
+#[test]
+#[should_panic(expected = "RSP (or ESP) can not be used as an index register")]
+pub fn rsp_as_index_register_panics()
+{
+	Any64BitMemory::base_64_index_64(RAX, RSP);
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn absolute_and_relative_instruction_pointer_displacements()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let (load, bytes) =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		instruction_stream.mov_Register64Bit_Any64BitMemory(RAX, Any64BitMemory::displacement(0x1000i32.into()));
+		instruction_stream.mov_Register64Bit_Any64BitMemory(RAX, Any64BitMemory::relative_instruction_pointer_relative_displacement(0x1000i32.into()));
+		
+		let load: unsafe extern "C" fn() -> u64 = instruction_stream.nullary_function_pointer();
+		let data = instruction_stream.create_label();
+		instruction_stream.mov_Register64Bit_Label(RAX, data);
+		instruction_stream.ret();
+		instruction_stream.attach_label(data);
+		instruction_stream.emit_data(&[0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88]);
+		
+		let (bytes, _) = instruction_stream.finish();
+		(load, bytes_to_string(bytes))
+	};
+	
+	assert_eq!(bytes, "48 8B 04 25 00 10 00 00 48 8B 05 00 10 00 00 48 8B 05 01 00 00 00 C3 11 22 33 44 55 66 77 88", "[disp32] must be absolute (with a SIB byte) and [rip + disp32] must not");
+	assert_eq!(unsafe { load() }, 0x8877665544332211);
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn lea_with_32_bit_destination()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let (truncating_add, bytes) =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let truncating_add: unsafe extern "C" fn(u64, u64) -> u64 = instruction_stream.binary_function_pointer();
+		instruction_stream.mov_Register64Bit_Register64Bit_rm64_r64(RAX, RDI);
+		instruction_stream.lea_Register32Bit_Any64BitMemory(Register32Bit::EAX, Any64BitMemory::base_64_index_64_scale(RDI, RSI, IndexScale::x2));
+		instruction_stream.ret();
+		
+		instruction_stream.lea_Register32Bit_Any32BitMemory(Register32Bit::EAX, Any32BitMemory::base_32_index_32_scale(Register32Bit::EBX, Register32Bit::ECX, IndexScale::x2));
+		instruction_stream.lea_Register64Bit_Any64BitMemory(RAX, Any64BitMemory::base_64_index_64_scale(RBX, RCX, IndexScale::x2));
+		instruction_stream.lea_Register32Bit_Any64BitMemory(Register32Bit::R10D, Any64BitMemory::base_64_index_64_scale(R8, R9, IndexScale::x2));
+		
+		let (bytes, _) = instruction_stream.finish();
+		(truncating_add, bytes_to_string(bytes))
+	};
+	
+	assert_eq!(bytes, "48 8B C7 8D 04 77 C3 67 8D 04 4B 48 8D 04 4B 47 8D 14 48");
+	assert_eq!(unsafe { truncating_add(0xFFFF_FFFF_FFFF_FFFF, 1) }, 1, "upper 32 bits were not zeroed");
+	assert_eq!(unsafe { truncating_add(0x1_0000_0010, 0x8) }, 0x20);
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn emit_all_instructions()
+{
+	use super::Inst::*;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let (multiply_add, bytes) =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let multiply_add: unsafe extern "C" fn(u64, u64) -> u64 = instruction_stream.binary_function_pointer();
+		let loop_ = instruction_stream.create_label();
+		instruction_stream.emit_all
+		(&[
+			MovRegReg(RAX, RDI),
+			AttachLabel(loop_),
+			AddRegReg(RAX, RSI),
+			SubRegImm(RDI, 1),
+			CmpRegImm(RDI, 0),
+			JccLabel(ConditionCode::NotEqual, loop_),
+			Ret,
+		]).expect("emit_all failed");
+		
+		let (bytes, _) = instruction_stream.finish();
+		(multiply_add, bytes_to_string(bytes))
+	};
+	
+	assert_eq!(bytes, "48 8B C7 48 01 F0 48 81 EF 01 00 00 00 48 81 FF 00 00 00 00 75 ED C3");
+	assert_eq!(unsafe { multiply_add(3, 5) }, 18);
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn instruction_pointer_arithmetic()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let (start, offset) =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let start = instruction_stream.start_instruction_pointer();
+		instruction_stream.mov_Register32Bit_Immediate32Bit(Register32Bit::EAX, 1i32.into());
+		instruction_stream.ret();
+		let second = instruction_stream.create_and_attach_label();
+		instruction_stream.mov_Register32Bit_Immediate32Bit(Register32Bit::EAX, 2i32.into());
+		instruction_stream.ret();
+		
+		let offset = instruction_stream.target_instruction_pointer(second).offset_from(start);
+		assert_eq!(start.offset_from(instruction_stream.target_instruction_pointer(second)), -offset);
+		
+		instruction_stream.finish();
+		(start, offset)
+	};
+	
+	assert_eq!(offset, 6);
+	let pointer = start.as_pointer().wrapping_offset(offset);
+	let second = InstructionPointer::from_pointer(pointer);
+	assert_eq!(second.as_usize(), start + 6);
+	
+	let function: unsafe extern "C" fn() -> u32 = unsafe { ::std::mem::transmute::<*const u8, unsafe extern "C" fn() -> u32>(second.as_pointer()) };
+	assert_eq!(unsafe { function() }, 2);
+}
+
+#[test]
+pub fn little_endian_emission()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.emit_word(0x0102);
+	instruction_stream.emit_double_word(0x01020304);
+	instruction_stream.emit_quad_word(0x0102030405060708);
+	instruction_stream.emit_double_quad_word(0x0102030405060708090A0B0C0D0E0F10);
+	
+	assert_eq!(instruction_stream.hexdump(), "02 01 04 03 02 01 08 07 06 05 04 03 02 01 10 0F 0E 0D 0C 0B 0A 09 08 07 06 05 04 03 02 01");
+}
+
+#[test]
+pub fn last_instruction_length()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	assert_eq!(instruction_stream.last_instruction_length(), 0);
+	
+	let start = instruction_stream.create_and_attach_label();
+	instruction_stream.nop();
+	assert_eq!(instruction_stream.last_instruction_length(), 1);
+	instruction_stream.add_Register64Bit_Register64Bit(RAX, RSI);
+	assert_eq!(instruction_stream.last_instruction_length(), 3);
+	instruction_stream.mov_Register64Bit_Immediate64Bit(R8, 0x1122334455667788i64.into());
+	assert_eq!(instruction_stream.last_instruction_length(), 10);
+	instruction_stream.emit_bytes(&[0xCC; 200]);
+	assert_eq!(instruction_stream.last_instruction_length(), 210, "bytes emitted by emit_bytes() are not an instruction");
+	
+	assert!(instruction_stream.jmp_Label(start).is_err());
+	assert_eq!(instruction_stream.last_instruction_length(), 0);
+	instruction_stream.jmp_Label_1(start);
+	assert_eq!(instruction_stream.last_instruction_length(), 5);
+}
+
+#[test]
+pub fn checkpoint_and_rollback()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	let start = instruction_stream.create_and_attach_label();
+	let end = instruction_stream.create_label();
+	instruction_stream.nop();
+	
+	let outer = instruction_stream.checkpoint();
+	instruction_stream.jmp_Label_1(end);
+	let inner = instruction_stream.checkpoint();
+	let speculative = instruction_stream.create_label();
+	instruction_stream.jmp_Label(speculative).expect("not yet attached");
+	instruction_stream.attach_label(speculative);
+	instruction_stream.attach_label(end);
+	instruction_stream.emit_label(start);
+	assert_eq!(instruction_stream.label_count(), 3);
+	
+	instruction_stream.rollback_to(inner);
+	assert_eq!(instruction_stream.instruction_pointer() - instruction_stream.start_instruction_pointer(), 6);
+	assert_eq!(instruction_stream.label_count(), 2);
+	
+	instruction_stream.rollback_to(outer);
+	assert_eq!(instruction_stream.hexdump(), "90");
+	
+	instruction_stream.jmp_Label(start).expect("start is near");
+	instruction_stream.attach_label(end);
+	instruction_stream.ret();
+	
+	let (bytes, _) = instruction_stream.finish();
+	assert_eq!(bytes_to_string(bytes), "90 EB FD C3");
+}
+
+#[test]
+pub fn transaction_rolls_back_on_error()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	let far_away = instruction_stream.create_and_attach_label();
+	instruction_stream.emit_bytes(&[0x90; 200]);
+	
+	let result = instruction_stream.transaction(|instruction_stream|
+	{
+		let label = instruction_stream.create_and_attach_label();
+		instruction_stream.cmp_Register64Bit_Register64Bit(RAX, RSI);
+		instruction_stream.jcc_Label(ConditionCode::Equal, label)?;
+		instruction_stream.jcc_Label(ConditionCode::NotEqual, far_away)
+	});
+	assert!(result.is_err());
+	assert_eq!(instruction_stream.instruction_pointer() - instruction_stream.start_instruction_pointer(), 200);
+	assert_eq!(instruction_stream.label_count(), 1);
+	
+	let result: Result<u8, ShortJumpTooFarError> = instruction_stream.transaction(|instruction_stream|
+	{
+		instruction_stream.ret();
+		Ok(1)
+	});
+	assert_eq!(result, Ok(1));
+	assert_eq!(instruction_stream.instruction_pointer() - instruction_stream.start_instruction_pointer(), 201);
+}
+
+#[test]
+pub fn memory_offsets_are_64_bit_addresses()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.mov_EAX_MemoryOffset32Bit(0xFFFF_FFFFu32.into());
+	instruction_stream.mov_EAX_MemoryOffset32Bit(0x1_0000_0000u64.into());
+	instruction_stream.mov_MemoryOffset32Bit_EAX(0x1_0000_0000u64.into());
+	
+	assert_eq!(instruction_stream.hexdump(), "A1 FF FF FF FF 00 00 00 00 A1 00 00 00 00 01 00 00 00 A3 00 00 00 00 01 00 00 00");
+}
+
+#[test]
+pub fn move_segment_registers()
+{
+	use super::mnemonic_parameter_types::registers::SegmentRegister::*;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.mov_SegmentRegister_Register16Bit(DS, Register16Bit::AX);
+	instruction_stream.mov_Register16Bit_SegmentRegister(Register16Bit::AX, DS);
+	instruction_stream.mov_SegmentRegister_Register64Bit(DS, RAX);
+	instruction_stream.mov_Register64Bit_SegmentRegister(RAX, DS);
+	instruction_stream.mov_SegmentRegister_Any16BitMemory(DS, Any16BitMemory::base_64(RDI));
+	instruction_stream.mov_Any16BitMemory_SegmentRegister(Any16BitMemory::base_64(RDI), FS);
+	instruction_stream.mov_SegmentRegister_Register16Bit(ES, Register16Bit::R9W);
+	
+	assert_eq!(instruction_stream.hexdump(), "8E D8 66 8C D8 48 8E D8 48 8C D8 8E 1F 8C 27 41 8E C1");
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn save_and_restore_flags()
+{
+	const CarryFlag: u64 = 0x01;
+	const ZeroFlag: u64 = 0x40;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let (compare_flags, compare_flags_in_ah, round_trip, bytes) =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let compare_flags: unsafe extern "C" fn(u64, u64) -> u64 = instruction_stream.binary_function_pointer();
+		instruction_stream.cmp_Register64Bit_Register64Bit(RDI, RSI);
+		instruction_stream.pushfq();
+		instruction_stream.pop_Register64Bit_r64(RAX);
+		instruction_stream.ret();
+		
+		let compare_flags_in_ah: unsafe extern "C" fn(u64, u64) -> u64 = instruction_stream.binary_function_pointer();
+		instruction_stream.mov_Register32Bit_Immediate32Bit(Register32Bit::EAX, 0i32.into());
+		instruction_stream.cmp_Register64Bit_Register64Bit(RDI, RSI);
+		instruction_stream.lahf();
+		instruction_stream.ret();
+		
+		let round_trip: unsafe extern "C" fn(u64) -> u64 = instruction_stream.unary_function_pointer();
+		instruction_stream.push_Register64Bit_r64(RDI);
+		instruction_stream.popfq();
+		instruction_stream.pushfq();
+		instruction_stream.pop_Register64Bit_r64(RAX);
+		instruction_stream.ret();
+		assert_eq!(instruction_stream.current_stack_delta(), 0);
+		
+		instruction_stream.sahf();
+		instruction_stream.pushf();
+		instruction_stream.popf();
+		
+		let (bytes, _) = instruction_stream.finish();
+		(compare_flags, compare_flags_in_ah, round_trip, bytes_to_string(bytes))
+	};
+	
+	assert!(bytes.ends_with("57 9D 9C 58 C3 9E 66 9C 66 9D"), "{}", bytes);
+	assert_eq!(unsafe { compare_flags(3, 3) } & (CarryFlag | ZeroFlag), ZeroFlag);
+	assert_eq!(unsafe { compare_flags(2, 3) } & (CarryFlag | ZeroFlag), CarryFlag);
+	assert_eq!((unsafe { compare_flags_in_ah(3, 3) } >> 8) & (CarryFlag | ZeroFlag), ZeroFlag);
+	assert_eq!(unsafe { round_trip(0x202 | CarryFlag) } & CarryFlag, CarryFlag);
+	assert_eq!(unsafe { round_trip(0x202) } & CarryFlag, 0);
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn flag_bit_instructions()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let (add_carry, bytes) =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let add_carry: unsafe extern "C" fn(u64) -> u64 = instruction_stream.unary_function_pointer();
+		instruction_stream.mov_Register64Bit_Register64Bit_rm64_r64(RAX, RDI);
+		instruction_stream.stc();
+		instruction_stream.cmc();
+		instruction_stream.cmc();
+		instruction_stream.adc_Register64Bit_Immediate8Bit(RAX, 0i8.into());
+		instruction_stream.clc();
+		instruction_stream.adc_Register64Bit_Immediate8Bit(RAX, 0i8.into());
+		instruction_stream.std();
+		instruction_stream.cld();
+		instruction_stream.ret();
+		
+		instruction_stream.cli();
+		instruction_stream.sti();
+		
+		let (bytes, _) = instruction_stream.finish();
+		(add_carry, bytes_to_string(bytes))
+	};
+	
+	assert_eq!(bytes, "48 8B C7 F9 F5 F5 48 83 D0 00 F8 48 83 D0 00 FD FC C3 FA FB");
+	assert_eq!(unsafe { add_carry(41) }, 42);
+}
+
+#[test]
+pub fn return_variants()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.emit_ret();
+	instruction_stream.emit_ret_pop(8);
+	instruction_stream.emit_ret_pop(0);
+	instruction_stream.ret_Far();
+	instruction_stream.ret_Immediate16Bit_Far(0x100u16.into());
+	
+	assert_eq!(instruction_stream.hexdump(), "C3 C2 08 00 C3 CB CA 00 01");
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn enter_and_leave()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let (function_pointer, bytes) =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let function_pointer: unsafe extern "C" fn(u64) -> u64 = instruction_stream.unary_function_pointer();
+		instruction_stream.emit_enter(24, 0);
+		assert_eq!(instruction_stream.current_stack_delta(), 32);
+		instruction_stream.mov_Any64BitMemory_Register64Bit(Any64BitMemory::base_64_displacement(RBP, (-8i32).into()), RDI);
+		instruction_stream.mov_Register64Bit_Any64BitMemory(RAX, Any64BitMemory::base_64_displacement(RBP, (-8i32).into()));
+		instruction_stream.emit_leave();
+		assert_eq!(instruction_stream.current_stack_delta(), 0);
+		instruction_stream.emit_ret();
+		
+		instruction_stream.emit_enter(0x100, 2);
+		assert_eq!(instruction_stream.current_stack_delta(), 8 + 16 + 0x100);
+		instruction_stream.pop_stack_frame_and_return();
+		assert_eq!(instruction_stream.current_stack_delta(), 0);
+		
+		let (bytes, _) = instruction_stream.finish();
+		(function_pointer, bytes_to_string(bytes))
+	};
+	
+	assert_eq!(bytes, "C8 18 00 00 48 89 7D F8 48 8B 45 F8 C9 C3 C8 00 01 02 C9 C3");
+	assert_eq!(unsafe { function_pointer(42) }, 42);
+}
+
+#[test]
+pub fn short_jump_fits()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	let backward = instruction_stream.create_and_attach_label();
+	let forward = instruction_stream.create_label();
+	assert_eq!(instruction_stream.short_jump_fits(forward), None);
+	
+	instruction_stream.emit_bytes(&[0x90; 126]);
+	assert_eq!(instruction_stream.short_jump_fits(backward), Some(true), "displacement of -128 should fit");
+	instruction_stream.nop();
+	assert_eq!(instruction_stream.short_jump_fits(backward), Some(false), "displacement of -129 should not fit");
+	assert!(instruction_stream.jmp_Label(backward).is_err());
+	
+	instruction_stream.attach_label(forward);
+	assert_eq!(instruction_stream.short_jump_fits(forward), Some(true));
+	assert_eq!(instruction_stream.instruction_pointer() - instruction_stream.start_instruction_pointer(), 127, "short_jump_fits() emitted something");
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn tail_calls_via_got_slots()
+{
+	extern "C" fn seven() -> u64
+	{
+		7
+	}
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let (call_external, call_internal) =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let internal = instruction_stream.create_label();
+		
+		let external_slot = instruction_stream.emit_got_slot(seven as extern "C" fn() -> u64 as usize);
+		let internal_slot = instruction_stream.create_and_attach_label();
+		instruction_stream.emit_label(internal);
+		
+		let call_external: unsafe extern "C" fn() -> u64 = instruction_stream.nullary_function_pointer();
+		instruction_stream.tail_call_via_got_slot(external_slot);
+		assert_eq!(instruction_stream.hexdump()[48 ..], *"FF 25 EA FF FF FF", "displacement should be to the start of the slots");
+		
+		let call_internal: unsafe extern "C" fn() -> u64 = instruction_stream.nullary_function_pointer();
+		instruction_stream.tail_call_via_got_slot(internal_slot);
+		
+		instruction_stream.attach_label(internal);
+		instruction_stream.mov_Register32Bit_Immediate32Bit(Register32Bit::EAX, 42i32.into());
+		instruction_stream.ret();
+		
+		instruction_stream.finish();
+		(call_external, call_internal)
+	};
+	
+	assert_eq!(unsafe { call_external() }, 7);
+	assert_eq!(unsafe { call_internal() }, 42);
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn finish_functions()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let addresses =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let double = instruction_stream.create_and_attach_label();
+		instruction_stream.lea_Register64Bit_Any64BitMemory(RAX, Any64BitMemory::base_64_index_64(RDI, RDI));
+		instruction_stream.ret();
+		
+		let cold = instruction_stream.create_section(16, 4096).expect("Could not create section");
+		instruction_stream.switch_section(cold);
+		let negate = instruction_stream.create_and_attach_label();
+		instruction_stream.mov_Register64Bit_Register64Bit_rm64_r64(RAX, RDI);
+		instruction_stream.neg_Register64Bit(RAX);
+		instruction_stream.ret();
+		instruction_stream.switch_section(SectionId::Default);
+		
+		let (addresses, _) = instruction_stream.finish_functions(&[("double", double), ("negate", negate)]);
+		addresses
+	};
+	
+	assert_eq!(addresses.len(), 2);
+	let double: unsafe extern "C" fn(i64) -> i64 = unsafe { ::std::mem::transmute::<*const u8, unsafe extern "C" fn(i64) -> i64>(addresses["double"]) };
+	let negate: unsafe extern "C" fn(i64) -> i64 = unsafe { ::std::mem::transmute::<*const u8, unsafe extern "C" fn(i64) -> i64>(addresses["negate"]) };
+	assert_eq!(unsafe { double(21) }, 42);
+	assert_eq!(unsafe { negate(42) }, -42);
+}
+
+#[test]
+pub fn cache_line_flushes()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.clflush_Any8BitMemory(Any8BitMemory::base_64(RDI));
+	instruction_stream.clflushopt_Any8BitMemory(Any8BitMemory::base_64(RDI));
+	instruction_stream.clwb_Any8BitMemory(Any8BitMemory::base_64(RDI));
+	instruction_stream.clwb_Any8BitMemory(Any8BitMemory::base_64_displacement(R9, 0x40i32.into()));
+	instruction_stream.clflushopt_Any8BitMemory(Any8BitMemory::base_64_index_64(RAX, RCX));
+	
+	assert_eq!(instruction_stream.hexdump(), "0F AE 3F 66 0F AE 3F 66 0F AE 37 66 41 0F AE 71 40 66 0F AE 3C 08");
+}
+
+#[test]
+pub fn memory_operand_builder()
+{
+	let memory: Any64BitMemory = MemoryOperand::base(RBX).index(RCX, IndexScale::x4).displacement(0x10).segment(SegmentRegister::FS).build();
+	assert_eq!(memory, Any64BitMemory::segment_base_64_index_64_scale_displacement(SegmentRegister::FS, RBX, RCX, IndexScale::x4, 0x10i32.into()));
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.mov_Register64Bit_Any64BitMemory(RAX, memory);
+	instruction_stream.lea_Register64Bit_Any64BitMemory(RDX, MemoryOperand::index(R9, IndexScale::x8).displacement(0x100).build());
+	
+	assert_eq!(instruction_stream.hexdump(), "64 48 8B 44 8B 10 4A 8D 14 CD 00 01 00 00");
+}
+
+#[test]
+pub fn memory_operand_address_size()
+{
+	let memory: Any64BitMemory = MemoryOperand::builder().base_32(Register32Bit::EBX).index_32(Register32Bit::ECX, IndexScale::x2).displacement(8).build();
+	assert!(memory.0.has_address_override_for_32_bit());
+	assert!(!MemoryOperand::base(RBX).index(RCX, IndexScale::x2).build::<MemoryOperand>().has_address_override_for_32_bit());
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.mov_Register64Bit_Any64BitMemory(RAX, Any64BitMemory::base_32(Register32Bit::EBX));
+	instruction_stream.mov_Register64Bit_Any64BitMemory(RAX, memory);
+	
+	assert_eq!(instruction_stream.hexdump(), "67 48 8B 03 67 48 8B 44 4B 08");
+}
+
+#[test]
+#[should_panic(expected = "can not be mixed")]
+pub fn memory_operand_mixing_address_sizes_panics()
+{
+	let _: Any64BitMemory = MemoryOperand::base(RBX).index_32(Register32Bit::ECX, IndexScale::x1).build();
+}
+
+#[test]
+pub fn byte_swaps()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.bswap_Register32Bit(Register32Bit::EAX);
+	instruction_stream.bswap_Register64Bit(RAX);
+	instruction_stream.bswap_Register64Bit(R12);
+	instruction_stream.bswap_Register32Bit(Register32Bit::R9D);
+	instruction_stream.bswap_Register16Bit(Register16Bit::CX);
+	instruction_stream.bswap_Register16Bit(Register16Bit::R10W);
+	
+	assert_eq!(instruction_stream.hexdump(), "0F C8 48 0F C8 49 0F CC 41 0F C9 66 C1 C1 08 66 41 C1 C2 08");
+}
+
+#[test]
+pub fn crc32_encodings()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.crc32_Register32Bit_Register8Bit(Register32Bit::EAX, Register8Bit::BL);
+	instruction_stream.crc32_Register32Bit_Register8Bit(Register32Bit::EAX, Register8Bit::SIL);
+	instruction_stream.crc32_Register32Bit_RegisterHigh8BitsOf16Bits(Register32Bit::ECX, RegisterHigh8BitsOf16Bits::AH);
+	instruction_stream.crc32_Register32Bit_Register16Bit(Register32Bit::EDX, Register16Bit::R9W);
+	instruction_stream.crc32_Register32Bit_Register32Bit(Register32Bit::R8D, Register32Bit::ECX);
+	instruction_stream.crc32_Register64Bit_Register64Bit(RAX, RCX);
+	instruction_stream.crc32_Register64Bit_Register8Bit(RAX, Register8Bit::R10B);
+	instruction_stream.crc32_Register32Bit_Any8BitMemory(Register32Bit::EAX, Any8BitMemory::base_64(RDI));
+	instruction_stream.crc32_Register32Bit_Any16BitMemory(Register32Bit::EAX, Any16BitMemory::base_64(RDI));
+	instruction_stream.crc32_Register32Bit_Any32BitMemory(Register32Bit::EAX, Any32BitMemory::base_64_displacement(RDI, 4i32.into()));
+	instruction_stream.crc32_Register64Bit_Any64BitMemory(R11, Any64BitMemory::base_64_index_64_scale(RDI, RCX, IndexScale::x8));
+	instruction_stream.crc32_Register64Bit_Any8BitMemory(RAX, Any8BitMemory::base_64(RDI));
+	
+	assert_eq!(instruction_stream.hexdump(), "F2 0F 38 F0 C3 F2 40 0F 38 F0 C6 F2 0F 38 F0 CC 66 F2 41 0F 38 F1 D1 F2 44 0F 38 F1 C1 F2 48 0F 38 F1 C1 F2 49 0F 38 F0 C2 F2 0F 38 F0 07 66 F2 0F 38 F1 07 F2 0F 38 F1 47 04 F2 4C 0F 38 F1 1C CF F2 48 0F 38 F0 07");
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn crc32_computes_crc32c()
+{
+	fn reference_crc32c(mut crc: u32, bytes: &[u8]) -> u32
+	{
+		for &byte in bytes
+		{
+			crc ^= byte as u32;
+			for _ in 0 .. 8
+			{
+				crc = (crc >> 1) ^ (0x82F63B78 & (crc & 1).wrapping_neg());
+			}
+		}
+		crc
+	}
+	
+	if !is_x86_feature_detected!("sse4.2")
+	{
+		return
+	}
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let crc32c =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let crc32c: unsafe extern "C" fn(*const u8, u64, u32) -> u32 = instruction_stream.ternary_function_pointer();
+		let next_byte = instruction_stream.create_label();
+		let done = instruction_stream.create_label();
+		instruction_stream.mov_Register32Bit_Register32Bit(Register32Bit::EAX, Register32Bit::EDX);
+		instruction_stream.test_Register64Bit_Register64Bit(RSI, RSI);
+		instruction_stream.je_Label(done).expect("Forward jump is short");
+		instruction_stream.attach_label(next_byte);
+		instruction_stream.crc32_Register32Bit_Any8BitMemory(Register32Bit::EAX, Any8BitMemory::base_64(RDI));
+		instruction_stream.inc_Register64Bit(RDI);
+		instruction_stream.dec_Register64Bit(RSI);
+		instruction_stream.jne_Label(next_byte).expect("Backwards jump is short");
+		instruction_stream.attach_label(done);
+		instruction_stream.ret();
+		
+		instruction_stream.finish();
+		crc32c
+	};
+	
+	let bytes = b"123456789";
+	assert_eq!(!unsafe { crc32c(bytes.as_ptr(), bytes.len() as u64, !0) }, 0xE3069283);
+	
+	let bytes = b"The quick brown fox jumps over the lazy dog";
+	assert_eq!(unsafe { crc32c(bytes.as_ptr(), bytes.len() as u64, 0x12345678) }, reference_crc32c(0x12345678, bytes));
+	assert_eq!(unsafe { crc32c(bytes.as_ptr(), 0, 0x12345678) }, 0x12345678);
+}
+
+#[test]
+pub fn packed_string_compare_encodings()
+{
+	use self::XMMRegister::*;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.pcmpistri_XMMRegister_XMMRegister_Immediate8Bit(XMM1, XMM2, 0x0Cu8.into());
+	instruction_stream.pcmpistri_XMMRegister_Any128BitMemory_Immediate8Bit(XMM0, Any128BitMemory::base_64(RDI), 0x00u8.into());
+	instruction_stream.pcmpestri_XMMRegister_XMMRegister_Immediate8Bit(XMM1, XMM9, 0x08u8.into());
+	instruction_stream.pcmpestri_XMMRegister_Any128BitMemory_Immediate8Bit(XMM3, Any128BitMemory::base_64_displacement(RSI, 0x10i32.into()), 0x18u8.into());
+	instruction_stream.pcmpistrm_XMMRegister_XMMRegister_Immediate8Bit(XMM10, XMM1, 0x40u8.into());
+	instruction_stream.pcmpistrm_XMMRegister_Any128BitMemory_Immediate8Bit(XMM1, Any128BitMemory::base_64(RDI), 0x40u8.into());
+	instruction_stream.pcmpestrm_XMMRegister_XMMRegister_Immediate8Bit(XMM2, XMM1, 0x44u8.into());
+	instruction_stream.pcmpestrm_XMMRegister_Any128BitMemory_Immediate8Bit(XMM2, Any128BitMemory::base_64(RDI), 0x44u8.into());
+	
+	assert_eq!(instruction_stream.hexdump(), "66 0F 3A 63 CA 0C 66 0F 3A 63 07 00 66 41 0F 3A 61 C9 08 66 0F 3A 61 5E 10 18 66 44 0F 3A 62 D1 40 66 0F 3A 62 0F 40 66 0F 3A 60 D1 44 66 0F 3A 60 17 44");
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn packed_string_compare_finds_first_of_any()
+{
+	if !is_x86_feature_detected!("sse4.2")
+	{
+		return
+	}
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let find_first_of_any =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let find_first_of_any: unsafe extern "C" fn(*const [u8; 16], *const [u8; 16]) -> u32 = instruction_stream.binary_function_pointer();
+		instruction_stream.movdqu_XMMRegister_Any128BitMemory(XMMRegister::XMM0, Any128BitMemory::base_64(RDI));
+		
+		// Unsigned bytes, equal any, least significant index.
+		instruction_stream.pcmpistri_XMMRegister_Any128BitMemory_Immediate8Bit(XMMRegister::XMM0, Any128BitMemory::base_64(RSI), 0x00u8.into());
+		instruction_stream.mov_Register32Bit_Register32Bit(Register32Bit::EAX, Register32Bit::ECX);
+		instruction_stream.ret();
+		
+		instruction_stream.finish();
+		find_first_of_any
+	};
+	
+	let characters = *b"xyzw\0\0\0\0\0\0\0\0\0\0\0\0";
+	assert_eq!(unsafe { find_first_of_any(&characters, b"hello world\0\0\0\0\0") }, 6);
+	assert_eq!(unsafe { find_first_of_any(&characters, b"abcdefghijklmnop") }, 16);
+}
+
+#[test]
+pub fn finish_relaxed_keeps_alignment_and_patch_points()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	let done = instruction_stream.create_label();
+	
+	// Shrinking this would move the aligned code after it.
+	instruction_stream.jcc_Label_auto(ConditionCode::Equal, done);
+	instruction_stream.emit_alignment(16);
+	instruction_stream.jmp_Label_auto(done);
+	let patch_point = instruction_stream.reserve_patch_point(3);
+	instruction_stream.jmp_Label_auto(done);
+	instruction_stream.nop();
+	instruction_stream.attach_label(done);
+	instruction_stream.ret();
+	
+	let (encoded_bytes, _hints) = instruction_stream.finish_relaxed();
+	
+	assert_eq!(encoded_bytes.len(), 28, "only the last jump should have been shrunk");
+	assert_eq!(&bytes_to_string(&encoded_bytes[0 .. 6]), "0F 84 15 00 00 00", "Jcc before alignment should not have been shrunk");
+	assert_eq!(&bytes_to_string(&encoded_bytes[16 .. 21]), "E9 06 00 00 00", "JMP before patch point should not have been shrunk");
+	assert_eq!(patch_point.instruction_pointer(), encoded_bytes.as_ptr() as usize + 21, "patch point was moved");
+	assert_eq!(&bytes_to_string(&encoded_bytes[24 .. 26]), "EB 01", "JMP after patch point should have been shrunk");
+}
+
+#[test]
+pub fn instruction_hook_is_called_once_for_automatically_sized_jumps()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_pointers = Vec::new();
+	
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		let start = instruction_stream.instruction_pointer();
+		
+		let instruction_pointers = &mut instruction_pointers;
+		instruction_stream.set_instruction_hook(move |instruction_pointer| instruction_pointers.push(instruction_pointer - start));
+		
+		let backwards = instruction_stream.create_and_attach_label();
+		let forwards = instruction_stream.create_label();
+		instruction_stream.jcc_Label_auto(ConditionCode::Equal, forwards);
+		instruction_stream.jmp_Label_auto(forwards);
+		instruction_stream.jcc_Label_auto(ConditionCode::NotEqual, backwards);
+		instruction_stream.attach_label(forwards);
+		instruction_stream.clear_instruction_hook();
+	}
+	
+	assert_eq!(instruction_pointers, vec![0, 6, 11], "hook was not called once for each jump");
+}
+
+#[test]
+pub fn emit_all_near_capacity()
+{
+	use super::Inst::*;
+	
+	let mut buffer = [0u8; 64];
+	let mut instruction_stream = InstructionStream::new_in_buffer(&mut buffer, &InstructionStreamHints::default());
+	instruction_stream.emit_bytes(&[0x90; 32]);
+	
+	let instructions = [MovRegReg(RAX, RDI), AddRegImm(RAX, 1), Push(RAX), Pop(RAX), Ret];
+	assert_eq!(instructions.iter().map(|instruction| instruction.maximum_length()).sum::<usize>(), 15);
+	
+	let too_many = [MovRegReg(RAX, RDI), AddRegImm(RAX, 1), Push(RAX), Pop(RAX), Nop, Nop, Nop, Nop, Ret];
+	assert!(instruction_stream.emit_all(&too_many).is_err(), "emit_all should have run out of space");
+	assert_eq!(instruction_stream.instruction_pointer() - instruction_stream.start_instruction_pointer(), 32, "nothing should have been emitted");
+	
+	instruction_stream.emit_all(&instructions).expect("emit_all should have fitted");
+	assert_eq!(instruction_stream.instruction_pointer() - instruction_stream.start_instruction_pointer(), 45);
+}
+
+#[test]
+pub fn index_scale_multipliers()
+{
+	for &index_scale in [IndexScale::x1, IndexScale::x2, IndexScale::x4, IndexScale::x8].iter()
+	{
+		assert_eq!(IndexScale::from_multiplier(index_scale.multiplier()), Some(index_scale), "multiplier did not round trip");
+	}
+	
+	assert_eq!(IndexScale::from_multiplier(3), None, "3 is not a valid multiplier");
+	assert_eq!(IndexScale::from_multiplier(0), None, "0 is not a valid multiplier");
+}
+
+#[test]
+pub fn sixty_four_bit_memory_offsets()
+{
+	use super::mnemonic_parameter_types::immediates::Immediate64Bit;
+	use super::mnemonic_parameter_types::memory_offsets::MemoryOffset64Bit;
+	
+	let memory_offset: MemoryOffset64Bit = (Some(SegmentRegister::GS), Immediate64Bit(0x10)).into();
+	let segment_register: Option<SegmentRegister> = memory_offset.into();
+	assert_eq!(segment_register, Some(SegmentRegister::GS), "segment register was lost");
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.mov_RAX_MemoryOffset64Bit(0x11223344_55667788u64.into());
+	instruction_stream.mov_MemoryOffset64Bit_RAX(0x1000u64.into());
+	
+	assert_eq!(&instruction_stream.hexdump(), "48 A1 88 77 66 55 44 33 22 11 48 A3 00 10 00 00 00 00 00 00", "Encoding of MOV RAX, moffs64 and MOV moffs64, RAX was wrong");
+}
+
+#[test]
+pub fn sixteen_bit_memory_offsets()
+{
+	use super::mnemonic_parameter_types::immediates::Immediate64Bit;
+	use super::mnemonic_parameter_types::memory_offsets::MemoryOffset16Bit;
+	
+	let memory_offset: MemoryOffset16Bit = (None, Immediate64Bit(0x10)).into();
+	let segment_register: Option<SegmentRegister> = memory_offset.into();
+	assert_eq!(segment_register, None, "segment register was invented");
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.mov_AX_MemoryOffset16Bit(0x1000u64.into());
+	
+	assert_eq!(&instruction_stream.hexdump(), "66 A1 00 10 00 00 00 00 00 00", "Encoding of MOV AX, moffs16 was wrong");
+}
+
+#[test]
+pub fn signed_displacements_are_emitted_in_twos_complement()
+{
+	use super::ByteEmitter;
+	use super::Displacement;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	map.make_writable();
+	let mut byte_emitter = ByteEmitter::new(&mut map);
+	
+	(-2i8).emit(&mut byte_emitter);
+	(-2i16).emit(&mut byte_emitter);
+	(-2i32).emit(&mut byte_emitter);
+	(-2i64).emit(&mut byte_emitter);
+	0x7Fi8.emit(&mut byte_emitter);
+	0x1234i16.emit(&mut byte_emitter);
+	
+	let bytes = unsafe { ::std::slice::from_raw_parts(map.address as *const u8, byte_emitter.instruction_pointer - map.address as usize) };
+	assert_eq!(bytes, &[0xFE, 0xFE, 0xFF, 0xFE, 0xFF, 0xFF, 0xFF, 0xFE, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F, 0x34, 0x12][..], "Signed displacements were not emitted in little-endian two's complement");
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn jumps_and_calls_to_absolute_addresses()
+{
+	extern "C" fn forty_two() -> u64
+	{
+		42
+	}
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let function_pointer =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let start = instruction_stream.instruction_pointer();
+		instruction_stream.jmp_absolute_address((start + 0x105) as *const u8).expect("target was near");
+		instruction_stream.call_absolute_address(start as *const u8).expect("target was near");
+		assert_eq!(&instruction_stream.hexdump(), "E9 00 01 00 00 E8 F6 FF FF FF", "Encoding of JMP rel32 and CALL rel32 was wrong");
+		
+		let far_away = start.wrapping_add(0x1_0000_0000) as *const u8;
+		assert_eq!(instruction_stream.jmp_absolute_address(far_away), Err(AbsoluteAddressTooFarError { target: far_away as usize, displacement: 0x1_0000_0000 - 15 }), "far JMP was not an error");
+		assert_eq!(instruction_stream.emitted_bytes().len(), 10, "far JMP was emitted");
+		
+		let function_pointer: unsafe extern "C" fn() -> u64 = instruction_stream.nullary_function_pointer();
+		instruction_stream.jmp_via_register(forty_two as *const u8, RAX);
+		
+		let _ = instruction_stream.finish();
+		
+		function_pointer
+	};
+	
+	assert_eq!(unsafe { function_pointer() }, 42, "JMP via register did not reach the target");
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn far_calls_and_jumps()
+{
+	extern "C" fn add_one(value: u64) -> u64
+	{
+		value + 1
+	}
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let function_pointer =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		let start = instruction_stream.instruction_pointer();
+		let near = (start + 0x100) as *const u8;
+		let far = start.wrapping_add(0x80_0000_0000) as *const u8;
+		
+		instruction_stream.emit_far_call(near, R11);
+		instruction_stream.emit_far_call(far, R11);
+		instruction_stream.emit_far_jmp(far, RAX);
+		let expected = format!("E8 FB 00 00 00 49 BB {} 41 FF D3 48 B8 {} FF E0", bytes_to_string(&(far as u64).to_le_bytes()), bytes_to_string(&(far as u64).to_le_bytes()));
+		assert_eq!(instruction_stream.hexdump(), expected, "Encoding of near and far CALL and JMP was wrong");
+		
+		let function_pointer: unsafe extern "C" fn(u64) -> u64 = instruction_stream.unary_function_pointer();
+		instruction_stream.push_stack_frame();
+		instruction_stream.emit_far_call(add_one as *const u8, R11);
+		instruction_stream.pop_stack_frame_and_return();
+		
+		let _ = instruction_stream.finish();
+		
+		function_pointer
+	};
+	
+	assert_eq!(unsafe { function_pointer(41) }, 42, "far CALL did not reach the target");
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn prologues_and_epilogues()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.emit_prologue(20);
+	instruction_stream.emit_epilogue();
+	assert_eq!(&instruction_stream.hexdump(), "55 48 8B EC 48 83 EC 20 C9 C3", "Encoding of prologue and epilogue was wrong");
+	
+	let function_pointer: unsafe extern "C" fn(u64) -> u64 = instruction_stream.unary_function_pointer();
+	instruction_stream.emit_prologue_with_callee_saves(8, &[RBX, R12, R13]);
+	assert_eq!(instruction_stream.current_stack_delta(), 40, "callee saves and frame were not tracked");
+	instruction_stream.mov_Register64Bit_Register64Bit_rm64_r64(RBX, RDI);
+	instruction_stream.mov_Register64Bit_Immediate64Bit(R12, 0u64.into());
+	instruction_stream.mov_Register64Bit_Immediate64Bit(R13, 0u64.into());
+	instruction_stream.mov_Any64BitMemory_Register64Bit(Any64BitMemory::base_64_displacement(RBP, (-32i32).into()), RBX);
+	instruction_stream.mov_Register64Bit_Any64BitMemory(RAX, Any64BitMemory::base_64_displacement(RBP, (-32i32).into()));
+	instruction_stream.emit_epilogue_with_callee_saves(&[RBX, R12, R13]);
+	assert_eq!(instruction_stream.current_stack_delta(), 0, "callee saves were not tracked");
+	
+	let _ = instruction_stream.finish();
+	
+	assert_eq!(unsafe { function_pointer(42) }, 42, "function with callee saves did not return its argument");
+}
+
+#[test]
+pub fn push_all_and_pop_all()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.push_all(&[RAX, RCX, R8]);
+	assert_eq!(instruction_stream.current_stack_delta(), 24, "pushes were not tracked");
+	
+	instruction_stream.pop_all(&[RAX, RCX, R8]);
+	assert_eq!(instruction_stream.current_stack_delta(), 0, "pops were not tracked");
+	
+	assert_eq!(&instruction_stream.hexdump(), "50 51 41 50 41 58 59 58", "POPs were not in reverse order");
+}
+
+#[test]
+pub fn stack_alignment_tracking()
+{
+	use self::Register16Bit::AX;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	assert_eq!(instruction_stream.stack_alignment_offset(), 8, "stack was not misaligned by the return address on entry");
+	
+	instruction_stream.emit_prologue(24);
+	assert_eq!(instruction_stream.current_stack_delta(), 40, "prologue was not tracked");
+	instruction_stream.debug_assert_stack_aligned_for_call();
+	
+	instruction_stream.push_Register16Bit(AX);
+	instruction_stream.pushfq();
+	instruction_stream.sub_Register64Bit_Immediate8Bit(RSP, 6i8.into());
+	assert_eq!(instruction_stream.current_stack_delta(), 56, "PUSH r16, PUSHFQ and SUB RSP were not tracked");
+	instruction_stream.debug_assert_stack_aligned_for_call();
+	
+	instruction_stream.add_Register64Bit_Immediate32Bit(RSP, 16i32.into());
+	instruction_stream.sub_Register64Bit_Immediate8Bit(RAX, 8i8.into());
+	assert_eq!(instruction_stream.stack_alignment_offset(), 0, "ADD RSP was not tracked or SUB RAX was");
+	
+	instruction_stream.emit_epilogue();
+	assert_eq!(instruction_stream.current_stack_delta(), 0, "epilogue was not tracked");
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "stack is not 16-byte aligned for a call")]
+pub fn misaligned_stack_for_call_panics()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.push_stack_frame();
+	instruction_stream.push_Register64Bit_r64(RBX);
+	instruction_stream.debug_assert_stack_aligned_for_call();
+}
+
+#[test]
+pub fn alignment_with_fill()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.ret();
+	assert_eq!(instruction_stream.emit_alignment_with_fill(8, 0xCC), 7);
+	assert_eq!(instruction_stream.emit_alignment_with_fill(8, 0xCC), 0);
+	instruction_stream.ret();
+	
+	assert_eq!(&instruction_stream.hexdump(), "C3 CC CC CC CC CC CC CC C3", "Padding was not INT3 to an 8 byte boundary");
+}
+
+#[test]
+pub fn instruction_hook()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_pointers = Vec::new();
+	
+	let start =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		let start = instruction_stream.instruction_pointer();
+		
+		let instruction_pointers = &mut instruction_pointers;
+		instruction_stream.set_instruction_hook(move |instruction_pointer| instruction_pointers.push(instruction_pointer - start));
+		
+		instruction_stream.push_stack_frame();
+		instruction_stream.emit_bytes(&[0x90, 0x90]);
+		instruction_stream.mov_Register64Bit_Immediate64Bit(RAX, 1u64.into());
+		instruction_stream.clear_instruction_hook();
+		instruction_stream.pop_stack_frame_and_return();
+		
+		start
+	};
+	
+	assert_ne!(start, 0, "start was not set");
+	assert_eq!(instruction_pointers, vec![0, 1, 6], "hook was not called at the start of each instruction");
+}
+
+#[test]
+pub fn annotations()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	let cold = instruction_stream.create_section(16, 4096).expect("Could not create section");
+	
+	instruction_stream.annotate(1);
+	instruction_stream.push_stack_frame();
+	
+	instruction_stream.switch_section(cold);
+	instruction_stream.annotate(3);
+	instruction_stream.ud2();
+	
+	instruction_stream.switch_section(SectionId::Default);
+	instruction_stream.annotate(2);
+	instruction_stream.pop_stack_frame_and_return();
+	
+	let (_, _, annotations) = instruction_stream.finish_with_annotations();
+	
+	assert_eq!(annotations, vec![(0, 1), (4, 2), (16, 3)], "annotations were not relocated and sorted");
+}
+
+#[test]
+pub fn elf_object()
+{
+	use super::Relocation;
+	use super::RelocationKind;
+	use super::mnemonic_parameter_types::immediates::Immediate32Bit;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	let answer = instruction_stream.create_and_attach_label();
+	let body = instruction_stream.create_label();
+	instruction_stream.jmp_Label(body).expect("Could not emit JMP");
+	instruction_stream.attach_label(body);
+	instruction_stream.mov_Register32Bit_Immediate32Bit(Register32Bit::EAX, Immediate32Bit(42));
+	instruction_stream.ret();
+	let table = instruction_stream.create_and_attach_label();
+	instruction_stream.emit_label(answer);
+	
+	assert_eq!(instruction_stream.relocations(), vec![Relocation { offset: 8, kind: RelocationKind::Absolute64Bit, target_offset: 0 }], "relocations were wrong");
+	
+	let object = instruction_stream.emit_elf_object(&[("answer".to_string(), answer), ("table".to_string(), table)]);
+	
+	assert_eq!(&object[0 .. 4], b"\x7FELF", "ELF magic was wrong");
+	assert_eq!(&object[16 .. 20], &[1, 0, 62, 0], "not a relocatable x86-64 object");
+	assert_eq!(&bytes_to_string(&object[64 .. 80]), "EB 00 B8 2A 00 00 00 C3 00 00 00 00 00 00 00 00", ".text was wrong");
+	assert!(object.windows(14).any(|window| window == b"\0answer\0table\0"), "symbol names were missing");
+}
+
+#[test]
+pub fn macho_object()
+{
+	use super::mnemonic_parameter_types::immediates::Immediate32Bit;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	let answer = instruction_stream.create_and_attach_label();
+	instruction_stream.mov_Register32Bit_Immediate32Bit(Register32Bit::EAX, Immediate32Bit(42));
+	instruction_stream.ret();
+	let table = instruction_stream.create_and_attach_label();
+	instruction_stream.emit_label(answer);
+	
+	let object = instruction_stream.emit_macho_object(&[("answer".to_string(), answer), ("table".to_string(), table)]);
+	
+	assert_eq!(&object[0 .. 16], &[0xCF, 0xFA, 0xED, 0xFE, 0x07, 0x00, 0x00, 0x01, 0x03, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00], "not a relocatable x86-64 Mach-O object");
+	assert_eq!(&bytes_to_string(&object[320 .. 336]), "B8 2A 00 00 00 C3 00 00 00 00 00 00 00 00 00 00", "__text was wrong");
+	assert!(object.windows(16).any(|window| window == b"\0_answer\0_table\0"), "symbol names were missing");
+	
+	#[cfg(target_os = "macos")]
+	{
+		use ::std::process::Command;
+		
+		let directory = ::std::env::temp_dir().join(format!("assembler-macho-object-{}", ::std::process::id()));
+		::std::fs::create_dir_all(&directory).expect("Could not create directory");
+		::std::fs::write(directory.join("answer.o"), &object).expect("Could not write object");
+		::std::fs::write(directory.join("main.c"), "int answer(void); extern int (*table)(void); int main(void) { return answer() + table(); }").expect("Could not write main.c");
+		
+		let status = Command::new("cc").current_dir(&directory).args(&["main.c", "answer.o", "-o", "main"]).status().expect("Could not run cc");
+		assert!(status.success(), "Could not link object");
+		
+		let status = Command::new(directory.join("main")).status().expect("Could not run linked program");
+		assert_eq!(status.code(), Some(84), "linked program returned wrong result");
+	}
+}
+
+#[test]
+#[cfg(feature = "disassembly")]
+pub fn disassembly()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	let top = instruction_stream.create_and_attach_label();
+	instruction_stream.push_Register64Bit_r64(RBX);
+	instruction_stream.jmp_Label(top).expect("Could not emit JMP");
+	
+	assert_eq!(instruction_stream.to_intel_syntax(), "label_0:\n00000000  push rbx\n00000001  jmp short label_0\n", "Intel syntax was wrong");
+	assert_eq!(instruction_stream.to_att_syntax(), "label_0:\n00000000  push %rbx\n00000001  jmp label_0\n", "AT&T syntax was wrong");
+}
+
+#[test]
+#[cfg(feature = "perf")]
+pub fn register_with_perf()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	let entry = instruction_stream.create_and_attach_label();
+	instruction_stream.zero_RAX();
+	instruction_stream.ret();
+	
+	let expected = format!("{:x} 3 register_with_perf_test_function", instruction_stream.start_instruction_pointer());
+	instruction_stream.register_with_perf("register_with_perf_test_function", entry).expect("Could not write perf map");
+	
+	let perf_map = ::std::fs::read_to_string(format!("/tmp/perf-{}.map", ::std::process::id())).expect("Could not read perf map");
+	assert!(perf_map.lines().any(|line| line == expected), "perf map did not contain '{}'", expected);
+}
+
+#[test]
+#[cfg(feature = "gdb")]
+pub fn register_with_gdb()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.ret();
+	
+	let first = instruction_stream.register_with_gdb(b"\x7FELF first");
+	let second = instruction_stream.register_with_gdb(b"\x7FELF second");
+	assert_eq!(second.symbol_file(), b"\x7FELF second", "symbol file was not copied");
+	
+	let first_entry = unsafe { (*::std::ptr::addr_of!(super::__jit_debug_descriptor)).first_entry };
+	assert_eq!(first_entry, second.jit_code_entry, "second registration was not first");
+	assert_eq!(unsafe { (*first_entry).next_entry }, first.jit_code_entry, "first registration was not linked");
+	
+	drop(second);
+	
+	let first_entry = unsafe { (*::std::ptr::addr_of!(super::__jit_debug_descriptor)).first_entry };
+	assert_eq!(first_entry, first.jit_code_entry, "second registration was not unlinked");
+	assert_eq!(unsafe { (*first_entry).previous_entry }, ::std::ptr::null_mut(), "first registration was not unlinked");
+	
+	drop(first);
+}
+
+#[test]
+pub fn high_8_bit_registers()
+{
+	use self::RegisterHigh8BitsOf16Bits::*;
+	use self::Register8Bit::*;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.mov_RegisterHigh8BitsOf16Bits_Register8Bit(AH, BL);
+	instruction_stream.mov_Register8Bit_RegisterHigh8BitsOf16Bits(CL, BH);
+	instruction_stream.mov_Register8Bit_Register8Bit(SPL, BL);
+	
+	let (encoded_bytes, _hints) = instruction_stream.finish();
+	
+	assert_eq!(&bytes_to_string(encoded_bytes), "88 DC 88 F9 40 88 DC", "Encoding of high 8-bit registers was wrong");
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "can not be encoded in an instruction with a REX prefix")]
+pub fn high_8_bit_registers_can_not_be_combined_with_a_rex_prefix()
+{
+	use self::RegisterHigh8BitsOf16Bits::*;
+	use self::Register8Bit::*;
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.mov_RegisterHigh8BitsOf16Bits_Register8Bit(AH, SPL);
+}
+
+#[test]
+pub fn conditional_moves()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.cmove_Register64Bit_Register64Bit(RAX, RBX);
+	instruction_stream.cmovl_Register32Bit_Register32Bit(Register32Bit::R8D, Register32Bit::ECX);
+	instruction_stream.cmova_Register16Bit_Any16BitMemory(Register16Bit::AX, Any16BitMemory::base_64(RDI));
+	instruction_stream.cmovne_Register64Bit_Any64BitMemory(R9, Any64BitMemory::base_64_displacement(RSI, 8i32.into()));
+	
+	let (encoded_bytes, _hints) = instruction_stream.finish();
+	
+	assert_eq!(&bytes_to_string(encoded_bytes), "48 0F 44 C3 44 0F 4C C1 66 0F 47 07 4C 0F 45 4E 08", "Encoding of conditional moves was wrong");
+}
+
+#[test]
+pub fn byte_set_on_condition()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.sete_Register8Bit(Register8Bit::AL);
+	instruction_stream.setne_Register8Bit(Register8Bit::SIL);
+	instruction_stream.setl_Register8Bit(Register8Bit::R9B);
+	instruction_stream.setb_Any8BitMemory(Any8BitMemory::base_64(RDI));
+	instruction_stream.sete_RegisterHigh8BitsOf16Bits(RegisterHigh8BitsOf16Bits::AH);
+	
+	let (encoded_bytes, _hints) = instruction_stream.finish();
+	
+	assert_eq!(&bytes_to_string(encoded_bytes), "0F 94 C0 40 0F 95 C6 41 0F 9C C1 0F 92 07 0F 94 C4", "Encoding of byte set on condition was wrong");
+}
+
+#[test]
+pub fn short_jump_too_far()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	let backward_label = instruction_stream.create_and_attach_label();
+	for _ in 0 .. 200
+	{
+		instruction_stream.nop();
+	}
+	let instruction_pointer_before_jump = instruction_stream.instruction_pointer();
+	
+	let error = instruction_stream.jmp_Label(backward_label).expect_err("8-bit JMP should have been too far");
+	assert_eq!(error, ShortJumpTooFarError { label: backward_label, displacement: -202 }, "error did not describe the jump");
+	assert_eq!(instruction_stream.instruction_pointer(), instruction_pointer_before_jump, "instruction stream was not rolled back");
+	
+	instruction_stream.jmp_Label_1(backward_label);
+	
+	let (encoded_bytes, _hints) = instruction_stream.finish();
+	
+	assert_eq!(&bytes_to_string(&encoded_bytes[200 ..]), "E9 33 FF FF FF", "Encoding of replacement 32-bit JMP was wrong");
+}
+
+#[test]
+pub fn automatically_sized_jumps()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	let near_label = instruction_stream.create_and_attach_label();
+	instruction_stream.emit_nops(200);
+	let short_label = instruction_stream.create_and_attach_label();
+	let forward_label = instruction_stream.create_label();
+	
+	instruction_stream.jmp_Label_auto(short_label);
+	instruction_stream.jmp_Label_auto(near_label);
+	instruction_stream.jcc_Label_auto(ConditionCode::Equal, short_label);
+	instruction_stream.jcc_Label_auto(ConditionCode::Less, near_label);
+	instruction_stream.jcc_Label_auto(ConditionCode::NotEqual, forward_label);
+	instruction_stream.attach_label(forward_label);
+	
+	let (encoded_bytes, _hints) = instruction_stream.finish();
+	
+	assert_eq!(&bytes_to_string(&encoded_bytes[200 ..]), "EB FE E9 31 FF FF FF 74 F7 0F 8C 29 FF FF FF 0F 85 00 00 00 00", "Encoding of automatically sized jumps was wrong");
+}
+
+#[test]
+pub fn finish_relaxed_shrinks_forward_jumps()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	let done = instruction_stream.create_label();
+	
+	// Only in range once the `JMP` below has been shrunk.
+	instruction_stream.jcc_Label_auto(ConditionCode::Equal, done);
+	instruction_stream.emit_nops(118);
+	instruction_stream.jmp_Label_auto(done);
+	instruction_stream.emit_nops(3);
+	instruction_stream.attach_label(done);
+	instruction_stream.ret();
+	instruction_stream.emit_label(done);
+	
+	let (encoded_bytes, _hints) = instruction_stream.finish_relaxed();
+	
+	assert_eq!(encoded_bytes.len(), 134, "jumps were not shrunk");
+	assert_eq!(&bytes_to_string(&encoded_bytes[0 .. 2]), "74 7B", "Encoding of relaxed Jcc was wrong");
+	assert_eq!(&bytes_to_string(&encoded_bytes[120 .. 122]), "EB 03", "Encoding of relaxed JMP was wrong");
+	assert_eq!(&bytes_to_string(&encoded_bytes[125 .. 126]), "C3", "code after relaxed jumps was not moved");
+	
+	let mut emitted_label = [0u8; 8];
+	emitted_label.copy_from_slice(&encoded_bytes[126 ..]);
+	assert_eq!(u64::from_le_bytes(emitted_label) as usize, encoded_bytes.as_ptr() as usize + 125, "emitted label was not relocated");
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn sections()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	let cold = instruction_stream.create_section(16, 4096).expect("Could not create section");
+	let entry = instruction_stream.create_and_attach_label();
+	let error = instruction_stream.create_label();
+	let done = instruction_stream.create_label();
+	
+	instruction_stream.jmp_Label_1(error);
+	instruction_stream.attach_label(done);
+	instruction_stream.ret();
+	
+	instruction_stream.switch_section(cold);
+	instruction_stream.attach_label(error);
+	instruction_stream.mov_Register64Bit_Immediate64Bit(RAX, 7u64.into());
+	instruction_stream.jmp_Label_1(done);
+	instruction_stream.switch_section(SectionId::Default);
+	
+	let (jit_function, _hints) = unsafe { instruction_stream.finish_with_entry::<unsafe extern "C" fn() -> u64>(entry) };
+	
+	assert_eq!(unsafe { (*jit_function)() }, 7, "function result was not 7");
+}
+
+#[test]
+pub fn emitted_bytes_and_hexdump()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	assert!(instruction_stream.emitted_bytes().is_empty(), "nothing should have been emitted");
+	assert_eq!(&instruction_stream.hexdump(), "", "hexdump of nothing should be empty");
+	
+	instruction_stream.push_stack_frame();
+	
+	assert_eq!(instruction_stream.emitted_bytes(), &[0x55, 0x48, 0x8B, 0xEC], "emitted bytes were wrong");
+	assert_eq!(&instruction_stream.hexdump(), "55 48 8B EC", "hexdump was wrong");
+}
+
+#[test]
+pub fn avx_transitions()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.vzeroupper();
+	instruction_stream.vzeroall();
+	
+	assert_eq!(&instruction_stream.hexdump(), "C5 F8 77 C5 FC 77", "Encoding of VZEROUPPER and VZEROALL was wrong");
+}
+
+#[test]
+pub fn processor_state_management()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.fxsave_Contiguous512ByteMemory(Contiguous512ByteMemory::base_64(RDI));
+	instruction_stream.fxrstor64_Contiguous512ByteMemory(Contiguous512ByteMemory::base_64(RDI));
+	instruction_stream.xsave_Any32BitMemory(Any32BitMemory::displacement(0x1000i32.into()));
+	instruction_stream.xrstor64_Any64BitMemory(Any64BitMemory::base_64(RSI));
+	
+	assert_eq!(&instruction_stream.hexdump(), "0F AE 07 48 0F AE 0F 0F AE 24 25 00 10 00 00 48 0F AE 2E", "Encoding of FXSAVE, FXRSTOR64, XSAVE and XRSTOR64 was wrong");
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "must be aligned to 64 bytes")]
+pub fn xsave_to_misaligned_absolute_address_panics()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.xsave_Any32BitMemory(Any32BitMemory::displacement(0x1010i32.into()));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "must be aligned to 16 bytes")]
+pub fn cmpxchg16b_to_misaligned_absolute_address_panics()
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	instruction_stream.lock_cmpxchg16b(Any128BitMemory::displacement(0x1008i32.into()));
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn lock_cmpxchg16b()
+{
+	#[repr(align(16))]
+	struct Aligned([u64; 2]);
+	
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	
+	let compare_and_swap =
+	{
+		let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+		
+		// (memory: *mut u128, expected_low, expected_high, new_low, new_high) -> ZF.
+		let compare_and_swap: unsafe extern "C" fn(*mut Aligned, u64, u64, u64, u64) -> u8 = instruction_stream.quinary_function_pointer();
+		instruction_stream.push_Register64Bit_r64(RBX);
+		instruction_stream.mov_Register64Bit_Register64Bit_r64_rm64(RAX, RSI);
+		instruction_stream.mov_Register64Bit_Register64Bit_r64_rm64(RBX, RCX);
+		instruction_stream.mov_Register64Bit_Register64Bit_r64_rm64(RCX, R8);
+		let start = instruction_stream.instruction_pointer() - instruction_stream.start_instruction_pointer();
+		instruction_stream.lock_cmpxchg16b(Any128BitMemory::base_64(RDI));
+		assert_eq!(&instruction_stream.hexdump()[start * 3 ..], "F0 48 0F C7 0F", "Encoding of LOCK CMPXCHG16B was wrong");
+		instruction_stream.setz_Register8Bit(Register8Bit::AL);
+		instruction_stream.pop_Register64Bit_r64(RBX);
+		instruction_stream.ret();
+		
+		instruction_stream.finish();
+		compare_and_swap
+	};
+	
+	let mut value = Aligned([1, 2]);
+	assert_eq!(unsafe { compare_and_swap(&mut value, 1, 2, 3, 4) }, 1, "CMPXCHG16B did not swap");
+	assert_eq!(value.0, [3, 4], "CMPXCHG16B did not store RCX:RBX");
+	assert_eq!(unsafe { compare_and_swap(&mut value, 1, 2, 5, 6) }, 0, "CMPXCHG16B swapped");
+	assert_eq!(value.0, [3, 4], "CMPXCHG16B stored when it should not have");
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn register_width_conversions()
+{
+	assert_eq!(Register64Bit::from(Register32Bit::EAX), RAX);
+	assert_eq!(Register32Bit::from(R15), Register32Bit::R15D);
+	assert_eq!(Register16Bit::from(Register8Bit::DIL), Register16Bit::DI);
+	assert_eq!(Register8Bit::from(Register16Bit::R9W), Register8Bit::R9B);
+	
+	let register: Register64Bit = Register8Bit::SPL.into();
+	assert_eq!(register, RSP);
+	
+	for index in 0 .. 16u8
+	{
+		let register_64_bit: Register64Bit = unsafe { ::std::mem::transmute(index) };
+		let register_32_bit = Register32Bit::from(register_64_bit);
+		let register_16_bit = Register16Bit::from(register_32_bit);
+		let register_8_bit = Register8Bit::from(register_16_bit);
+		
+		assert_eq!(register_32_bit.index(), index);
+		assert_eq!(register_16_bit.index(), index);
+		assert_eq!(register_8_bit.index(), index);
+		assert_eq!(Register64Bit::from(register_8_bit), register_64_bit);
+		
+		assert_eq!(register_64_bit.requires_rex_bit(), index > 7);
+		assert_eq!(register_8_bit.requires_rex_bit(), index > 7);
+		assert_eq!(register_8_bit.requires_rex_byte(), index > 3);
+		assert!(!register_32_bit.requires_rex_byte());
+	}
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+pub fn compute_rex()
+{
+	let register = |index: u8| -> Register64Bit { unsafe { ::std::mem::transmute(index) } };
+	let register_8_bit = |index: u8| -> Register8Bit { unsafe { ::std::mem::transmute(index) } };
+	let w_bit = |w: bool| if w { 0x48 } else { 0x00 };
+	let expected = |rex: u8| if rex == 0x00 { None } else { Some(rex) };
+	
+	for w in [false, true].iter().cloned()
+	{
+		for reg in 0 .. 16u8
+		{
+			let r_bit = if reg > 7 { 0x44 } else { 0x00 };
+			let reg_8_bit_rex = if reg > 3 { 0x40 } else { 0x00 };
+			
+			for rm in 0 .. 16u8
+			{
+				let b_bit = if rm > 7 { 0x41 } else { 0x00 };
+				let rm_8_bit_rex = if rm > 3 { 0x40 } else { 0x00 };
+				
+				assert_eq!(InstructionStream::compute_rex(w, register(reg), register(rm)), expected(w_bit(w) | r_bit | b_bit), "w {} reg {} rm {}", w, reg, rm);
+				assert_eq!(InstructionStream::compute_rex(w, Register32Bit::from(register(reg)), Register32Bit::from(register(rm))), expected(w_bit(w) | r_bit | b_bit), "w {} reg {} rm {} (32-bit)", w, reg, rm);
+				assert_eq!(InstructionStream::compute_rex(w, register_8_bit(reg), register_8_bit(rm)), expected(w_bit(w) | r_bit | b_bit | reg_8_bit_rex | rm_8_bit_rex), "w {} reg {} rm {} (8-bit)", w, reg, rm);
+				
+				assert_eq!(InstructionStream::compute_rex(w, register(reg), Any64BitMemory::base_64(register(rm))), expected(w_bit(w) | r_bit | b_bit), "w {} reg {} base {}", w, reg, rm);
+				
+				if rm != 4
+				{
+					let x_bit = if rm > 7 { 0x42 } else { 0x00 };
+					assert_eq!(InstructionStream::compute_rex(w, register(reg), Any64BitMemory::index_64(register(rm))), expected(w_bit(w) | r_bit | x_bit), "w {} reg {} index {}", w, reg, rm);
+					assert_eq!(InstructionStream::compute_rex(w, register(reg), Any64BitMemory::base_64_index_64(R13, register(rm))), expected(w_bit(w) | r_bit | 0x41 | x_bit), "w {} reg {} base R13 index {}", w, reg, rm);
+				}
+			}
+			
+			assert_eq!(InstructionStream::compute_rex(w, register(reg), Any64BitMemory::displacement(0i32.into())), expected(w_bit(w) | r_bit), "w {} reg {} displacement", w, reg);
+			assert_eq!(InstructionStream::compute_rex(w, register(reg), Any64BitMemory::relative_instruction_pointer_relative()), expected(w_bit(w) | r_bit), "w {} reg {} RIP-relative", w, reg);
+		}
+	}
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
 pub fn validate_that_rust_follows_the_system_v_abi_for_bool()
 {
-	let mut map = ExecutableAnonymousMemoryMap::new(4096, false).expect("Could not anonymously mmap");
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
 	
 	let false_function_pointer =
 	{
@@ -88,7 +2649,7 @@ pub fn validate_that_rust_follows_the_system_v_abi_for_bool()
 	// See AMD64 ABI 1.0 – August 13, 2018 – 8:25, page 22, third-to-last paragraph and footnote 16.
 	// In essence, a _Bool should be interpreted only from the bottom 8 bits.
 	
-	let mut map = ExecutableAnonymousMemoryMap::new(4096, false).expect("Could not anonymously mmap");
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
 	
 	let false_function_pointer =
 	{
@@ -109,9 +2670,10 @@ pub fn validate_that_rust_follows_the_system_v_abi_for_bool()
 }
 
 #[test]
+#[cfg(target_arch = "x86_64")]
 pub fn validate_that_rust_follows_the_system_v_abi_for_u128()
 {
-	let mut map = ExecutableAnonymousMemoryMap::new(4096, false).expect("Could not anonymously mmap");
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
 	
 	let u128_function_pointer: unsafe extern "C" fn() -> u128 =
 	{
@@ -185,7 +2747,7 @@ pub fn validate_that_rust_follows_the_system_v_abi_for_u128()
 #[test]
 pub fn emit()
 {
-	let mut map = ExecutableAnonymousMemoryMap::new(4096, false).unwrap();
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).unwrap();
 	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
 	
 	let offset: usize = 64;
@@ -0,0 +1,85 @@
+// This file is part of assembler. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT. No part of predicator, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of assembler. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/assembler/master/COPYRIGHT.
+
+
+//! Disassembles emitted instructions with `iced-x86` and checks they are what was asked for.
+
+
+use super::super::ExecutableAnonymousMemoryMap;
+use super::super::InstructionStream;
+use super::super::InstructionStreamHints;
+use super::super::mnemonic_parameter_types::memory::*;
+use super::super::mnemonic_parameter_types::registers::*;
+use ::iced_x86::Decoder;
+use ::iced_x86::DecoderOptions;
+use ::iced_x86::Formatter;
+use ::iced_x86::IntelFormatter;
+
+
+#[test]
+pub fn general_purpose()
+{
+	use self::Register64Bit::*;
+	
+	assert_disassembles_to(|instruction_stream| instruction_stream.mov_Register64Bit_Register64Bit_rm64_r64(RAX, RBX), "mov rax,rbx");
+	assert_disassembles_to(|instruction_stream| instruction_stream.mov_Register64Bit_Register64Bit_rm64_r64(R8, R15), "mov r8,r15");
+	assert_disassembles_to(|instruction_stream| instruction_stream.mov_Register64Bit_Immediate64Bit(RCX, 0x1234_5678_9ABC_DEF0u64.into()), "mov rcx,123456789ABCDEF0h");
+	assert_disassembles_to(|instruction_stream| instruction_stream.add_Register64Bit_Immediate8Bit(RSP, 8i8.into()), "add rsp,8");
+	assert_disassembles_to(|instruction_stream| instruction_stream.imul_Register64Bit_Register64Bit_Immediate32Bit(RDX, R9, 1000i32.into()), "imul rdx,r9,3E8h");
+	assert_disassembles_to(|instruction_stream| instruction_stream.lea_Register64Bit_Any64BitMemory(RAX, Any64BitMemory::base_64_index_64_scale_displacement(RDI, R12, IndexScale::x8, 16i32.into())), "lea rax,[rdi+r12*8+10h]");
+	assert_disassembles_to(|instruction_stream| instruction_stream.lea_compute(RAX, RBX, RCX, IndexScale::x4, 8i32.into()), "lea rax,[rbx+rcx*4+8]");
+	assert_disassembles_to(|instruction_stream| instruction_stream.lea_compute(RDX, RBP, RCX, IndexScale::x2, 0i32.into()), "lea rdx,[rbp+rcx*2]");
+	assert_disassembles_to(|instruction_stream| instruction_stream.lea_compute(RDX, R13, R8, IndexScale::x1, (-1i32).into()), "lea rdx,[r13+r8-1]");
+	assert_disassembles_to(|instruction_stream| instruction_stream.ud2(), "ud2");
+	assert_disassembles_to(|instruction_stream| instruction_stream.int_Three(), "int3");
+	assert_disassembles_to(|instruction_stream| instruction_stream.push_Register64Bit_r64(R13), "push r13");
+	assert_disassembles_to(|instruction_stream| instruction_stream.xor_Register32Bit_Register32Bit(Register32Bit::EAX, Register32Bit::EAX), "xor eax,eax");
+	assert_disassembles_to(|instruction_stream| instruction_stream.mov_Register16Bit_Register16Bit(Register16Bit::AX, Register16Bit::R10W), "mov ax,r10w");
+	assert_disassembles_to(|instruction_stream| instruction_stream.mov_Register8Bit_Register8Bit(Register8Bit::SIL, Register8Bit::R9B), "mov sil,r9b");
+	assert_disassembles_to(|instruction_stream| instruction_stream.mov_RegisterHigh8BitsOf16Bits_Register8Bit(RegisterHigh8BitsOf16Bits::AH, Register8Bit::BL), "mov ah,bl");
+}
+
+#[test]
+pub fn mmx()
+{
+	use self::MMRegister::*;
+	
+	assert_disassembles_to(|instruction_stream| instruction_stream.movq_MMRegister_MMRegister(MM4, MM1), "movq mm4,mm1");
+	assert_disassembles_to(|instruction_stream| instruction_stream.paddd_MMRegister_MMRegister(MM7, MM4), "paddd mm7,mm4");
+}
+
+#[test]
+pub fn sse_and_avx()
+{
+	use self::XMMRegister::*;
+	use self::YMMRegister::*;
+	
+	assert_disassembles_to(|instruction_stream| instruction_stream.addps_XMMRegister_XMMRegister(XMM1, XMM14), "addps xmm1,xmm14");
+	assert_disassembles_to(|instruction_stream| instruction_stream.movdqa_XMMRegister_XMMRegister(XMM8, XMM2), "movdqa xmm8,xmm2");
+	assert_disassembles_to(|instruction_stream| instruction_stream.vaddps_YMM_YMM_YMM(YMM0, YMM9, YMM15), "vaddps ymm0,ymm9,ymm15");
+	assert_disassembles_to(|instruction_stream| instruction_stream.vaddps_YMM_YMM_Any256BitMemory(YMM3, YMM4, Any256BitMemory::base_64(Register64Bit::R13)), "vaddps ymm3,ymm4,[r13]");
+}
+
+fn assert_disassembles_to(emit_instructions: impl FnOnce(&mut InstructionStream), expected: &str)
+{
+	let mut map = ExecutableAnonymousMemoryMap::new(4096, false, true).expect("Could not anonymously mmap");
+	let mut instruction_stream = map.instruction_stream(&InstructionStreamHints::default());
+	
+	emit_instructions(&mut instruction_stream);
+	
+	let encoded_bytes = instruction_stream.emitted_bytes();
+	let mut decoder = Decoder::with_ip(64, encoded_bytes, 0, DecoderOptions::NONE);
+	let mut formatter = IntelFormatter::new();
+	
+	let mut disassembly = Vec::new();
+	for instruction in &mut decoder
+	{
+		assert!(!instruction.is_invalid(), "Could not disassemble '{}' (expected '{}')", instruction_stream.hexdump(), expected);
+		
+		let mut output = String::new();
+		formatter.format(&instruction, &mut output);
+		disassembly.push(output);
+	}
+	
+	assert_eq!(disassembly.join("; "), expected, "Disassembly of '{}' was wrong", instruction_stream.hexdump());
+}